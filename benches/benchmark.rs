@@ -14,7 +14,7 @@
  * limitations under the License.
  */
 
-use criterion::{criterion_group, criterion_main, Criterion};
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
 use grex::RegExpBuilder;
 use itertools::Itertools;
 use std::fs::File;
@@ -131,6 +131,30 @@ fn benchmark_grex_with_verbose_mode(c: &mut Criterion) {
     });
 }
 
+/// Creates all numbers with the given count of digits, padded with leading zeros.
+/// Their DFA has many states before but only few states after minimization.
+fn create_padded_numbers(digit_count: u32) -> Vec<String> {
+    (0..10_usize.pow(digit_count))
+        .map(|number| format!("{:0width$}", number, width = digit_count as usize))
+        .collect_vec()
+}
+
+fn benchmark_grex_with_large_inputs(c: &mut Criterion) {
+    let mut group = c.benchmark_group("grex with large inputs");
+    group.sample_size(10);
+
+    for digit_count in [4, 5, 6] {
+        let test_cases = create_padded_numbers(digit_count);
+        group.bench_with_input(
+            BenchmarkId::from_parameter(test_cases.len()),
+            &test_cases,
+            |bencher, test_cases| bencher.iter(|| RegExpBuilder::from(test_cases).build()),
+        );
+    }
+
+    group.finish();
+}
+
 criterion_group!(
     benches,
     benchmark_grex_with_default_settings,
@@ -142,7 +166,8 @@ criterion_group!(
     benchmark_grex_with_conversion_of_whitespace,
     benchmark_grex_with_conversion_of_non_whitespace,
     benchmark_grex_with_case_insensitive_matching,
-    benchmark_grex_with_verbose_mode
+    benchmark_grex_with_verbose_mode,
+    benchmark_grex_with_large_inputs
 );
 
 criterion_main!(benches);
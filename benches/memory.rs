@@ -0,0 +1,108 @@
+/*
+ * Copyright © 2019-today Peter M. Stahl pemistahl@gmail.com
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either expressed or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Measures the peak heap memory needed to build regular expressions from test cases
+//! whose minimized DFA still has many states. Run with `cargo bench --bench memory`.
+//!
+//! Before the states were eliminated sparsely, a dense matrix of subexpressions between
+//! all pairs of states was allocated. The following peak memory was measured with it and
+//! with the sparse elimination on the same random words. From 6000 words on, the dense
+//! matrix alone would have taken 11.4 GiB and 25.9 GiB, so its allocation failed.
+//!
+//! ```text
+//! test cases  minimized states  dense matrix (MiB)  sparse (MiB)
+//!        250              1224                59.0           1.5
+//!        500              2241               195.2           2.8
+//!       1000              4015               621.9           5.1
+//!       2000              7104              1938.7           9.2
+//!       6000             17469       out of memory          24.7
+//!      10000             26389       out of memory          37.3
+//! ```
+
+use grex::RegExpBuilder;
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Instant;
+
+struct PeakMemoryAllocator;
+
+static CURRENT_MEMORY: AtomicUsize = AtomicUsize::new(0);
+static PEAK_MEMORY: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for PeakMemoryAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            let current = CURRENT_MEMORY.fetch_add(layout.size(), Ordering::SeqCst) + layout.size();
+            PEAK_MEMORY.fetch_max(current, Ordering::SeqCst);
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        CURRENT_MEMORY.fetch_sub(layout.size(), Ordering::SeqCst);
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: PeakMemoryAllocator = PeakMemoryAllocator;
+
+/// Creates random lowercase words of equal length. As they hardly share any prefixes
+/// or suffixes, their minimized DFA has almost as many states as characters.
+fn create_random_words(word_count: usize, word_length: usize) -> Vec<String> {
+    let mut seed = 42_u64;
+    (0..word_count)
+        .map(|_| {
+            (0..word_length)
+                .map(|_| {
+                    seed = seed
+                        .wrapping_mul(6364136223846793005)
+                        .wrapping_add(1442695040888963407);
+                    char::from(b'a' + ((seed >> 33) % 26) as u8)
+                })
+                .collect()
+        })
+        .collect()
+}
+
+fn main() {
+    println!(
+        "{:>10} {:>16} {:>18} {:>12}",
+        "test cases", "minimized states", "peak memory (MiB)", "time (ms)"
+    );
+
+    for word_count in [250, 500, 1000, 2000, 6000, 10000] {
+        let test_cases = create_random_words(word_count, 8);
+
+        let baseline_memory = CURRENT_MEMORY.load(Ordering::SeqCst);
+        PEAK_MEMORY.store(baseline_memory, Ordering::SeqCst);
+        let start = Instant::now();
+
+        let report = RegExpBuilder::from(&test_cases).build_report();
+
+        let elapsed = start.elapsed();
+        let peak_memory = PEAK_MEMORY.load(Ordering::SeqCst) - baseline_memory;
+
+        println!(
+            "{:>10} {:>16} {:>18.1} {:>12}",
+            word_count,
+            report.minimized_dfa_state_count(),
+            peak_memory as f64 / (1024.0 * 1024.0),
+            elapsed.as_millis()
+        );
+    }
+}
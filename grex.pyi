@@ -13,23 +13,80 @@
 # See the License for the specific language governing permissions and
 # limitations under the License.
 
-from typing import List
+import re
+
+from typing import BinaryIO, Iterable, List, TextIO, Union
+
+TestCases = Union[Iterable[Union[str, bytes, bytearray]], TextIO, BinaryIO]
+
+
+class RegExpMetadata:
+    """This class contains a regular expression for Python's `re` module
+    together with information about how it has been generated.
+
+    It is an immutable extension class rather than a dataclass. Its attributes are
+    read-only and two instances are equal if all their attributes are equal,
+    but functions of module `dataclasses` such as `asdict` cannot be applied to it.
+
+    Attributes:
+        pattern (str): The regular expression without inline flags
+        flags (int): The flags of module `re` to compile the pattern with
+        test_case_count (int): The number of unique test cases
+        dfa_state_count (int): The number of states of the DFA before minimization
+        minimized_dfa_state_count (int): The number of states of the DFA after minimization
+        is_fallback_taken (bool): Whether the pattern is a plain alternation of the test cases
+        generalizations (list[str]): The generalizations applied to stay within the maximum length
+    """
+
+    pattern: str
+    flags: int
+    test_case_count: int
+    dfa_state_count: int
+    minimized_dfa_state_count: int
+    is_fallback_taken: bool
+    generalizations: List[str]
 
 
 class RegExpBuilder:
     """This class builds regular expressions from user-provided test cases."""
 
     @classmethod
-    def from_test_cases(cls, test_cases: List[str]) -> "RegExpBuilder":
+    def from_test_cases(cls, test_cases: TestCases, encoding: str = "utf-8") -> "RegExpBuilder":
         """Specify the test cases to build the regular expression from.
 
         The test cases need not be sorted because `RegExpBuilder` sorts them internally.
+        They are taken one by one from any iterable, such as a list, a generator or
+        a pandas series, without collecting them into a list first. Duplicates are dropped.
+
+        Test cases of type `bytes` or `bytearray` are decoded with the given encoding.
+        If a file object is given, each of its lines is a test case, without the line ending.
 
         Args:
-            test_cases (list[str]): The list of test cases
+            test_cases (Iterable[str | bytes | bytearray]): The test cases or a file object
+            encoding (str): Either 'utf-8' or 'latin-1', defaults to 'utf-8'
 
         Raises:
-            ValueError: if `test_cases` is empty
+            ValueError: if `test_cases` is empty, if `encoding` is not supported
+                or if a test case cannot be decoded
+            TypeError: if `test_cases` is not iterable or contains items of other types
+        """
+
+    def with_negative_examples(
+        self, negative_test_cases: TestCases, encoding: str = "utf-8"
+    ) -> "RegExpBuilder":
+        """Specify negative test cases which must not be matched by the regular expression.
+
+        Conversions which generalize the test cases, such as the conversion to character classes,
+        are backed off wherever they would lead to a match of one of the negative test cases.
+        The negative test cases are accepted in the same forms as in `from_test_cases`.
+
+        Args:
+            negative_test_cases (Iterable[str | bytes | bytearray]): The negative test cases or a file object
+            encoding (str): Either 'utf-8' or 'latin-1', defaults to 'utf-8'
+
+        Raises:
+            ValueError: if `encoding` is not supported or if a negative test case cannot be decoded
+            TypeError: if `negative_test_cases` is not iterable or contains items of other types
         """
 
     def with_conversion_of_digits(self) -> "RegExpBuilder":
@@ -82,12 +139,66 @@ class RegExpBuilder:
         Non-words which are also non-space characters are converted to `\W`.
         """
 
+    def with_conversion_of_general_categories(self) -> "RegExpBuilder":
+        """Convert any character to the character class of its Unicode general category,
+        e.g. `A` to `\p{Lu}` and `!` to `\p{Po}`. Control, format, private use
+        and unassigned characters are not converted.
+
+        This method takes precedence over `with_conversion_of_scripts` and all remaining
+        character class conversions except for `with_conversion_of_digits` and
+        `with_conversion_of_whitespace` if both are set.
+
+        Module `re` does not support general categories, so `compile` and `build_with_metadata`
+        raise an error. The expression returned by `build` can be used with module `regex`.
+        """
+
+    def with_conversion_of_scripts(self) -> "RegExpBuilder":
+        """Convert any character to the character class of its Unicode script,
+        e.g. `α` to `\p{Greek}` and `漢` to `\p{Han}`. Characters shared by several scripts,
+        such as ASCII digits and punctuation, are not converted.
+
+        This method takes precedence over `with_conversion_of_words` and all remaining
+        character class conversions except for `with_conversion_of_digits`,
+        `with_conversion_of_whitespace` and `with_conversion_of_general_categories` if both are set.
+
+        Module `re` does not support scripts, so `compile` and `build_with_metadata`
+        raise an error. The expression returned by `build` can be used with module `regex`.
+        """
+
     def with_conversion_of_repetitions(self) -> "RegExpBuilder":
         """Detect repeated non-overlapping substrings and to convert them to `{min,max}` quantifier notation."""
 
+    def with_conversion_of_numeric_ranges(self) -> "RegExpBuilder":
+        """Convert the numbers among the test cases to an expression matching exactly
+        the range between the smallest and the largest of them.
+        """
+
+    def with_positional_char_classes(self) -> "RegExpBuilder":
+        """Merge alternatives of equal length which only consist of single characters
+        into one character class per position.
+        """
+
+    def with_positional_char_class_tolerance(self, factor: int) -> "RegExpBuilder":
+        """Specify how many strings the character classes inferred by `with_positional_char_classes`
+        may match for each string matched by the merged alternatives.
+
+        If the tolerance is not explicitly set with this method, a default value of 4 will be used.
+
+        Args:
+            factor (int): The maximum number of strings matched per merged alternative
+
+        Raises:
+            ValueError: if `factor` is zero
+        """
+
     def with_case_insensitive_matching(self) -> "RegExpBuilder":
         """Enable case-insensitive matching of test cases so that letters match both upper and lower case."""
 
+    def with_case_insensitive_char_classes(self) -> "RegExpBuilder":
+        """Merge test cases which only differ in the case of some letters by converting
+        exactly these letters to character classes such as `[hH]`.
+        """
+
     def with_capturing_groups(self) -> "RegExpBuilder":
         """Replace non-capturing groups with capturing ones."""
 
@@ -117,6 +228,19 @@ class RegExpBuilder:
             ValueError: if `length` is zero
         """
 
+    def with_max_length(self, length: int) -> "RegExpBuilder":
+        """Specify the maximum number of characters of the resulting regular expression.
+
+        If the exact regular expression is longer, it is generalized step by step,
+        e.g. by converting digits to `\d`, until it fits.
+
+        Args:
+            length (int): The maximum length of the regular expression
+
+        Raises:
+            ValueError: if `length` is zero
+        """
+
     def with_escaping_of_non_ascii_chars(self, use_surrogate_pairs: bool) -> "RegExpBuilder":
         """Convert non-ASCII characters to unicode escape sequences.
 
@@ -149,4 +273,51 @@ class RegExpBuilder:
         """
 
     def build(self) -> str:
-        """Build the actual regular expression using the previously given settings."""
+        """Build the actual regular expression using the previously given settings.
+
+        Raises:
+            ValueError: if some negative test cases cannot be excluded
+                or if the maximum length cannot be reached
+        """
+
+    def build_many(
+        self, test_case_collections: Iterable[TestCases], encoding: str = "utf-8"
+    ) -> List[str]:
+        """Build one regular expression for each of the given collections of test cases,
+        using the previously given settings. The test cases of this builder itself are ignored.
+
+        The regular expressions are built in parallel on as many threads as there are CPU cores.
+        Each collection of test cases is accepted in the same forms as in `from_test_cases`.
+
+        Args:
+            test_case_collections (Iterable[Iterable[str | bytes | bytearray]]): The collections of test cases
+            encoding (str): Either 'utf-8' or 'latin-1', defaults to 'utf-8'
+
+        Raises:
+            ValueError: if a collection of test cases is empty or under the same conditions as `build`
+            TypeError: if a collection of test cases is not iterable or contains items of other types
+        """
+
+    def compile(self) -> re.Pattern[str]:
+        """Build the regular expression using the previously given settings
+        and compile it with module `re`.
+
+        The expression follows the semantics of module `re`, e.g. it is anchored
+        at the end with `\Z` because `$` also matches before a final line break.
+        Case-insensitive matching and verbose mode are passed as `re.IGNORECASE`
+        and `re.VERBOSE` instead of inline flags.
+
+        Raises:
+            ValueError: if some negative test cases cannot be excluded,
+                if the maximum length cannot be reached or if a setting is not supported by module `re`
+        """
+
+    def build_with_metadata(self) -> RegExpMetadata:
+        """Build the regular expression for module `re` using the previously given settings
+        and return it together with information about how it has been generated.
+
+        The pattern is the same as the one compiled by `compile`.
+
+        Raises:
+            ValueError: under the same conditions as `compile`
+        """
@@ -0,0 +1,211 @@
+/*
+ * Copyright © 2019-today Peter M. Stahl pemistahl@gmail.com
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either expressed or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::cluster::is_script_name;
+use crate::expression::Expression;
+use crate::grapheme::Grapheme;
+use crate::quantifier::Quantifier;
+use std::collections::BTreeSet;
+
+/// This enum represents the syntax tree of a generated regular expression.
+///
+/// Anchors and flags are not part of the tree, they follow from the settings
+/// of [`RegExpBuilder`](crate::RegExpBuilder) alone.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Ast {
+    /// Matches any one of the contained nodes.
+    Alternation(Vec<Ast>),
+
+    /// Matches a single character from the given character class.
+    Class(CharClass),
+
+    /// Matches the contained nodes one after another.
+    Concat(Vec<Ast>),
+
+    /// Matches the given string literally, it is not escaped.
+    Literal(String),
+
+    /// Matches the contained node repeatedly. A missing maximum means no upper bound.
+    Repeat {
+        node: Box<Ast>,
+        min: u32,
+        max: Option<u32>,
+    },
+}
+
+/// This enum specifies the character classes which may occur in the syntax tree.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum CharClass {
+    /// Any of the given characters, written as `[...]`.
+    Set(BTreeSet<char>),
+
+    /// Any Unicode decimal digit, written as `\d`.
+    Digit,
+
+    /// Any character which is not a Unicode decimal digit, written as `\D`.
+    NonDigit,
+
+    /// Any Unicode whitespace character, written as `\s`.
+    Whitespace,
+
+    /// Any character which is not a Unicode whitespace character, written as `\S`.
+    NonWhitespace,
+
+    /// Any Unicode word character, written as `\w`.
+    Word,
+
+    /// Any character which is not a Unicode word character, written as `\W`.
+    NonWord,
+
+    /// Any character of the given Unicode general category, written as `\p{Lu}`.
+    GeneralCategory(String),
+
+    /// Any character of the given Unicode script, written as `\p{Greek}`.
+    Script(String),
+}
+
+impl Ast {
+    pub(crate) fn from(expr: &Expression) -> Self {
+        match expr {
+            Expression::Alternation(options, _, _, _) => {
+                Ast::Alternation(options.iter().map(Ast::from).collect())
+            }
+            Expression::CharacterClass(char_set, _) => Ast::Class(CharClass::Set(char_set.clone())),
+            Expression::Concatenation(expr1, expr2, _, _, _) => {
+                Self::concat(vec![Ast::from(expr1), Ast::from(expr2)])
+            }
+            Expression::Literal(cluster, _, _) => Self::concat(
+                cluster
+                    .graphemes()
+                    .iter()
+                    .map(Self::from_grapheme)
+                    .collect(),
+            ),
+            Expression::Repetition(expr, quantifier, _, _, _) => Ast::Repeat {
+                node: Box::new(Ast::from(expr)),
+                min: 0,
+                max: match quantifier {
+                    Quantifier::KleeneStar => None,
+                    Quantifier::QuestionMark => Some(1),
+                },
+            },
+        }
+    }
+
+    fn from_grapheme(grapheme: &Grapheme) -> Self {
+        let node = if grapheme.has_repetitions() {
+            Self::concat(
+                grapheme
+                    .repetitions
+                    .iter()
+                    .map(Self::from_grapheme)
+                    .collect(),
+            )
+        } else if grapheme.is_case_class {
+            // The class consists of the brackets and both cases of a letter, e.g. `[hH]`.
+            let char_set = grapheme.value().chars().skip(1).take(2).collect();
+            Ast::Class(CharClass::Set(char_set))
+        } else {
+            Self::concat(
+                grapheme
+                    .chars()
+                    .iter()
+                    .flat_map(|it| Self::split(it))
+                    .collect(),
+            )
+        };
+
+        if grapheme.minimum() == 1 && grapheme.maximum() == 1 {
+            node
+        } else {
+            Ast::Repeat {
+                node: Box::new(node),
+                min: grapheme.minimum(),
+                max: Some(grapheme.maximum()),
+            }
+        }
+    }
+
+    /// Splits a string of graphemes into literals and the character classes
+    /// they have been converted to.
+    fn split(s: &str) -> Vec<Ast> {
+        let mut nodes = vec![];
+        let mut chars = s.chars();
+
+        while let Some(c) = chars.next() {
+            let class = match c {
+                '\\' => match chars.clone().next() {
+                    Some('d') => Some(CharClass::Digit),
+                    Some('D') => Some(CharClass::NonDigit),
+                    Some('s') => Some(CharClass::Whitespace),
+                    Some('S') => Some(CharClass::NonWhitespace),
+                    Some('w') => Some(CharClass::Word),
+                    Some('W') => Some(CharClass::NonWord),
+                    Some('p') => {
+                        let name = chars
+                            .clone()
+                            .skip(2)
+                            .take_while(|&it| it != '}')
+                            .collect::<String>();
+                        // The closing brace is skipped together with the class letter below.
+                        chars.nth(name.chars().count() + 1);
+                        if is_script_name(&name) {
+                            Some(CharClass::Script(name))
+                        } else {
+                            Some(CharClass::GeneralCategory(name))
+                        }
+                    }
+                    _ => None,
+                },
+                _ => None,
+            };
+            match class {
+                Some(class) => {
+                    chars.next();
+                    nodes.push(Ast::Class(class));
+                }
+                None => nodes.push(Ast::Literal(c.to_string())),
+            }
+        }
+
+        nodes
+    }
+
+    /// Flattens nested concatenations and merges adjacent literals.
+    fn concat(nodes: Vec<Ast>) -> Self {
+        let mut flattened_nodes: Vec<Ast> = vec![];
+
+        for node in nodes {
+            let inner_nodes = match node {
+                Ast::Concat(inner_nodes) => inner_nodes,
+                _ => vec![node],
+            };
+            for inner_node in inner_nodes {
+                match (flattened_nodes.last_mut(), inner_node) {
+                    (_, Ast::Literal(s)) if s.is_empty() => {}
+                    (Some(Ast::Literal(previous)), Ast::Literal(s)) => previous.push_str(&s),
+                    (_, inner_node) => flattened_nodes.push(inner_node),
+                }
+            }
+        }
+
+        match flattened_nodes.len() {
+            0 => Ast::Literal(String::new()),
+            1 => flattened_nodes.pop().unwrap(),
+            _ => Ast::Concat(flattened_nodes),
+        }
+    }
+}
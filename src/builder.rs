@@ -14,10 +14,18 @@
  * limitations under the License.
  */
 
+use crate::ast::Ast;
 use crate::config::RegExpConfig;
+use crate::dialect::Dialect;
+use crate::error::GrexError;
+use crate::generalization::Generalization;
+use crate::incremental::IncrementalRegExpBuilder;
 use crate::regexp::RegExp;
+use crate::report::Report;
 use itertools::Itertools;
-use std::io::ErrorKind;
+use std::collections::BTreeSet;
+use std::fs::File;
+use std::io::{BufRead, BufReader};
 use std::path::PathBuf;
 
 pub(crate) const MISSING_TEST_CASES_MESSAGE: &str =
@@ -29,12 +37,25 @@ pub(crate) const MINIMUM_REPETITIONS_MESSAGE: &str =
 pub(crate) const MINIMUM_SUBSTRING_LENGTH_MESSAGE: &str =
     "Minimum substring length must be greater than zero";
 
+pub(crate) const POSITIONAL_CHAR_CLASS_TOLERANCE_MESSAGE: &str =
+    "Tolerance of positional character classes must be greater than zero";
+
+pub(crate) const MAXIMUM_LENGTH_MESSAGE: &str =
+    "Maximum length of the regular expression must be greater than zero";
+
+pub(crate) const CONFLICTING_TEST_CASES_MESSAGE: &str =
+    "Some negative test cases cannot be excluded because they are matched by the test cases themselves";
+
 /// This struct builds regular expressions from user-provided test cases.
 #[derive(Clone)]
 #[cfg_attr(feature = "python", pyo3::prelude::pyclass)]
 pub struct RegExpBuilder {
     pub(crate) test_cases: Vec<String>,
+    pub(crate) negative_test_cases: Vec<String>,
     pub(crate) config: RegExpConfig,
+    /// The settings extended by the generalizations needed to stay within the maximum length.
+    /// They are kept here because the built regular expression borrows them.
+    generalized_config: Option<RegExpConfig>,
 }
 
 impl RegExpBuilder {
@@ -43,14 +64,102 @@ impl RegExpBuilder {
     /// The test cases need not be sorted because `RegExpBuilder` sorts them internally.
     ///
     /// ⚠ Panics if `test_cases` is empty.
+    /// Use [`try_from`](Self::try_from) to handle this case as an error instead.
     pub fn from<T: Clone + Into<String>>(test_cases: &[T]) -> Self {
+        Self::try_from(test_cases).unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// Specifies the test cases to build the regular expression from.
+    ///
+    /// The test cases need not be sorted because `RegExpBuilder` sorts them internally.
+    ///
+    /// Returns [`GrexError::MissingTestCases`] if `test_cases` is empty.
+    pub fn try_from<T: Clone + Into<String>>(test_cases: &[T]) -> Result<Self, GrexError> {
         if test_cases.is_empty() {
-            panic!("{}", MISSING_TEST_CASES_MESSAGE);
+            return Err(GrexError::MissingTestCases);
         }
-        Self {
+        Ok(Self {
             test_cases: test_cases.iter().cloned().map(|it| it.into()).collect_vec(),
+            negative_test_cases: vec![],
             config: RegExpConfig::new(),
+            generalized_config: None,
+        })
+    }
+
+    /// Specifies the test cases to build the regular expression from,
+    /// taking them one by one from an iterator.
+    ///
+    /// Unlike [`try_from`](Self::try_from), the test cases need not be collected
+    /// into a slice beforehand. Duplicate test cases are dropped while iterating,
+    /// but all distinct test cases are kept in memory until the regular expression is built.
+    /// The panicking variant is provided by the [`FromIterator`] implementation.
+    ///
+    /// Returns [`GrexError::MissingTestCases`] if the iterator is empty.
+    pub fn try_from_iter<I, T>(test_cases: I) -> Result<Self, GrexError>
+    where
+        I: IntoIterator<Item = T>,
+        T: AsRef<str>,
+    {
+        let mut distinct_test_cases = BTreeSet::new();
+        for test_case in test_cases {
+            insert_test_case(&mut distinct_test_cases, test_case.as_ref());
+        }
+        Self::try_from_distinct(distinct_test_cases)
+    }
+
+    /// Specifies a reader providing test cases to build the regular expression from.
+    ///
+    /// The test cases are read line by line in the same way as
+    /// [`from_file`](Self::from_file) does, without reading the entire input into a single string.
+    /// Duplicate test cases are dropped while reading, but all distinct test cases are kept
+    /// in memory until the regular expression is built.
+    ///
+    /// An empty input results in a regular expression which matches the empty string only.
+    ///
+    /// ⚠ Panics if:
+    /// - the input cannot be read
+    /// - the input's encoding is not valid UTF-8 data
+    ///
+    /// Use [`try_from_reader`](Self::try_from_reader) to handle these cases as errors instead.
+    pub fn from_reader<R: BufRead>(reader: R) -> Self {
+        match Self::try_from_reader(reader) {
+            Ok(builder) => builder,
+            Err(GrexError::MissingTestCases) => Self::from(&[""]),
+            Err(error) => panic!("{}", error),
+        }
+    }
+
+    /// Specifies a reader providing test cases to build the regular expression from.
+    ///
+    /// The test cases are read line by line in the same way as
+    /// [`try_from_file`](Self::try_from_file) does, without reading the entire input into a single string.
+    /// Duplicate test cases are dropped while reading, but all distinct test cases are kept
+    /// in memory until the regular expression is built.
+    ///
+    /// Returns an error if:
+    /// - the input cannot be read ([`GrexError::Io`])
+    /// - the input's encoding is not valid UTF-8 data ([`GrexError::InvalidUtf8`])
+    /// - the input does not contain any test cases ([`GrexError::MissingTestCases`])
+    pub fn try_from_reader<R: BufRead>(mut reader: R) -> Result<Self, GrexError> {
+        let mut distinct_test_cases = BTreeSet::new();
+        let mut line = vec![];
+        let mut line_number = 0;
+
+        while reader.read_until(b'\n', &mut line)? > 0 {
+            line_number += 1;
+            if line.ends_with(b"\n") {
+                line.pop();
+                if line.ends_with(b"\r") {
+                    line.pop();
+                }
+            }
+            let test_case =
+                std::str::from_utf8(&line).map_err(|_| GrexError::InvalidUtf8 { line_number })?;
+            insert_test_case(&mut distinct_test_cases, test_case);
+            line.clear();
         }
+
+        Self::try_from_distinct(distinct_test_cases)
     }
 
     /// Specifies a text file containing test cases to build the regular expression from.
@@ -62,29 +171,65 @@ impl RegExpBuilder {
     /// a carriage return with a line feed (`\r\n`).
     /// The final line ending is optional.
     ///
+    /// An empty file results in a regular expression which matches the empty string only.
+    ///
     /// ⚠ Panics if:
     /// - the file cannot be found
     /// - the file's encoding is not valid UTF-8 data
     /// - the file cannot be opened because of conflicting permissions
+    ///
+    /// Use [`try_from_file`](Self::try_from_file) to handle these cases as errors instead.
     pub fn from_file<T: Into<PathBuf>>(file_path: T) -> Self {
-        match std::fs::read_to_string(file_path.into()) {
-            Ok(file_content) => Self {
-                test_cases: file_content.lines().map(|it| it.to_string()).collect_vec(),
-                config: RegExpConfig::new(),
-            },
-            Err(error) => match error.kind() {
-                ErrorKind::NotFound => panic!("The specified file could not be found"),
-                ErrorKind::InvalidData => {
-                    panic!("The specified file's encoding is not valid UTF-8")
-                }
-                ErrorKind::PermissionDenied => {
-                    panic!("Permission denied: The specified file could not be opened")
-                }
-                _ => panic!("{}", error),
-            },
+        match Self::try_from_file(file_path) {
+            Ok(builder) => builder,
+            Err(GrexError::MissingTestCases) => Self::from(&[""]),
+            Err(error) => panic!("{}", error),
         }
     }
 
+    /// Specifies a text file containing test cases to build the regular expression from.
+    ///
+    /// The test cases need not be sorted because `RegExpBuilder` sorts them internally.
+    ///
+    /// Each test case needs to be on a separate line.
+    /// Lines may be ended with either a newline (`\n`) or
+    /// a carriage return with a line feed (`\r\n`).
+    /// The final line ending is optional.
+    ///
+    /// Returns an error if:
+    /// - the file cannot be found ([`GrexError::FileNotFound`])
+    /// - the file cannot be opened because of conflicting permissions
+    ///   ([`GrexError::PermissionDenied`])
+    /// - the file cannot be read for any other reason ([`GrexError::Io`])
+    /// - the file's encoding is not valid UTF-8 data ([`GrexError::InvalidUtf8`])
+    /// - the file does not contain any test cases ([`GrexError::MissingTestCases`])
+    pub fn try_from_file<T: Into<PathBuf>>(file_path: T) -> Result<Self, GrexError> {
+        let file = File::open(file_path.into())?;
+        Self::try_from_reader(BufReader::new(file))
+    }
+
+    /// Specifies test cases which the resulting regular expression must not match.
+    ///
+    /// Conversions which generalize the test cases, such as the conversion to character classes,
+    /// are backed off wherever they would lead to a match of one of the negative test cases.
+    /// Before [`build`](Self::build) returns, the resulting regular expression is verified
+    /// to match all test cases and none of the negative ones.
+    ///
+    /// ⚠ [`build`](Self::build) panics and [`try_build`](Self::try_build) returns an error
+    /// if a negative test case cannot be excluded, e.g. because it is identical
+    /// to one of the test cases.
+    pub fn with_negative_examples<T: Clone + Into<String>>(
+        &mut self,
+        negative_test_cases: &[T],
+    ) -> &mut Self {
+        self.negative_test_cases = negative_test_cases
+            .iter()
+            .cloned()
+            .map(|it| it.into())
+            .collect_vec();
+        self
+    }
+
     /// Converts any Unicode decimal digit to character class `\d`.
     ///
     /// This method takes precedence over
@@ -157,6 +302,45 @@ impl RegExpBuilder {
         self
     }
 
+    /// Converts any character to the character class of its Unicode general category,
+    /// e.g. `A` to `\p{Lu}` and `!` to `\p{Po}`. Control, format, private use and
+    /// unassigned characters are not converted. With case-insensitive matching enabled,
+    /// upper, lower and title case letters are not converted either.
+    ///
+    /// [`with_conversion_of_digits`](Self::with_conversion_of_digits) and
+    /// [`with_conversion_of_whitespace`](Self::with_conversion_of_whitespace)
+    /// take precedence over this method if both are set.
+    /// Decimal digits are converted to `\d` instead of `\p{Nd}`, spaces to `\s` instead of `\p{Zs}`.
+    ///
+    /// This method takes precedence over
+    /// [`with_conversion_of_scripts`](Self::with_conversion_of_scripts) and all remaining
+    /// character class conversions if both are set.
+    ///
+    /// ⚠ Python and POSIX regular expressions do not support general categories.
+    pub fn with_conversion_of_general_categories(&mut self) -> &mut Self {
+        self.config.is_general_category_converted = true;
+        self
+    }
+
+    /// Converts any character to the character class of its Unicode script,
+    /// e.g. `α` to `\p{Greek}` and `漢` to `\p{Han}`. Characters shared by several scripts,
+    /// such as ASCII digits and punctuation, are not converted.
+    ///
+    /// [`with_conversion_of_digits`](Self::with_conversion_of_digits),
+    /// [`with_conversion_of_whitespace`](Self::with_conversion_of_whitespace) and
+    /// [`with_conversion_of_general_categories`](Self::with_conversion_of_general_categories)
+    /// take precedence over this method if both are set.
+    ///
+    /// This method takes precedence over
+    /// [`with_conversion_of_words`](Self::with_conversion_of_words) and all remaining
+    /// character class conversions if both are set.
+    ///
+    /// ⚠ Python, .NET and POSIX regular expressions do not support scripts.
+    pub fn with_conversion_of_scripts(&mut self) -> &mut Self {
+        self.config.is_script_converted = true;
+        self
+    }
+
     /// Detects repeated non-overlapping substrings and
     /// to convert them to `{min,max}` quantifier notation.
     pub fn with_conversion_of_repetitions(&mut self) -> &mut Self {
@@ -164,6 +348,65 @@ impl RegExpBuilder {
         self
     }
 
+    /// Converts numbers in test cases which otherwise only differ in their numbers
+    /// to an expression matching exactly the range between the smallest and the largest of them,
+    /// e.g. `1980` and `2029` to `(?:19[89][0-9]|20[0-2][0-9])`.
+    ///
+    /// Only maximal runs of ASCII digits without leading zeros are treated as numbers.
+    /// Ranges which would match a negative test case are not converted.
+    pub fn with_conversion_of_numeric_ranges(&mut self) -> &mut Self {
+        self.config.is_numeric_range_converted = true;
+        self
+    }
+
+    /// Merges alternatives of equal length which only consist of single characters
+    /// into one character class per position, e.g. `a1x`, `b2x` and `c3x` to `[a-c][1-3]x`.
+    ///
+    /// The resulting expression usually matches more strings than the test cases.
+    /// How much over-generalization is acceptable is specified with
+    /// [`with_positional_char_class_tolerance`](Self::with_positional_char_class_tolerance).
+    /// Character classes which would match a negative test case are not inferred.
+    /// Neither are character classes containing non-ASCII characters if these are escaped with
+    /// [`with_escaping_of_non_ascii_chars`](Self::with_escaping_of_non_ascii_chars).
+    pub fn with_positional_char_classes(&mut self) -> &mut Self {
+        self.config.is_positional_char_class_inferred = true;
+        self
+    }
+
+    /// Specifies how many strings the character classes inferred by
+    /// [`with_positional_char_classes`](Self::with_positional_char_classes) may match
+    /// for each string matched by the merged alternatives. Alternatives are only merged
+    /// if the product of the character class sizes does not exceed this limit.
+    ///
+    /// If the tolerance is not explicitly set with this method, a default value of 4 will be used.
+    ///
+    /// ⚠ Panics if `factor` is zero.
+    /// Use [`try_with_positional_char_class_tolerance`](Self::try_with_positional_char_class_tolerance)
+    /// to handle this case as an error instead.
+    pub fn with_positional_char_class_tolerance(&mut self, factor: u32) -> &mut Self {
+        self.try_with_positional_char_class_tolerance(factor)
+            .unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// Specifies how many strings the character classes inferred by
+    /// [`with_positional_char_classes`](Self::with_positional_char_classes) may match
+    /// for each string matched by the merged alternatives. Alternatives are only merged
+    /// if the product of the character class sizes does not exceed this limit.
+    ///
+    /// If the tolerance is not explicitly set with this method, a default value of 4 will be used.
+    ///
+    /// Returns [`GrexError::InvalidPositionalCharClassTolerance`] if `factor` is zero.
+    pub fn try_with_positional_char_class_tolerance(
+        &mut self,
+        factor: u32,
+    ) -> Result<&mut Self, GrexError> {
+        if factor == 0 {
+            return Err(GrexError::InvalidPositionalCharClassTolerance);
+        }
+        self.config.positional_char_class_tolerance = factor;
+        Ok(self)
+    }
+
     /// Enables case-insensitive matching of test cases
     /// so that letters match both upper and lower case.
     pub fn with_case_insensitive_matching(&mut self) -> &mut Self {
@@ -171,6 +414,18 @@ impl RegExpBuilder {
         self
     }
 
+    /// Merges test cases which only differ in the case of some letters
+    /// by converting exactly these letters to character classes, e.g. `Hello` and `hello`
+    /// to `[hH]ello`. The remaining parts of the regular expression stay case-sensitive.
+    ///
+    /// This is an alternative to [`with_case_insensitive_matching`](Self::with_case_insensitive_matching)
+    /// for regex engines which do not support the `(?i)` flag. If both are set,
+    /// case-insensitive matching takes precedence.
+    pub fn with_case_insensitive_char_classes(&mut self) -> &mut Self {
+        self.config.is_case_insensitive_char_class_enabled = true;
+        self
+    }
+
     /// Replaces non-capturing groups with capturing ones.
     pub fn with_capturing_groups(&mut self) -> &mut Self {
         self.config.is_capturing_group_enabled = true;
@@ -183,12 +438,25 @@ impl RegExpBuilder {
     /// If the quantity is not explicitly set with this method, a default value of 1 will be used.
     ///
     /// ⚠ Panics if `quantity` is zero.
+    /// Use [`try_with_minimum_repetitions`](Self::try_with_minimum_repetitions)
+    /// to handle this case as an error instead.
     pub fn with_minimum_repetitions(&mut self, quantity: u32) -> &mut Self {
+        self.try_with_minimum_repetitions(quantity)
+            .unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// Specifies the minimum quantity of substring repetitions to be converted if
+    /// [`with_conversion_of_repetitions`](Self::with_conversion_of_repetitions) is set.
+    ///
+    /// If the quantity is not explicitly set with this method, a default value of 1 will be used.
+    ///
+    /// Returns [`GrexError::InvalidMinimumRepetitions`] if `quantity` is zero.
+    pub fn try_with_minimum_repetitions(&mut self, quantity: u32) -> Result<&mut Self, GrexError> {
         if quantity == 0 {
-            panic!("{}", MINIMUM_REPETITIONS_MESSAGE);
+            return Err(GrexError::InvalidMinimumRepetitions);
         }
         self.config.minimum_repetitions = quantity;
-        self
+        Ok(self)
     }
 
     /// Specifies the minimum length a repeated substring must have in order to be converted if
@@ -197,12 +465,62 @@ impl RegExpBuilder {
     /// If the length is not explicitly set with this method, a default value of 1 will be used.
     ///
     /// ⚠ Panics if `length` is zero.
+    /// Use [`try_with_minimum_substring_length`](Self::try_with_minimum_substring_length)
+    /// to handle this case as an error instead.
     pub fn with_minimum_substring_length(&mut self, length: u32) -> &mut Self {
+        self.try_with_minimum_substring_length(length)
+            .unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// Specifies the minimum length a repeated substring must have in order to be converted if
+    /// [`with_conversion_of_repetitions`](Self::with_conversion_of_repetitions) is set.
+    ///
+    /// If the length is not explicitly set with this method, a default value of 1 will be used.
+    ///
+    /// Returns [`GrexError::InvalidMinimumSubstringLength`] if `length` is zero.
+    pub fn try_with_minimum_substring_length(
+        &mut self,
+        length: u32,
+    ) -> Result<&mut Self, GrexError> {
         if length == 0 {
-            panic!("{}", MINIMUM_SUBSTRING_LENGTH_MESSAGE);
+            return Err(GrexError::InvalidMinimumSubstringLength);
         }
         self.config.minimum_substring_length = length;
-        self
+        Ok(self)
+    }
+
+    /// Specifies the maximum number of characters of the resulting regular expression.
+    ///
+    /// If the exact regular expression is longer, the generalizations listed in
+    /// [`Generalization`](crate::Generalization) are applied one after another
+    /// in the order of their loss of precision until it fits. Generalizations which
+    /// would not shorten the expression are skipped.
+    /// The applied generalizations are listed by [`Report::generalizations`].
+    ///
+    /// ⚠ Panics if `length` is zero.
+    /// Use [`try_with_max_length`](Self::try_with_max_length) to handle this case as an error instead.
+    /// [`build`](Self::build) panics and [`try_build`](Self::try_build) returns
+    /// [`GrexError::MaximumLengthExceeded`] if even the most generalized expression is too long.
+    pub fn with_max_length(&mut self, length: usize) -> &mut Self {
+        self.try_with_max_length(length)
+            .unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// Specifies the maximum number of characters of the resulting regular expression.
+    ///
+    /// If the exact regular expression is longer, the generalizations listed in
+    /// [`Generalization`](crate::Generalization) are applied one after another
+    /// in the order of their loss of precision until it fits. Generalizations which
+    /// would not shorten the expression are skipped.
+    /// The applied generalizations are listed by [`Report::generalizations`].
+    ///
+    /// Returns [`GrexError::InvalidMaximumLength`] if `length` is zero.
+    pub fn try_with_max_length(&mut self, length: usize) -> Result<&mut Self, GrexError> {
+        if length == 0 {
+            return Err(GrexError::InvalidMaximumLength);
+        }
+        self.config.maximum_length = Some(length);
+        Ok(self)
     }
 
     /// Converts non-ASCII characters to unicode escape sequences.
@@ -245,6 +563,19 @@ impl RegExpBuilder {
         self
     }
 
+    /// Targets the resulting regular expression at the given regex engine.
+    ///
+    /// The dialect determines the syntax of escape sequences, flags and anchors.
+    /// Characters are only converted to `\d`, `\w` and `\s` if these character classes
+    /// match them in the selected dialect, e.g. `\d` matches ASCII digits only in
+    /// [`Dialect::EcmaScript`].
+    ///
+    /// If this method is not called, [`Dialect::Rust`] is used.
+    pub fn with_dialect(&mut self, dialect: Dialect) -> &mut Self {
+        self.config.dialect = dialect;
+        self
+    }
+
     /// Provides syntax highlighting for the resulting regular expression.
     ///
     /// ⚠ This method may only be used if the resulting regular expression is meant to
@@ -258,7 +589,258 @@ impl RegExpBuilder {
     }
 
     /// Builds the actual regular expression using the previously given settings.
+    ///
+    /// ⚠ Panics if some negative test cases cannot be excluded
+    /// or if the selected dialect does not support some of the settings.
+    /// Use [`try_build`](Self::try_build) to handle this case as an error instead.
     pub fn build(&mut self) -> String {
-        RegExp::from(&mut self.test_cases, &self.config).to_string()
+        self.try_build().unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// Builds the actual regular expression using the previously given settings.
+    ///
+    /// Returns [`GrexError::ConflictingTestCases`] if some negative test cases
+    /// cannot be excluded, e.g. because they are identical to some of the test cases.
+    ///
+    /// Returns [`GrexError::UnsupportedFeature`] if the selected dialect does not support
+    /// some of the settings, e.g. verbose mode in [`Dialect::EcmaScript`].
+    pub fn try_build(&mut self) -> Result<String, GrexError> {
+        self.build_regexp().map(|regexp| regexp.to_string())
+    }
+
+    /// Builds the syntax tree of the regular expression using the previously given settings.
+    ///
+    /// The tree does not contain anchors or flags and does not depend on the selected dialect.
+    ///
+    /// ⚠ Panics under the same conditions as [`build`](Self::build).
+    /// Use [`try_build_ast`](Self::try_build_ast) to handle this case as an error instead.
+    pub fn build_ast(&mut self) -> Ast {
+        self.try_build_ast()
+            .unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// Builds the syntax tree of the regular expression using the previously given settings.
+    ///
+    /// Returns the same errors as [`try_build`](Self::try_build).
+    pub fn try_build_ast(&mut self) -> Result<Ast, GrexError> {
+        self.build_regexp().map(|regexp| regexp.to_ast())
+    }
+
+    /// Describes the regular expression built from the previously given settings in English.
+    ///
+    /// Each part of the expression is described on its own line. Nested parts are
+    /// indented in the same way as the expression in [verbose mode](Self::with_verbose_mode).
+    ///
+    /// ⚠ Panics under the same conditions as [`build`](Self::build).
+    /// Use [`try_explain`](Self::try_explain) to handle this case as an error instead.
+    pub fn explain(&mut self) -> String {
+        self.try_explain()
+            .unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// Describes the regular expression built from the previously given settings in English.
+    ///
+    /// Returns the same errors as [`try_build`](Self::try_build).
+    pub fn try_explain(&mut self) -> Result<String, GrexError> {
+        self.build_regexp().map(|regexp| regexp.to_explanation())
+    }
+
+    /// Builds the actual regular expression using the previously given settings
+    /// and returns it together with information about how it has been generated.
+    ///
+    /// ⚠ Panics under the same conditions as [`build`](Self::build).
+    /// Use [`try_build_report`](Self::try_build_report) to handle this case as an error instead.
+    pub fn build_report(&mut self) -> Report {
+        self.try_build_report()
+            .unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// Builds the actual regular expression using the previously given settings
+    /// and returns it together with information about how it has been generated.
+    ///
+    /// Returns the same errors as [`try_build`](Self::try_build).
+    pub fn try_build_report(&mut self) -> Result<Report, GrexError> {
+        self.build_regexp().map(|regexp| regexp.to_report())
+    }
+
+    /// Builds the regular expression as a pattern and flags for the `RegExp` constructor
+    /// of JavaScript, regardless of the selected dialect.
+    #[cfg(target_family = "wasm")]
+    pub(crate) fn try_build_for_javascript(&mut self) -> Result<(String, String), GrexError> {
+        let dialect = self.config.dialect;
+        self.config.dialect = Dialect::EcmaScript;
+        let result = self.build_regexp().map(|regexp| regexp.to_javascript());
+        self.config.dialect = dialect;
+        result
+    }
+
+    /// Creates an [`IncrementalRegExpBuilder`] with the current test cases and settings.
+    ///
+    /// Use it to build regular expressions repeatedly from test cases which are inserted
+    /// and removed over time, without building the DFA from scratch each time.
+    pub fn to_incremental(&self) -> IncrementalRegExpBuilder {
+        IncrementalRegExpBuilder::new(
+            &self.test_cases,
+            &self.negative_test_cases,
+            self.config.clone(),
+        )
+    }
+
+    fn try_from_distinct(test_cases: BTreeSet<String>) -> Result<Self, GrexError> {
+        if test_cases.is_empty() {
+            return Err(GrexError::MissingTestCases);
+        }
+        Ok(Self {
+            test_cases: test_cases.into_iter().collect_vec(),
+            negative_test_cases: vec![],
+            config: RegExpConfig::new(),
+            generalized_config: None,
+        })
+    }
+
+    fn build_regexp(&mut self) -> Result<RegExp<'_>, GrexError> {
+        if self.test_cases.is_empty() {
+            return Err(GrexError::MissingTestCases);
+        }
+        self.config.dialect.check_support(&self.config)?;
+
+        self.generalized_config = match self.config.maximum_length {
+            Some(maximum_length) => Some(self.generalize(maximum_length)?),
+            None => None,
+        };
+
+        RegExp::from(
+            &mut self.test_cases,
+            &self.negative_test_cases,
+            self.generalized_config.as_ref().unwrap_or(&self.config),
+        )
+    }
+
+    /// Returns the settings extended by those generalizations which are needed
+    /// for the regular expression to stay within the maximum length.
+    /// Generalizations which do not shorten the regular expression are left out.
+    fn generalize(&self, maximum_length: usize) -> Result<RegExpConfig, GrexError> {
+        let mut config = self.config.clone();
+        let mut length = self.build_without_highlighting(&config)?.chars().count();
+
+        // A generalization which does not shorten the expression on its own may do so
+        // together with later ones, such as repetitions of digits converted to `\d`,
+        // so it is kept pending and tried again along with each of them.
+        let mut pending_generalizations = vec![];
+        let mut is_generalized = true;
+
+        while is_generalized {
+            is_generalized = false;
+
+            for generalization in Generalization::ALL {
+                if length <= maximum_length {
+                    return Ok(config);
+                }
+                if generalization.is_applied(&config)
+                    || pending_generalizations.contains(&generalization)
+                {
+                    continue;
+                }
+                let (mut generalized_config, mut generalized_regexp) =
+                    self.build_with_generalizations(&config, &[generalization])?;
+
+                if !pending_generalizations.is_empty() {
+                    let mut combined_generalizations = pending_generalizations.clone();
+                    combined_generalizations.push(generalization);
+                    let (mut combined_config, combined_regexp) =
+                        self.build_with_generalizations(&config, &combined_generalizations)?;
+
+                    // Pending generalizations which do not change the combined expression
+                    // are left out.
+                    for pending_generalization in pending_generalizations.iter() {
+                        let reduced_generalizations = combined_generalizations
+                            .iter()
+                            .filter(|it| *it != pending_generalization)
+                            .copied()
+                            .collect_vec();
+                        let (reduced_config, reduced_regexp) =
+                            self.build_with_generalizations(&config, &reduced_generalizations)?;
+
+                        if reduced_regexp == combined_regexp {
+                            combined_generalizations = reduced_generalizations;
+                            combined_config = reduced_config;
+                        }
+                    }
+
+                    if combined_regexp.chars().count() < generalized_regexp.chars().count() {
+                        generalized_config = combined_config;
+                        generalized_regexp = combined_regexp;
+                    }
+                }
+
+                let generalized_length = generalized_regexp.chars().count();
+
+                if generalized_length < length {
+                    pending_generalizations.retain(|it| !it.is_applied(&generalized_config));
+                    config = generalized_config;
+                    length = generalized_length;
+                    is_generalized = true;
+                } else {
+                    pending_generalizations.push(generalization);
+                }
+            }
+
+            // Pending generalizations are tried on their own again
+            // after later ones have been applied.
+            if is_generalized {
+                pending_generalizations.clear();
+            }
+        }
+
+        if length <= maximum_length {
+            Ok(config)
+        } else {
+            Err(GrexError::MaximumLengthExceeded {
+                length,
+                maximum_length,
+            })
+        }
+    }
+
+    /// Builds the regular expression with the given settings extended by the given
+    /// generalizations, returning the extended settings along with it.
+    fn build_with_generalizations(
+        &self,
+        config: &RegExpConfig,
+        generalizations: &[Generalization],
+    ) -> Result<(RegExpConfig, String), GrexError> {
+        let mut generalized_config = config.clone();
+        for generalization in generalizations {
+            generalization.apply(&mut generalized_config);
+        }
+        let regexp = self.build_without_highlighting(&generalized_config)?;
+        Ok((generalized_config, regexp))
+    }
+
+    /// Builds the regular expression with the given settings, leaving out
+    /// syntax highlighting as its escape sequences do not count towards the maximum length.
+    fn build_without_highlighting(&self, config: &RegExpConfig) -> Result<String, GrexError> {
+        let mut config = config.clone();
+        config.is_output_colorized = false;
+        let mut test_cases = self.test_cases.clone();
+        RegExp::from(&mut test_cases, &self.negative_test_cases, &config)
+            .map(|regexp| regexp.to_string())
+    }
+}
+
+impl<T: AsRef<str>> FromIterator<T> for RegExpBuilder {
+    /// Specifies the test cases to build the regular expression from,
+    /// taking them one by one from an iterator.
+    ///
+    /// ⚠ Panics if the iterator is empty.
+    /// Use [`try_from_iter`](Self::try_from_iter) to handle this case as an error instead.
+    fn from_iter<I: IntoIterator<Item = T>>(test_cases: I) -> Self {
+        Self::try_from_iter(test_cases).unwrap_or_else(|error| panic!("{}", error))
+    }
+}
+
+fn insert_test_case(test_cases: &mut BTreeSet<String>, test_case: &str) {
+    if !test_cases.contains(test_case) {
+        test_cases.insert(test_case.to_string());
     }
 }
@@ -15,8 +15,8 @@
  */
 
 use crate::config::RegExpConfig;
-use crate::grapheme::Grapheme;
-use crate::unicode_tables::{DECIMAL_NUMBER, WHITE_SPACE, WORD};
+use crate::grapheme::{case_class, Grapheme};
+use crate::unicode_tables::{DECIMAL_NUMBER, SCRIPTS, WHITE_SPACE, WORD};
 use itertools::Itertools;
 use lazy_static::lazy_static;
 use std::cmp::Ordering;
@@ -79,41 +79,63 @@ impl<'a> GraphemeCluster<'a> {
         }
     }
 
-    pub(crate) fn convert_to_char_classes(&mut self) {
-        let is_digit_converted = self.config.is_digit_converted;
-        let is_non_digit_converted = self.config.is_non_digit_converted;
-        let is_space_converted = self.config.is_space_converted;
-        let is_non_space_converted = self.config.is_non_space_converted;
-        let is_word_converted = self.config.is_word_converted;
-        let is_non_word_converted = self.config.is_non_word_converted;
+    pub(crate) fn convert_to_char_classes(&mut self, negative_clusters: &[GraphemeCluster]) {
+        let config = self.config;
+        let original_graphemes = self.graphemes.clone();
 
         for grapheme in self.graphemes.iter_mut() {
+            if grapheme.is_case_class {
+                continue;
+            }
             grapheme.chars = grapheme
                 .chars
                 .iter()
                 .map(|it| {
                     it.chars()
-                        .map(|c| {
-                            if is_digit_converted && is_digit(c) {
-                                "\\d".to_string()
-                            } else if is_word_converted && is_word(c) {
-                                "\\w".to_string()
-                            } else if is_space_converted && is_space(c) {
-                                "\\s".to_string()
-                            } else if is_non_digit_converted && !is_digit(c) {
-                                "\\D".to_string()
-                            } else if is_non_word_converted && !is_word(c) {
-                                "\\W".to_string()
-                            } else if is_non_space_converted && !is_space(c) {
-                                "\\S".to_string()
-                            } else {
-                                c.to_string()
-                            }
-                        })
+                        .map(|c| convert_to_char_class(c, config))
                         .join("")
                 })
                 .collect_vec();
         }
+
+        // Back off from the conversion wherever a negative test case would be matched.
+        // Restoring the first literal grapheme in which both test cases differ is enough
+        // to exclude the negative test case again.
+        for negative_cluster in negative_clusters {
+            if let Some(idx) = self.find_grapheme_to_restore(&original_graphemes, negative_cluster)
+            {
+                self.graphemes[idx] = original_graphemes[idx].clone();
+            }
+        }
+    }
+
+    /// Merges clusters which only differ in the case of some letters into a single cluster
+    /// in which exactly these letters are replaced with a character class such as `[hH]`.
+    pub(crate) fn merge_case_variants(clusters: Vec<GraphemeCluster<'a>>) -> Vec<Self> {
+        let mut merged_clusters: Vec<GraphemeCluster<'a>> = vec![];
+        let mut indices: HashMap<Vec<String>, usize> = HashMap::new();
+
+        for cluster in clusters {
+            let lowercase_values = cluster
+                .graphemes
+                .iter()
+                .map(|it| it.value().to_lowercase())
+                .collect_vec();
+
+            match indices.get(&lowercase_values) {
+                Some(&idx) if merged_clusters[idx].is_case_variant(&cluster) => {
+                    merged_clusters[idx].merge_case_variant(&cluster)
+                }
+                _ => {
+                    indices
+                        .entry(lowercase_values)
+                        .or_insert(merged_clusters.len());
+                    merged_clusters.push(cluster);
+                }
+            }
+        }
+
+        merged_clusters
     }
 
     pub(crate) fn convert_repetitions(&mut self) {
@@ -157,16 +179,199 @@ impl<'a> GraphemeCluster<'a> {
     pub(crate) fn is_empty(&self) -> bool {
         self.graphemes.is_empty()
     }
+
+    fn is_case_variant(&self, other: &GraphemeCluster) -> bool {
+        self.size() == other.size()
+            && self.graphemes.iter().zip(other.graphemes.iter()).all(
+                |(grapheme, other_grapheme)| {
+                    grapheme.value() == other_grapheme.value()
+                        || (to_case_class(grapheme).is_some()
+                            && to_case_class(grapheme) == to_case_class(other_grapheme))
+                },
+            )
+    }
+
+    fn merge_case_variant(&mut self, other: &GraphemeCluster) {
+        for (grapheme, other_grapheme) in self.graphemes.iter_mut().zip(other.graphemes.iter()) {
+            if grapheme.value() != other_grapheme.value() {
+                grapheme.chars = vec![to_case_class(grapheme).unwrap()];
+                grapheme.is_case_class = true;
+            }
+        }
+    }
+
+    fn find_grapheme_to_restore(
+        &self,
+        original_graphemes: &[Grapheme],
+        negative_cluster: &GraphemeCluster,
+    ) -> Option<usize> {
+        if self.size() != negative_cluster.size() {
+            return None;
+        }
+
+        let is_negative_cluster_matched = original_graphemes
+            .iter()
+            .zip(self.graphemes.iter())
+            .zip(negative_cluster.graphemes.iter())
+            .all(|((original, converted), negative)| {
+                let original_value = original.value();
+                let negative_value = negative.value();
+
+                if original == converted {
+                    return original_value == negative_value;
+                }
+
+                original_value.chars().count() == negative_value.chars().count()
+                    && original_value
+                        .chars()
+                        .zip(negative_value.chars())
+                        .all(|(o, n)| o == n || is_char_class_matched(o, n, self.config))
+            });
+
+        if !is_negative_cluster_matched {
+            return None;
+        }
+
+        original_graphemes
+            .iter()
+            .zip(negative_cluster.graphemes.iter())
+            .position(|(original, negative)| original.value() != negative.value())
+    }
+}
+
+fn to_case_class(grapheme: &Grapheme) -> Option<String> {
+    if grapheme.is_case_class {
+        return Some(grapheme.value());
+    }
+    let value = grapheme.value();
+    let mut chars = value.chars();
+    match (chars.next(), chars.next()) {
+        (Some(c), None) => case_class(c),
+        _ => None,
+    }
+}
+
+fn convert_to_char_class(c: char, config: &RegExpConfig) -> String {
+    // A character is converted only if its Unicode property agrees with
+    // the semantics of the character class in the selected dialect.
+    let dialect = config.dialect;
+    let is_digit_char = is_digit(c);
+    let is_word_char = is_word(c);
+    let is_space_char = is_space(c);
+
+    if config.is_digit_converted && is_digit_char && dialect.matches_digit_class(c) {
+        "\\d".to_string()
+    } else if config.is_space_converted && is_space_char && dialect.matches_space_class(c) {
+        "\\s".to_string()
+    } else if let Some(category) = general_category(c)
+        .filter(|_| config.is_general_category_converted && !is_cased_letter(c, config))
+    {
+        format!("\\p{{{}}}", category)
+    } else if let Some(script) = script(c).filter(|_| config.is_script_converted) {
+        format!("\\p{{{}}}", script)
+    } else if config.is_word_converted && is_word_char && dialect.matches_word_class(c) {
+        "\\w".to_string()
+    } else if config.is_non_digit_converted && !is_digit_char && !dialect.matches_digit_class(c) {
+        "\\D".to_string()
+    } else if config.is_non_word_converted && !is_word_char && !dialect.matches_word_class(c) {
+        "\\W".to_string()
+    } else if config.is_non_space_converted && !is_space_char && !dialect.matches_space_class(c) {
+        "\\S".to_string()
+    } else {
+        c.to_string()
+    }
+}
+
+fn is_char_class_matched(original: char, other: char, config: &RegExpConfig) -> bool {
+    let dialect = config.dialect;
+    match convert_to_char_class(original, config).as_str() {
+        "\\d" => dialect.matches_digit_class(other),
+        "\\w" => dialect.matches_word_class(other),
+        "\\s" => dialect.matches_space_class(other),
+        "\\D" => !dialect.matches_digit_class(other),
+        "\\W" => !dialect.matches_word_class(other),
+        "\\S" => !dialect.matches_space_class(other),
+        class => match class.strip_prefix("\\p{") {
+            Some(name) => {
+                let name = name.trim_end_matches('}');
+                general_category(other) == Some(name) || script(other) == Some(name)
+            }
+            None => false,
+        },
+    }
+}
+
+/// Case-insensitive matching lets `\p{Lu}` match lowercase letters as well,
+/// so the cased letter categories are left to the other character classes.
+fn is_cased_letter(c: char, config: &RegExpConfig) -> bool {
+    config.is_case_insensitive_matching && matches!(general_category(c), Some("Lu" | "Ll" | "Lt"))
+}
+
+/// Returns the abbreviated general category of the given character.
+/// The categories of control, format, private use, surrogate and unassigned
+/// characters are not returned as these characters do not form a useful class.
+pub(crate) fn general_category(c: char) -> Option<&'static str> {
+    let abbreviation = match GeneralCategory::of(c) {
+        GeneralCategory::UppercaseLetter => "Lu",
+        GeneralCategory::LowercaseLetter => "Ll",
+        GeneralCategory::TitlecaseLetter => "Lt",
+        GeneralCategory::ModifierLetter => "Lm",
+        GeneralCategory::OtherLetter => "Lo",
+        GeneralCategory::NonspacingMark => "Mn",
+        GeneralCategory::SpacingMark => "Mc",
+        GeneralCategory::EnclosingMark => "Me",
+        GeneralCategory::DecimalNumber => "Nd",
+        GeneralCategory::LetterNumber => "Nl",
+        GeneralCategory::OtherNumber => "No",
+        GeneralCategory::ConnectorPunctuation => "Pc",
+        GeneralCategory::DashPunctuation => "Pd",
+        GeneralCategory::OpenPunctuation => "Ps",
+        GeneralCategory::ClosePunctuation => "Pe",
+        GeneralCategory::InitialPunctuation => "Pi",
+        GeneralCategory::FinalPunctuation => "Pf",
+        GeneralCategory::OtherPunctuation => "Po",
+        GeneralCategory::MathSymbol => "Sm",
+        GeneralCategory::CurrencySymbol => "Sc",
+        GeneralCategory::ModifierSymbol => "Sk",
+        GeneralCategory::OtherSymbol => "So",
+        GeneralCategory::SpaceSeparator => "Zs",
+        GeneralCategory::LineSeparator => "Zl",
+        GeneralCategory::ParagraphSeparator => "Zp",
+        _ => return None,
+    };
+    Some(abbreviation)
 }
 
-fn is_digit(c: char) -> bool {
+/// Returns the name of the script the given character belongs to. The pseudo scripts
+/// `Common` and `Inherited` are not returned as they are shared by unrelated characters.
+pub(crate) fn script(c: char) -> Option<&'static str> {
+    lazy_static! {
+        static ref SCRIPT_RANGES: Vec<(char, char, &'static str)> = SCRIPTS
+            .iter()
+            .filter(|(name, _)| !matches!(*name, "Common" | "Inherited"))
+            .flat_map(|(name, ranges)| ranges.iter().map(move |(start, end)| (*start, *end, *name)))
+            .sorted_by_key(|(start, _, _)| *start)
+            .collect_vec();
+    }
+    let idx = SCRIPT_RANGES.partition_point(|(start, _, _)| *start <= c);
+    match idx.checked_sub(1).map(|it| SCRIPT_RANGES[it]) {
+        Some((_, end, name)) if c <= end => Some(name),
+        _ => None,
+    }
+}
+
+pub(crate) fn is_script_name(name: &str) -> bool {
+    SCRIPTS.iter().any(|(script_name, _)| *script_name == name)
+}
+
+pub(crate) fn is_digit(c: char) -> bool {
     lazy_static! {
         static ref VALID_NUMERIC_CHARS: Vec<CharRange> = convert_chars_to_range(DECIMAL_NUMBER);
     }
     VALID_NUMERIC_CHARS.iter().any(|range| range.contains(c))
 }
 
-fn is_word(c: char) -> bool {
+pub(crate) fn is_word(c: char) -> bool {
     lazy_static! {
         static ref VALID_ALPHANUMERIC_CHARS: Vec<CharRange> = convert_chars_to_range(WORD);
     }
@@ -175,7 +380,7 @@ fn is_word(c: char) -> bool {
         .any(|range| range.contains(c))
 }
 
-fn is_space(c: char) -> bool {
+pub(crate) fn is_space(c: char) -> bool {
     lazy_static! {
         static ref VALID_SPACE_CHARS: Vec<CharRange> = convert_chars_to_range(WHITE_SPACE);
     }
@@ -286,8 +491,28 @@ fn replace_graphemes_with_repetitions(
         return;
     }
 
+    // The graphemes each element of `repetitions` consists of,
+    // which are searched for nested repetitions afterwards.
+    let mut repeated_graphemes = vec![];
+
     for grapheme in graphemes {
         repetitions.push(grapheme.clone());
+        repeated_graphemes.push(
+            grapheme
+                .chars
+                .iter()
+                .map(|it| {
+                    let mut repeated_grapheme = Grapheme::from(
+                        it,
+                        config.is_capturing_group_enabled,
+                        config.is_output_colorized,
+                        config.is_verbose_mode_enabled,
+                    );
+                    repeated_grapheme.is_case_class = grapheme.is_case_class;
+                    repeated_grapheme
+                })
+                .collect_vec(),
+        );
     }
 
     for (range, substr) in coalesced_repetitions.iter() {
@@ -301,38 +526,37 @@ fn replace_graphemes_with_repetitions(
             continue;
         }
 
-        repetitions.splice(
-            range.clone(),
-            [Grapheme::new(
-                substr.clone(),
-                count,
-                count,
-                config.is_capturing_group_enabled,
-                config.is_output_colorized,
-                config.is_verbose_mode_enabled,
-            )]
-            .iter()
-            .cloned(),
+        let substr_graphemes = graphemes[range.start..range.start + substr.len()].to_vec();
+        let mut new_grapheme = Grapheme::new(
+            substr.clone(),
+            count,
+            count,
+            config.is_capturing_group_enabled,
+            config.is_output_colorized,
+            config.is_verbose_mode_enabled,
         );
+        new_grapheme.is_case_class =
+            substr_graphemes.len() == 1 && substr_graphemes[0].is_case_class;
+
+        repetitions.splice(range.clone(), [new_grapheme]);
+        repeated_graphemes.splice(range.clone(), [substr_graphemes]);
     }
 
-    for new_grapheme in repetitions.iter_mut() {
+    for (new_grapheme, repeated_graphemes) in repetitions.iter_mut().zip(repeated_graphemes) {
         convert_repetitions(
-            &new_grapheme
-                .chars
-                .iter()
-                .map(|it| {
-                    Grapheme::from(
-                        it,
-                        config.is_capturing_group_enabled,
-                        config.is_output_colorized,
-                        config.is_verbose_mode_enabled,
-                    )
-                })
-                .collect_vec(),
+            &repeated_graphemes,
             new_grapheme.repetitions.as_mut(),
             config,
         );
+
+        // Character classes within repeated substrings are kept as graphemes of their own,
+        // so that they are not escaped like literal characters.
+        if !new_grapheme.has_repetitions()
+            && repeated_graphemes.len() > 1
+            && repeated_graphemes.iter().any(|it| it.is_case_class)
+        {
+            new_grapheme.repetitions = repeated_graphemes;
+        }
     }
 }
 
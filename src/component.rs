@@ -23,9 +23,9 @@ pub(crate) enum Component {
     Caret(bool),
     CharClass(String),
     DollarSign(bool),
+    Flags(String),
     Hyphen,
-    IgnoreCaseFlag,
-    IgnoreCaseAndVerboseModeFlag,
+    InlineFlags(String, bool),
     LeftBracket,
     Pipe,
     Quantifier(Quantifier, bool),
@@ -35,7 +35,6 @@ pub(crate) enum Component {
     RightParenthesis,
     UncapturedLeftParenthesis,
     UncapturedParenthesizedExpression(String, bool, bool),
-    VerboseModeFlag,
 }
 
 impl Component {
@@ -100,12 +99,20 @@ impl Component {
                     Self::yellow_bold(&self.to_string(), is_escaped)
                 }
             }
+            Component::Flags(_) => Self::bright_yellow_on_black(&self.to_string(), is_escaped),
             Component::Hyphen => Self::cyan_bold(&self.to_string(), is_escaped),
-            Component::IgnoreCaseFlag => {
-                Self::bright_yellow_on_black(&self.to_string(), is_escaped)
-            }
-            Component::IgnoreCaseAndVerboseModeFlag => {
-                format!("{}\n", Self::bright_yellow_on_black("(?ix)", is_escaped))
+            Component::InlineFlags(flags, is_verbose_mode_enabled) => {
+                if *is_verbose_mode_enabled {
+                    format!(
+                        "{}\n",
+                        Self::bright_yellow_on_black(
+                            &Component::InlineFlags(flags.clone(), false).to_string(),
+                            is_escaped
+                        )
+                    )
+                } else {
+                    Self::bright_yellow_on_black(&self.to_string(), is_escaped)
+                }
             }
             Component::LeftBracket => Self::cyan_bold(&self.to_string(), is_escaped),
             Component::Pipe => Self::red_bold(&self.to_string(), is_escaped),
@@ -178,9 +185,6 @@ impl Component {
                     )
                 }
             }
-            Component::VerboseModeFlag => {
-                format!("{}\n", Self::bright_yellow_on_black("(?x)", is_escaped))
-            }
         }
     }
 
@@ -274,9 +278,14 @@ impl Display for Component {
                     } else {
                         "$".to_string()
                     },
+                Component::Flags(flags) => flags.clone(),
                 Component::Hyphen => "-".to_string(),
-                Component::IgnoreCaseFlag => "(?i)".to_string(),
-                Component::IgnoreCaseAndVerboseModeFlag => "(?ix)\n".to_string(),
+                Component::InlineFlags(flags, is_verbose_mode_enabled) =>
+                    if *is_verbose_mode_enabled {
+                        format!("(?{})\n", flags)
+                    } else {
+                        format!("(?{})", flags)
+                    },
                 Component::LeftBracket => "[".to_string(),
                 Component::Pipe => "|".to_string(),
                 Component::Quantifier(quantifier, is_verbose_mode_enabled) =>
@@ -340,7 +349,6 @@ impl Display for Component {
                         )
                     }
                 }
-                Component::VerboseModeFlag => "(?x)\n".to_string(),
             }
         )
     }
@@ -14,6 +14,9 @@
  * limitations under the License.
  */
 
+use crate::dialect::Dialect;
+use crate::generalization::Generalization;
+
 #[derive(Clone, Debug, Hash, Ord, PartialOrd, Eq, PartialEq)]
 pub struct RegExpConfig {
     pub(crate) minimum_repetitions: u32,
@@ -24,8 +27,14 @@ pub struct RegExpConfig {
     pub(crate) is_non_space_converted: bool,
     pub(crate) is_word_converted: bool,
     pub(crate) is_non_word_converted: bool,
+    pub(crate) is_general_category_converted: bool,
+    pub(crate) is_script_converted: bool,
     pub(crate) is_repetition_converted: bool,
+    pub(crate) is_numeric_range_converted: bool,
+    pub(crate) is_positional_char_class_inferred: bool,
+    pub(crate) positional_char_class_tolerance: u32,
     pub(crate) is_case_insensitive_matching: bool,
+    pub(crate) is_case_insensitive_char_class_enabled: bool,
     pub(crate) is_capturing_group_enabled: bool,
     pub(crate) is_non_ascii_char_escaped: bool,
     pub(crate) is_astral_code_point_converted_to_surrogate: bool,
@@ -33,6 +42,9 @@ pub struct RegExpConfig {
     pub(crate) is_start_anchor_disabled: bool,
     pub(crate) is_end_anchor_disabled: bool,
     pub(crate) is_output_colorized: bool,
+    pub(crate) dialect: Dialect,
+    pub(crate) maximum_length: Option<usize>,
+    pub(crate) generalizations: Vec<Generalization>,
 }
 
 impl RegExpConfig {
@@ -46,8 +58,14 @@ impl RegExpConfig {
             is_non_space_converted: false,
             is_word_converted: false,
             is_non_word_converted: false,
+            is_general_category_converted: false,
+            is_script_converted: false,
             is_repetition_converted: false,
+            is_numeric_range_converted: false,
+            is_positional_char_class_inferred: false,
+            positional_char_class_tolerance: 4,
             is_case_insensitive_matching: false,
+            is_case_insensitive_char_class_enabled: false,
             is_capturing_group_enabled: false,
             is_non_ascii_char_escaped: false,
             is_astral_code_point_converted_to_surrogate: false,
@@ -55,6 +73,9 @@ impl RegExpConfig {
             is_start_anchor_disabled: false,
             is_end_anchor_disabled: false,
             is_output_colorized: false,
+            dialect: Dialect::Rust,
+            maximum_length: None,
+            generalizations: vec![],
         }
     }
 
@@ -65,6 +86,8 @@ impl RegExpConfig {
             || self.is_non_space_converted
             || self.is_word_converted
             || self.is_non_word_converted
+            || self.is_general_category_converted
+            || self.is_script_converted
             || self.is_case_insensitive_matching
             || self.is_capturing_group_enabled
     }
@@ -23,17 +23,18 @@ use petgraph::stable_graph::{Edges, StableGraph};
 use petgraph::visit::Dfs;
 use petgraph::{Directed, Direction};
 use std::cmp::{max, min};
-use std::collections::{BTreeSet, HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
 
 type State = NodeIndex<u32>;
 type StateLabel = String;
 type EdgeLabel = Grapheme;
 
 pub struct Dfa<'a> {
-    alphabet: BTreeSet<Grapheme>,
     graph: StableGraph<StateLabel, EdgeLabel>,
     initial_state: State,
     final_state_indices: HashSet<usize>,
+    is_repetition_range_merged: bool,
+    unminimized_state_count: usize,
     config: &'a RegExpConfig,
 }
 
@@ -41,22 +42,62 @@ impl<'a> Dfa<'a> {
     pub(crate) fn from(
         grapheme_clusters: &[GraphemeCluster],
         is_minimized: bool,
+        is_repetition_range_merged: bool,
         config: &'a RegExpConfig,
     ) -> Self {
         let mut dfa = Self::new(config);
+        dfa.is_repetition_range_merged = is_repetition_range_merged;
         for cluster in grapheme_clusters {
             dfa.insert(cluster);
         }
+        dfa.unminimized_state_count = dfa.state_count();
         if is_minimized {
             dfa.minimize();
         }
         dfa
     }
 
+    /// Creates an already minimized DFA from its states, each given as a flag whether it is final
+    /// and its outgoing transitions to the indices of other states. The first state is the initial one.
+    pub(crate) fn from_transitions(
+        states: &[(bool, Vec<(Grapheme, usize)>)],
+        unminimized_state_count: usize,
+        config: &'a RegExpConfig,
+    ) -> Self {
+        let mut dfa = Self::new(config);
+        let mut new_states = vec![dfa.initial_state];
+
+        for _ in 1..states.len() {
+            new_states.push(dfa.graph.add_node("".to_string()));
+        }
+
+        for (state_idx, (is_final, transitions)) in states.iter().enumerate() {
+            if *is_final {
+                dfa.final_state_indices
+                    .insert(new_states[state_idx].index());
+            }
+            // Edges are iterated in reverse order of insertion.
+            for (grapheme, target_idx) in transitions.iter().rev() {
+                dfa.graph.add_edge(
+                    new_states[state_idx],
+                    new_states[*target_idx],
+                    grapheme.clone(),
+                );
+            }
+        }
+
+        dfa.unminimized_state_count = unminimized_state_count;
+        dfa
+    }
+
     pub(crate) fn state_count(&self) -> usize {
         self.graph.node_count()
     }
 
+    pub(crate) fn unminimized_state_count(&self) -> usize {
+        self.unminimized_state_count
+    }
+
     pub(crate) fn states_in_depth_first_order(&self) -> Vec<State> {
         let mut depth_first_search = Dfs::new(&self.graph, self.initial_state);
         let mut states = vec![];
@@ -66,6 +107,7 @@ impl<'a> Dfa<'a> {
         states
     }
 
+    #[allow(mismatched_lifetime_syntaxes)]
     pub(crate) fn outgoing_edges(&self, state: State) -> Edges<Grapheme, Directed> {
         self.graph.edges_directed(state, Direction::Outgoing)
     }
@@ -78,10 +120,11 @@ impl<'a> Dfa<'a> {
         let mut graph = StableGraph::new();
         let initial_state = graph.add_node("".to_string());
         Self {
-            alphabet: BTreeSet::new(),
             graph,
             initial_state,
             final_state_indices: HashSet::new(),
+            is_repetition_range_merged: true,
+            unminimized_state_count: 1,
             config,
         }
     }
@@ -90,7 +133,6 @@ impl<'a> Dfa<'a> {
         let mut current_state = self.initial_state;
 
         for grapheme in cluster.graphemes() {
-            self.alphabet.insert(grapheme.clone());
             current_state = self.return_next_state(current_state, grapheme);
         }
         self.final_state_indices.insert(current_state.index());
@@ -112,10 +154,12 @@ impl<'a> Dfa<'a> {
                 continue;
             }
 
-            if current_grapheme.maximum() == grapheme.maximum() - 1 {
+            if self.is_repetition_range_merged
+                && current_grapheme.maximum() == grapheme.maximum() - 1
+            {
                 let min = min(current_grapheme.minimum(), grapheme.minimum());
                 let max = max(current_grapheme.maximum(), grapheme.maximum());
-                let new_grapheme = Grapheme::new(
+                let mut new_grapheme = Grapheme::new(
                     grapheme.chars().clone(),
                     min,
                     max,
@@ -123,6 +167,10 @@ impl<'a> Dfa<'a> {
                     self.config.is_output_colorized,
                     self.config.is_verbose_mode_enabled,
                 );
+                new_grapheme.is_case_class = grapheme.is_case_class;
+                if grapheme.contains_case_class() {
+                    new_grapheme.repetitions = grapheme.repetitions.clone();
+                }
                 self.graph
                     .update_edge(current_state, next_state, new_grapheme);
                 return Some(next_state);
@@ -140,95 +188,80 @@ impl<'a> Dfa<'a> {
         next_state
     }
 
-    #[allow(clippy::many_single_char_names)]
+    /// Minimizes the DFA with Hopcroft's partition refinement algorithm.
+    ///
+    /// The incoming transitions of all states are indexed once, so that the predecessors
+    /// of each splitting block are found without scanning all edges of the graph.
     fn minimize(&mut self) {
-        let mut p = self.get_initial_partition();
-        let mut w = p.iter().cloned().collect_vec();
-
-        while !w.is_empty() {
-            let a = w.drain(0..1).next().unwrap();
-
-            for edge_label in self.alphabet.iter() {
-                let x = self.get_parent_states(&a, edge_label);
-                let mut replacements = vec![];
-                let mut is_replacement_needed = true;
-                let mut start_idx = 0;
-
-                while is_replacement_needed {
-                    for (idx, y) in p.iter().enumerate().skip(start_idx) {
-                        if x.intersection(y).count() == 0 || y.difference(&x).count() == 0 {
-                            is_replacement_needed = false;
-                            continue;
-                        }
-
-                        let i = x.intersection(y).copied().collect::<HashSet<State>>();
-                        let d = y.difference(&x).copied().collect::<HashSet<State>>();
+        let states = self.graph.node_indices().collect_vec();
+        let state_indices: HashMap<State, usize> = states
+            .iter()
+            .enumerate()
+            .map(|(idx, state)| (*state, idx))
+            .collect();
+
+        let mut symbols = HashMap::new();
+        let mut incoming_transitions = vec![vec![]; states.len()];
+
+        for edge in self.graph.edge_indices() {
+            let (source, target) = self.graph.edge_endpoints(edge).unwrap();
+            let grapheme = self.graph.edge_weight(edge).unwrap();
+            let symbol_count = symbols.len();
+            let symbol = *symbols.entry(grapheme).or_insert(symbol_count);
+            incoming_transitions[state_indices[&target]].push((symbol, state_indices[&source]));
+        }
 
-                        is_replacement_needed = true;
-                        start_idx = idx;
+        let (final_states, non_final_states): (Vec<usize>, Vec<usize>) = (0..states.len())
+            .partition(|&idx| self.final_state_indices.contains(&states[idx].index()));
 
-                        replacements.push((y.clone(), i, d));
+        let mut partition = Partition::new(states.len(), vec![final_states, non_final_states]);
+        let mut worklist = (0..partition.block_count()).collect_vec();
+        let mut is_in_worklist = vec![true; partition.block_count()];
+        let mut predecessors: BTreeMap<usize, Vec<usize>> = BTreeMap::new();
 
-                        break;
-                    }
+        while let Some(splitter) = worklist.pop() {
+            is_in_worklist[splitter] = false;
+            predecessors.clear();
 
-                    if is_replacement_needed {
-                        let (_, i, d) = replacements.last().unwrap();
-
-                        p.remove(start_idx);
-                        p.insert(start_idx, i.clone());
-                        p.insert(start_idx + 1, d.clone());
-                    }
+            for &state in partition.states(splitter) {
+                for &(symbol, source) in incoming_transitions[state].iter() {
+                    predecessors.entry(symbol).or_default().push(source);
                 }
+            }
 
-                for (y, i, d) in replacements {
-                    if w.contains(&y) {
-                        let idx = w.iter().position(|it| it == &y).unwrap();
-                        w.remove(idx);
-                        w.push(i);
-                        w.push(d);
-                    } else if i.len() <= d.len() {
-                        w.push(i);
+            for parent_states in predecessors.values() {
+                for (block, new_block) in partition.refine(parent_states) {
+                    is_in_worklist.push(false);
+
+                    if is_in_worklist[block]
+                        || partition.states(new_block).len() <= partition.states(block).len()
+                    {
+                        worklist.push(new_block);
+                        is_in_worklist[new_block] = true;
                     } else {
-                        w.push(d);
+                        worklist.push(block);
+                        is_in_worklist[block] = true;
                     }
                 }
             }
         }
 
-        self.recreate_graph(p.iter().filter(|&it| !it.is_empty()).collect_vec());
-    }
-
-    fn get_initial_partition(&self) -> Vec<HashSet<State>> {
-        let (final_states, non_final_states): (HashSet<State>, HashSet<State>) = self
-            .graph
-            .node_indices()
-            .partition(|&state| !self.final_state_indices.contains(&state.index()));
-
-        vec![final_states, non_final_states]
-    }
-
-    fn get_parent_states(&self, a: &HashSet<State>, label: &Grapheme) -> HashSet<State> {
-        let mut x = HashSet::new();
-
-        for &state in a {
-            let direct_parent_states = self.graph.neighbors_directed(state, Direction::Incoming);
-            for parent_state in direct_parent_states {
-                let edge = self.graph.find_edge(parent_state, state).unwrap();
-                let grapheme = self.graph.edge_weight(edge).unwrap();
-                if grapheme.value() == label.value()
-                    && (grapheme.maximum() == label.maximum()
-                        || grapheme.minimum() == label.minimum())
-                {
-                    x.insert(parent_state);
-                    break;
-                }
-            }
-        }
-        x
+        let equivalence_classes = (0..partition.block_count())
+            .map(|block| {
+                partition
+                    .states(block)
+                    .iter()
+                    .map(|&idx| states[idx])
+                    .sorted()
+                    .collect_vec()
+            })
+            .sorted()
+            .collect_vec();
+
+        self.recreate_graph(equivalence_classes);
     }
 
-    fn recreate_graph(&mut self, p: Vec<&HashSet<State>>) {
+    fn recreate_graph(&mut self, p: Vec<Vec<State>>) {
         let mut graph = StableGraph::<StateLabel, EdgeLabel>::new();
         let mut final_state_indices = HashSet::new();
         let mut state_mappings = HashMap::new();
@@ -241,12 +274,15 @@ impl<'a> Dfa<'a> {
                 if self.initial_state == *old_state {
                     new_initial_state = Some(new_state);
                 }
+                if self.final_state_indices.contains(&old_state.index()) {
+                    final_state_indices.insert(new_state.index());
+                }
                 state_mappings.insert(*old_state, new_state);
             }
         }
 
         for equivalence_class in p.iter() {
-            let old_source_state = *equivalence_class.iter().next().unwrap();
+            let old_source_state = equivalence_class[0];
             let new_source_state = state_mappings.get(&old_source_state).unwrap();
 
             for old_target_state in self.graph.neighbors(old_source_state) {
@@ -259,10 +295,6 @@ impl<'a> Dfa<'a> {
                 let new_target_state = state_mappings.get(&old_target_state).unwrap();
 
                 graph.add_edge(*new_source_state, *new_target_state, grapheme.clone());
-
-                if self.final_state_indices.contains(&old_target_state.index()) {
-                    final_state_indices.insert(new_target_state.index());
-                }
             }
         }
         self.initial_state = new_initial_state.unwrap();
@@ -271,7 +303,100 @@ impl<'a> Dfa<'a> {
     }
 }
 
+/// A partition of the states `0..n` into blocks. The states of each block are stored
+/// contiguously, so that a block is split in time proportional to the number of states
+/// moved out of it.
+struct Partition {
+    elements: Vec<usize>,
+    locations: Vec<usize>,
+    block_indices: Vec<usize>,
+    blocks: Vec<(usize, usize)>,
+    marked_counts: Vec<usize>,
+}
+
+impl Partition {
+    fn new(state_count: usize, blocks: Vec<Vec<usize>>) -> Self {
+        let mut partition = Self {
+            elements: Vec::with_capacity(state_count),
+            locations: vec![0; state_count],
+            block_indices: vec![0; state_count],
+            blocks: vec![],
+            marked_counts: vec![],
+        };
+
+        for block in blocks.into_iter().filter(|it| !it.is_empty()) {
+            let start = partition.elements.len();
+            for state in block {
+                partition.locations[state] = partition.elements.len();
+                partition.block_indices[state] = partition.blocks.len();
+                partition.elements.push(state);
+            }
+            partition.blocks.push((start, partition.elements.len()));
+            partition.marked_counts.push(0);
+        }
+
+        partition
+    }
+
+    fn block_count(&self) -> usize {
+        self.blocks.len()
+    }
+
+    fn states(&self, block: usize) -> &[usize] {
+        let (start, end) = self.blocks[block];
+        &self.elements[start..end]
+    }
+
+    /// Splits each block which contains some but not all of the given states
+    /// into the given states and the remaining ones. Returns the index of each split block
+    /// together with the index of the new block containing the given states.
+    fn refine(&mut self, states: &[usize]) -> Vec<(usize, usize)> {
+        let mut touched_blocks = vec![];
+
+        for &state in states {
+            let block = self.block_indices[state];
+            let marked_location = self.blocks[block].0 + self.marked_counts[block];
+            let location = self.locations[state];
+
+            if location < marked_location {
+                continue;
+            }
+            if self.marked_counts[block] == 0 {
+                touched_blocks.push(block);
+            }
+
+            let other_state = self.elements[marked_location];
+            self.elements.swap(location, marked_location);
+            self.locations[state] = marked_location;
+            self.locations[other_state] = location;
+            self.marked_counts[block] += 1;
+        }
+
+        let mut splits = vec![];
+
+        for block in touched_blocks {
+            let (start, end) = self.blocks[block];
+            let marked_count = std::mem::take(&mut self.marked_counts[block]);
+
+            if marked_count < end - start {
+                let new_block = self.blocks.len();
+                self.blocks.push((start, start + marked_count));
+                self.marked_counts.push(0);
+                self.blocks[block] = (start + marked_count, end);
+
+                for &state in self.elements[start..start + marked_count].iter() {
+                    self.block_indices[state] = new_block;
+                }
+                splits.push((block, new_block));
+            }
+        }
+
+        splits
+    }
+}
+
 #[cfg(test)]
+#[allow(clippy::bool_assert_comparison, clippy::get_first)]
 mod tests {
     use super::*;
 
@@ -291,6 +416,7 @@ mod tests {
         let dfa = Dfa::from(
             &[GraphemeCluster::from("abcd", &RegExpConfig::new())],
             true,
+            true,
             &config,
         );
 
@@ -310,6 +436,7 @@ mod tests {
                 GraphemeCluster::from("abxd", &RegExpConfig::new()),
             ],
             true,
+            true,
             &config,
         );
         let state = State::new(2);
@@ -342,6 +469,7 @@ mod tests {
                 GraphemeCluster::from("axyz", &RegExpConfig::new()),
             ],
             true,
+            true,
             &config,
         );
         let states = dfa.states_in_depth_first_order();
@@ -424,6 +552,40 @@ mod tests {
         assert_eq!(dfa.graph.edge_count(), 5);
     }
 
+    #[test]
+    fn test_minimization_algorithm_with_distinguishable_states() {
+        let config = RegExpConfig::new();
+        let mut dfa = Dfa::new(&config);
+
+        for test_case in ["ab", "ad", "cb"] {
+            dfa.insert(&GraphemeCluster::from(test_case, &RegExpConfig::new()));
+        }
+        assert_eq!(dfa.graph.node_count(), 6);
+        assert_eq!(dfa.graph.edge_count(), 5);
+
+        dfa.minimize();
+        assert_eq!(dfa.graph.node_count(), 4);
+        assert_eq!(dfa.graph.edge_count(), 5);
+    }
+
+    #[test]
+    fn test_dfa_constructor_with_transitions() {
+        let config = RegExpConfig::new();
+        let dfa = Dfa::from_transitions(
+            &[
+                (false, vec![(Grapheme::from("a", false, false, false), 1)]),
+                (true, vec![(Grapheme::from("b", false, false, false), 1)]),
+            ],
+            3,
+            &config,
+        );
+        assert_eq!(dfa.graph.node_count(), 2);
+        assert_eq!(dfa.graph.edge_count(), 2);
+        assert_eq!(dfa.unminimized_state_count(), 3);
+        assert!(!dfa.is_final_state(dfa.initial_state));
+        assert!(dfa.is_final_state(State::new(1)));
+    }
+
     #[test]
     fn test_dfa_constructor() {
         let config = RegExpConfig::new();
@@ -433,6 +595,7 @@ mod tests {
                 GraphemeCluster::from("abxd", &RegExpConfig::new()),
             ],
             true,
+            true,
             &config,
         );
         assert_eq!(dfa.graph.node_count(), 5);
@@ -0,0 +1,518 @@
+/*
+ * Copyright © 2019-today Peter M. Stahl pemistahl@gmail.com
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either expressed or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::cluster::{is_digit, is_script_name, is_space, is_word};
+use crate::config::RegExpConfig;
+use crate::error::GrexError;
+use std::fmt::{Display, Formatter, Result};
+use std::iter::Peekable;
+use std::str::{Chars, FromStr};
+
+/// This enum specifies the regular expression engines whose syntax
+/// and semantics the resulting regular expression is targeted at.
+#[derive(Clone, Copy, Debug, Default, Hash, Ord, PartialOrd, Eq, PartialEq)]
+pub enum Dialect {
+    /// The [regex](https://docs.rs/regex) crate of the Rust programming language.
+    #[default]
+    Rust,
+
+    /// Perl Compatible Regular Expressions as used by PHP, R and many other tools.
+    Pcre,
+
+    /// JavaScript regular expressions, rendered as a literal such as `/^a$/u`.
+    EcmaScript,
+
+    /// The `re` module of the Python programming language.
+    Python,
+
+    /// The RE2 syntax of the `regexp` package of the Go programming language.
+    Go,
+
+    /// The `System.Text.RegularExpressions` namespace of .NET.
+    DotNet,
+
+    /// The `java.util.regex` package of the Java programming language.
+    Java,
+
+    /// POSIX extended regular expressions as used by `grep -E`, `sed -E` and `awk`.
+    PosixExtended,
+
+    /// POSIX basic regular expressions as used by `grep` and `sed`.
+    /// They do not support alternation.
+    PosixBasic,
+}
+
+impl Dialect {
+    pub(crate) fn check_support(
+        &self,
+        config: &RegExpConfig,
+    ) -> std::result::Result<(), GrexError> {
+        let feature = if config.is_verbose_mode_enabled
+            && matches!(
+                self,
+                Dialect::EcmaScript | Dialect::Go | Dialect::PosixExtended | Dialect::PosixBasic
+            ) {
+            Some("Verbose mode")
+        } else if config.is_case_insensitive_matching && self.is_posix() {
+            Some("Case-insensitive matching")
+        } else if config.is_non_ascii_char_escaped && self.is_posix() {
+            Some("Escaping of non-ASCII characters")
+        } else if config.is_general_category_converted
+            && (*self == Dialect::Python || self.is_posix())
+        {
+            Some("Conversion of general categories")
+        } else if config.is_script_converted
+            && (matches!(self, Dialect::Python | Dialect::DotNet) || self.is_posix())
+        {
+            Some("Conversion of scripts")
+        } else {
+            None
+        };
+
+        match feature {
+            Some(feature) => Err(GrexError::UnsupportedFeature {
+                feature,
+                dialect: *self,
+            }),
+            None => Ok(()),
+        }
+    }
+
+    pub(crate) fn is_posix(&self) -> bool {
+        matches!(self, Dialect::PosixExtended | Dialect::PosixBasic)
+    }
+
+    /// Returns the flags of the regular expression, without any delimiters.
+    pub(crate) fn flags(&self, config: &RegExpConfig) -> String {
+        let mut flags = String::new();
+        if config.is_case_insensitive_matching {
+            flags.push('i');
+        }
+        // Java matches case-insensitively only within US-ASCII by default.
+        if config.is_case_insensitive_matching && *self == Dialect::Java {
+            flags.push('u');
+        }
+        // The unicode flag enables the `\u{...}` escape syntax in JavaScript.
+        if *self == Dialect::EcmaScript {
+            flags.push('u');
+        }
+        if config.is_verbose_mode_enabled {
+            flags.push('x');
+        }
+        flags
+    }
+
+    /// Returns the anchor which matches only at the very end of a string.
+    /// The dollar sign of several engines also matches before a final line break.
+    pub(crate) fn end_anchor(&self) -> &'static str {
+        match self {
+            Dialect::Pcre | Dialect::DotNet | Dialect::Java => "\\z",
+            Dialect::Python => "\\Z",
+            _ => "$",
+        }
+    }
+
+    /// Returns whether `\d` matches the ASCII digits only in this dialect.
+    pub(crate) fn is_digit_class_ascii_only(&self) -> bool {
+        !matches!(self, Dialect::Rust | Dialect::Python | Dialect::DotNet)
+    }
+
+    /// Returns whether `\d` matches the given character in this dialect.
+    pub(crate) fn matches_digit_class(&self, c: char) -> bool {
+        if self.is_digit_class_ascii_only() {
+            c.is_ascii_digit()
+        } else {
+            is_digit(c)
+        }
+    }
+
+    /// Returns whether `\w` matches the given character in this dialect.
+    pub(crate) fn matches_word_class(&self, c: char) -> bool {
+        match self {
+            Dialect::Rust | Dialect::Python | Dialect::DotNet => is_word(c),
+            _ => c.is_ascii_alphanumeric() || c == '_',
+        }
+    }
+
+    /// Returns whether `\s` matches the given character in this dialect.
+    pub(crate) fn matches_space_class(&self, c: char) -> bool {
+        match self {
+            Dialect::Rust | Dialect::DotNet => is_space(c),
+            Dialect::Python => is_space(c) || ('\u{1c}'..='\u{1f}').contains(&c),
+            Dialect::EcmaScript => (is_space(c) && c != '\u{85}') || c == '\u{feff}',
+            Dialect::Go => matches!(c, '\t' | '\n' | '\u{c}' | '\r' | ' '),
+            _ => matches!(c, '\t' | '\n' | '\u{b}' | '\u{c}' | '\r' | ' '),
+        }
+    }
+
+    /// Converts a regular expression in Rust syntax to the syntax of this dialect.
+    pub(crate) fn convert_syntax(&self, regexp: &str) -> String {
+        if self.is_posix() {
+            self.convert_to_posix_syntax(regexp)
+        } else {
+            self.convert_escape_sequences(regexp)
+        }
+    }
+
+    fn convert_escape_sequences(&self, regexp: &str) -> String {
+        let mut converted_regexp = String::with_capacity(regexp.len());
+        let mut chars = regexp.chars().peekable();
+        let mut is_within_char_class = false;
+
+        while let Some(c) = chars.next() {
+            match c {
+                // Skip ANSI color codes which contain brackets themselves.
+                '\u{1b}' => {
+                    converted_regexp.push(c);
+                    copy_color_code(&mut chars, &mut converted_regexp);
+                }
+                '\\' => match chars.next() {
+                    Some('u') if chars.peek() == Some(&'{') => {
+                        chars.next();
+                        let hex = chars
+                            .by_ref()
+                            .take_while(|&it| it != '}')
+                            .collect::<String>();
+                        let code_point = u32::from_str_radix(&hex, 16).unwrap();
+                        converted_regexp.push_str(&self.unicode_escape_sequence(code_point));
+                    }
+                    Some('v') if matches!(self, Dialect::Pcre | Dialect::Java) => {
+                        // `\v` denotes any vertical whitespace in these dialects.
+                        converted_regexp.push_str("\\x0b");
+                    }
+                    Some('p') if chars.peek() == Some(&'{') => {
+                        chars.next();
+                        let name = chars
+                            .by_ref()
+                            .take_while(|&it| it != '}')
+                            .collect::<String>();
+                        converted_regexp.push_str(&self.unicode_property_class(&name));
+                    }
+                    Some('-') if *self == Dialect::EcmaScript && !is_within_char_class => {
+                        // Escaping a hyphen outside of a character class
+                        // is a syntax error if the unicode flag is set.
+                        converted_regexp.push('-');
+                    }
+                    Some(escaped_char) => {
+                        converted_regexp.push(c);
+                        converted_regexp.push(escaped_char);
+                    }
+                    None => converted_regexp.push(c),
+                },
+                '/' if *self == Dialect::EcmaScript => converted_regexp.push_str("\\/"),
+                '[' => {
+                    is_within_char_class = true;
+                    converted_regexp.push(c);
+                }
+                ']' => {
+                    is_within_char_class = false;
+                    converted_regexp.push(c);
+                }
+                _ => converted_regexp.push(c),
+            }
+        }
+
+        converted_regexp
+    }
+
+    /// Replaces the character classes `\d`, `\w`, `\s` and their negations of a regular
+    /// expression in Rust syntax by equivalents which match the same characters as in this
+    /// dialect. This allows to verify the regular expression with the regex crate.
+    pub(crate) fn emulate_char_classes(&self, regexp: &str) -> String {
+        let mut emulated_regexp = String::with_capacity(regexp.len());
+        let mut chars = regexp.chars();
+
+        while let Some(c) = chars.next() {
+            if c != '\\' {
+                emulated_regexp.push(c);
+                continue;
+            }
+            match chars.next() {
+                Some(class) => match self.char_class_set(class.to_ascii_lowercase()) {
+                    Some(set) if class.is_ascii_uppercase() => {
+                        emulated_regexp.push_str(&format!("[^{}]", set))
+                    }
+                    Some(set) => emulated_regexp.push_str(&format!("[{}]", set)),
+                    None => {
+                        emulated_regexp.push(c);
+                        emulated_regexp.push(class);
+                    }
+                },
+                None => emulated_regexp.push(c),
+            }
+        }
+
+        emulated_regexp
+    }
+
+    fn char_class_set(&self, class: char) -> Option<&'static str> {
+        match (self, class) {
+            (Dialect::Rust | Dialect::Python | Dialect::DotNet, 'd' | 'w') => None,
+            (_, 'd') => Some("0-9"),
+            (_, 'w') => Some("0-9A-Za-z_"),
+            (Dialect::Rust | Dialect::DotNet, 's') => None,
+            (Dialect::Python, 's') => Some("\\s\\x1c-\\x1f"),
+            (Dialect::EcmaScript, 's') => Some("\\s\\x{feff}--\\x85"),
+            (Dialect::Go, 's') => Some("\\t\\n\\f\\r "),
+            (_, 's') => Some("\\t\\n\\v\\f\\r "),
+            _ => None,
+        }
+    }
+
+    /// POSIX regular expressions know neither non-capturing groups, nor shorthand
+    /// character classes, nor escape sequences for control characters. Within bracket
+    /// expressions, the backslash does not escape anything at all.
+    fn convert_to_posix_syntax(&self, regexp: &str) -> String {
+        let is_basic = *self == Dialect::PosixBasic;
+        let mut converted_regexp = String::with_capacity(regexp.len());
+        let mut chars = regexp.chars().peekable();
+
+        while let Some(c) = chars.next() {
+            match c {
+                '\u{1b}' => {
+                    converted_regexp.push(c);
+                    copy_color_code(&mut chars, &mut converted_regexp);
+                }
+                '\\' => match chars.next() {
+                    Some(class @ ('d' | 'D' | 'w' | 'W' | 's' | 'S')) => {
+                        converted_regexp.push_str(posix_char_class(class))
+                    }
+                    Some(escaped_char) if control_char(escaped_char).is_some() => {
+                        converted_regexp.push(control_char(escaped_char).unwrap())
+                    }
+                    // These characters are not special in basic regular expressions
+                    // and escaping them would turn them into operators.
+                    Some(escaped_char @ ('(' | ')' | '{' | '+' | '?' | '|')) if is_basic => {
+                        converted_regexp.push(escaped_char)
+                    }
+                    // Escaping these characters is undefined behavior.
+                    Some(escaped_char @ ('-' | ']' | '}')) => converted_regexp.push(escaped_char),
+                    Some(escaped_char) => {
+                        converted_regexp.push(c);
+                        converted_regexp.push(escaped_char);
+                    }
+                    None => converted_regexp.push(c),
+                },
+                '(' => {
+                    if chars.peek() == Some(&'?') {
+                        // Skip the `?:` of non-capturing groups.
+                        chars.nth(1);
+                    }
+                    converted_regexp.push_str(if is_basic { "\\(" } else { "(" });
+                }
+                ')' | '{' | '}' | '|' if is_basic => {
+                    converted_regexp.push('\\');
+                    converted_regexp.push(c);
+                }
+                '?' if is_basic => converted_regexp.push_str("\\{0,1\\}"),
+                '[' => converted_regexp.push_str(&convert_to_bracket_expression(&mut chars)),
+                _ => converted_regexp.push(c),
+            }
+        }
+
+        converted_regexp
+    }
+
+    /// Scripts need a prefix in some dialects to be distinguished from other properties.
+    fn unicode_property_class(&self, name: &str) -> String {
+        match self {
+            Dialect::EcmaScript if is_script_name(name) => format!("\\p{{Script={}}}", name),
+            Dialect::Java if is_script_name(name) => format!("\\p{{Is{}}}", name),
+            _ => format!("\\p{{{}}}", name),
+        }
+    }
+
+    fn unicode_escape_sequence(&self, code_point: u32) -> String {
+        let is_astral_code_point = code_point > 0xffff;
+        match self {
+            Dialect::Rust => format!("\\u{{{:x}}}", code_point),
+            Dialect::Pcre | Dialect::Go => format!("\\x{{{:x}}}", code_point),
+            Dialect::EcmaScript if is_astral_code_point => format!("\\u{{{:x}}}", code_point),
+            Dialect::Python if is_astral_code_point => format!("\\U{:08x}", code_point),
+            Dialect::Java if is_astral_code_point => format!("\\x{{{:x}}}", code_point),
+            // .NET does not know any escape sequence for astral code points.
+            Dialect::DotNet if is_astral_code_point => char::from_u32(code_point)
+                .unwrap()
+                .encode_utf16(&mut [0; 2])
+                .iter()
+                .map(|it| format!("\\u{:04x}", it))
+                .collect(),
+            _ => format!("\\u{:04x}", code_point),
+        }
+    }
+}
+
+fn copy_color_code(chars: &mut Peekable<Chars>, target: &mut String) {
+    for code_char in chars.by_ref() {
+        target.push(code_char);
+        if code_char == 'm' {
+            break;
+        }
+    }
+}
+
+fn control_char(escaped_char: char) -> Option<char> {
+    match escaped_char {
+        'n' => Some('\n'),
+        'r' => Some('\r'),
+        't' => Some('\t'),
+        'v' => Some('\u{b}'),
+        'f' => Some('\u{c}'),
+        _ => None,
+    }
+}
+
+fn posix_char_class(class: char) -> &'static str {
+    match class {
+        'd' => "[[:digit:]]",
+        'D' => "[^[:digit:]]",
+        'w' => "[[:alnum:]_]",
+        'W' => "[^[:alnum:]_]",
+        's' => "[[:space:]]",
+        _ => "[^[:space:]]",
+    }
+}
+
+/// Reads a character class in Rust syntax up to its closing bracket
+/// and converts it to a POSIX bracket expression.
+fn convert_to_bracket_expression(chars: &mut Peekable<Chars>) -> String {
+    let mut ranges: Vec<(char, char)> = vec![];
+    let mut is_range = false;
+
+    while let Some(c) = chars.next() {
+        let member = match c {
+            '\u{1b}' => {
+                copy_color_code(chars, &mut String::new());
+                continue;
+            }
+            ']' => break,
+            '-' => {
+                is_range = true;
+                continue;
+            }
+            '\\' => {
+                let escaped_char = chars.next().unwrap();
+                control_char(escaped_char).unwrap_or(escaped_char)
+            }
+            _ => c,
+        };
+        match ranges.last_mut() {
+            Some((_, end)) if is_range => *end = member,
+            _ => ranges.push((member, member)),
+        }
+        is_range = false;
+    }
+
+    // The characters `]`, `^` and `-` are only treated literally at certain
+    // positions within the bracket expression, so they are split off the ranges.
+    let special_chars = ['-', ']', '^'];
+    let mut contained_special_chars = vec![];
+    let mut members = String::new();
+
+    for (start, end) in ranges {
+        let mut current = start;
+        for special_char in special_chars {
+            if current <= special_char && special_char <= end {
+                contained_special_chars.push(special_char);
+                if current < special_char {
+                    members.push_str(&format_range(current, shift(special_char, -1)));
+                }
+                current = shift(special_char, 1);
+            }
+        }
+        if current <= end {
+            members.push_str(&format_range(current, end));
+        }
+    }
+
+    let has_bracket = contained_special_chars.contains(&']');
+    let has_caret = contained_special_chars.contains(&'^');
+    let has_hyphen = contained_special_chars.contains(&'-');
+
+    if members.is_empty() && !has_bracket && has_caret && !has_hyphen {
+        return "\\^".to_string();
+    }
+
+    let mut bracket_expression = String::from("[");
+    if has_bracket {
+        bracket_expression.push(']');
+    }
+    if members.is_empty() && !has_bracket && has_caret {
+        // A leading caret would negate the bracket expression.
+        bracket_expression.push_str("-^");
+    } else {
+        bracket_expression.push_str(&members);
+        if has_caret {
+            bracket_expression.push('^');
+        }
+        if has_hyphen {
+            bracket_expression.push('-');
+        }
+    }
+    bracket_expression.push(']');
+    bracket_expression
+}
+
+fn format_range(start: char, end: char) -> String {
+    match end as u32 - start as u32 {
+        0 => start.to_string(),
+        1 => format!("{}{}", start, end),
+        _ => format!("{}-{}", start, end),
+    }
+}
+
+fn shift(c: char, offset: i32) -> char {
+    char::from_u32((c as i32 + offset) as u32).unwrap()
+}
+
+impl Display for Dialect {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Dialect::Rust => "Rust",
+                Dialect::Pcre => "PCRE",
+                Dialect::EcmaScript => "ECMAScript",
+                Dialect::Python => "Python",
+                Dialect::Go => "Go",
+                Dialect::DotNet => ".NET",
+                Dialect::Java => "Java",
+                Dialect::PosixExtended => "POSIX ERE",
+                Dialect::PosixBasic => "POSIX BRE",
+            }
+        )
+    }
+}
+
+impl FromStr for Dialect {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "rust" => Ok(Dialect::Rust),
+            "pcre" => Ok(Dialect::Pcre),
+            "ecmascript" => Ok(Dialect::EcmaScript),
+            "python" => Ok(Dialect::Python),
+            "go" => Ok(Dialect::Go),
+            "dotnet" => Ok(Dialect::DotNet),
+            "java" => Ok(Dialect::Java),
+            "posix-ere" => Ok(Dialect::PosixExtended),
+            "posix-bre" => Ok(Dialect::PosixBasic),
+            _ => Err(format!("unknown regex dialect '{}'", s)),
+        }
+    }
+}
@@ -0,0 +1,125 @@
+/*
+ * Copyright © 2019-today Peter M. Stahl pemistahl@gmail.com
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either expressed or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::builder::{
+    CONFLICTING_TEST_CASES_MESSAGE, MAXIMUM_LENGTH_MESSAGE, MINIMUM_REPETITIONS_MESSAGE,
+    MINIMUM_SUBSTRING_LENGTH_MESSAGE, MISSING_TEST_CASES_MESSAGE,
+    POSITIONAL_CHAR_CLASS_TOLERANCE_MESSAGE,
+};
+use crate::dialect::Dialect;
+use std::fmt::{Display, Formatter, Result};
+use std::io::ErrorKind;
+
+/// This enum specifies the errors which may occur while building regular expressions.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum GrexError {
+    /// No test cases have been provided.
+    MissingTestCases,
+
+    /// The specified file could not be found.
+    FileNotFound,
+
+    /// The specified file could not be opened because of conflicting permissions.
+    PermissionDenied,
+
+    /// Reading the specified file failed for any other reason.
+    Io(ErrorKind),
+
+    /// The specified file's encoding is not valid UTF-8,
+    /// starting in the given line which is counted from 1.
+    InvalidUtf8 { line_number: usize },
+
+    /// The minimum quantity of substring repetitions has been set to zero.
+    InvalidMinimumRepetitions,
+
+    /// The minimum length of repeated substrings has been set to zero.
+    InvalidMinimumSubstringLength,
+
+    /// The tolerance of positional character classes has been set to zero.
+    InvalidPositionalCharClassTolerance,
+
+    /// The maximum length of the regular expression has been set to zero.
+    InvalidMaximumLength,
+
+    /// Even the most generalized regular expression exceeds the maximum length.
+    /// `length` is the number of characters of the shortest regular expression found.
+    MaximumLengthExceeded {
+        length: usize,
+        maximum_length: usize,
+    },
+
+    /// Some negative test cases are matched by the test cases themselves.
+    ConflictingTestCases,
+
+    /// The given feature cannot be expressed in the syntax of the selected dialect.
+    UnsupportedFeature {
+        feature: &'static str,
+        dialect: Dialect,
+    },
+}
+
+impl From<std::io::Error> for GrexError {
+    fn from(error: std::io::Error) -> Self {
+        match error.kind() {
+            ErrorKind::NotFound => GrexError::FileNotFound,
+            ErrorKind::PermissionDenied => GrexError::PermissionDenied,
+            kind => GrexError::Io(kind),
+        }
+    }
+}
+
+impl Display for GrexError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            GrexError::MissingTestCases => write!(f, "{}", MISSING_TEST_CASES_MESSAGE),
+            GrexError::FileNotFound => write!(f, "The specified file could not be found"),
+            GrexError::PermissionDenied => write!(
+                f,
+                "Permission denied: The specified file could not be opened"
+            ),
+            GrexError::Io(kind) => write!(f, "{}", std::io::Error::from(*kind)),
+            GrexError::InvalidUtf8 { line_number } => write!(
+                f,
+                "The specified file's encoding is not valid UTF-8 in line {}",
+                line_number
+            ),
+            GrexError::InvalidMinimumRepetitions => write!(f, "{}", MINIMUM_REPETITIONS_MESSAGE),
+            GrexError::InvalidMinimumSubstringLength => {
+                write!(f, "{}", MINIMUM_SUBSTRING_LENGTH_MESSAGE)
+            }
+            GrexError::InvalidPositionalCharClassTolerance => {
+                write!(f, "{}", POSITIONAL_CHAR_CLASS_TOLERANCE_MESSAGE)
+            }
+            GrexError::InvalidMaximumLength => write!(f, "{}", MAXIMUM_LENGTH_MESSAGE),
+            GrexError::MaximumLengthExceeded {
+                length,
+                maximum_length,
+            } => write!(
+                f,
+                "The shortest regular expression found has {} characters and exceeds the maximum length of {}",
+                length, maximum_length
+            ),
+            GrexError::ConflictingTestCases => write!(f, "{}", CONFLICTING_TEST_CASES_MESSAGE),
+            GrexError::UnsupportedFeature { feature, dialect } => write!(
+                f,
+                "{} is not supported by the {} regex dialect",
+                feature, dialect
+            ),
+        }
+    }
+}
+
+impl std::error::Error for GrexError {}
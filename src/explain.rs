@@ -0,0 +1,135 @@
+/*
+ * Copyright © 2019-today Peter M. Stahl pemistahl@gmail.com
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either expressed or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::ast::{Ast, CharClass};
+use crate::config::RegExpConfig;
+use crate::format::group_consecutive_chars;
+use itertools::Itertools;
+use std::collections::BTreeSet;
+
+/// Describes the given syntax tree in English, one line per node.
+/// Nested nodes are indented in the same way as the regular expression in verbose mode.
+pub(crate) fn explain(ast: &Ast, config: &RegExpConfig) -> String {
+    let mut lines = vec![];
+
+    if config.is_case_insensitive_matching {
+        lines.push("matching letters case-insensitively".to_string());
+    }
+    if !config.is_start_anchor_disabled {
+        lines.push("the start of the string".to_string());
+    }
+    explain_node(ast, 1, &mut lines);
+    if !config.is_end_anchor_disabled {
+        lines.push("the end of the string".to_string());
+    }
+
+    lines.join("\n")
+}
+
+fn explain_node(ast: &Ast, nesting_level: usize, lines: &mut Vec<String>) {
+    let indentation = "  ".repeat(nesting_level);
+
+    match ast {
+        Ast::Alternation(options) => {
+            lines.push(format!("{indentation}one of the following alternatives:"));
+            for (i, option) in options.iter().enumerate() {
+                if i > 0 {
+                    lines.push(format!("{indentation}  or"));
+                }
+                explain_node(option, nesting_level + 1, lines);
+            }
+        }
+        Ast::Concat(nodes) => {
+            for node in nodes {
+                explain_node(node, nesting_level, lines);
+            }
+        }
+        Ast::Repeat { node, min, max } => {
+            let quantity = describe_quantity(*min, *max);
+            match node.as_ref() {
+                Ast::Class(_) | Ast::Literal(_) => {
+                    lines.push(format!("{indentation}{}, {quantity}", describe_leaf(node)));
+                }
+                _ => {
+                    lines.push(format!("{indentation}the following, {quantity}:"));
+                    explain_node(node, nesting_level + 1, lines);
+                }
+            }
+        }
+        _ => lines.push(format!("{indentation}{}", describe_leaf(ast))),
+    }
+}
+
+fn describe_leaf(ast: &Ast) -> String {
+    match ast {
+        Ast::Literal(s) if s.is_empty() => "the empty string".to_string(),
+        Ast::Literal(s) if s.chars().count() == 1 => format!("the character {:?}", s),
+        Ast::Literal(s) => format!("the text {:?}", s),
+        Ast::Class(class) => describe_char_class(class),
+        _ => unreachable!("only literals and character classes are leaves"),
+    }
+}
+
+fn describe_char_class(class: &CharClass) -> String {
+    match class {
+        CharClass::Set(char_set) => {
+            format!("one of the characters {}", describe_char_set(char_set))
+        }
+        CharClass::Digit => "a digit".to_string(),
+        CharClass::NonDigit => "a character which is not a digit".to_string(),
+        CharClass::Whitespace => "a whitespace character".to_string(),
+        CharClass::NonWhitespace => "a character which is not whitespace".to_string(),
+        CharClass::Word => "a word character".to_string(),
+        CharClass::NonWord => "a character which is not a word character".to_string(),
+        CharClass::GeneralCategory(category) => {
+            format!("a character of the general category {}", category)
+        }
+        CharClass::Script(script) => {
+            format!("a character of the {} script", script.replace('_', " "))
+        }
+    }
+}
+
+fn describe_char_set(char_set: &BTreeSet<char>) -> String {
+    group_consecutive_chars(char_set)
+        .iter()
+        .flat_map(|subset| {
+            if subset.len() <= 2 {
+                subset
+                    .iter()
+                    .map(|c| format!("{:?}", c.to_string()))
+                    .collect_vec()
+            } else {
+                vec![format!(
+                    "{:?} to {:?}",
+                    subset.first().unwrap().to_string(),
+                    subset.last().unwrap().to_string()
+                )]
+            }
+        })
+        .join(", ")
+}
+
+fn describe_quantity(min: u32, max: Option<u32>) -> String {
+    match (min, max) {
+        (0, Some(1)) => "optionally".to_string(),
+        (0, None) => "zero or more times".to_string(),
+        (1, None) => "one or more times".to_string(),
+        (min, None) => format!("at least {min} times"),
+        (min, Some(max)) if min == max => format!("exactly {min} times"),
+        (min, Some(max)) => format!("between {min} and {max} times"),
+    }
+}
@@ -22,27 +22,40 @@ use crate::quantifier::Quantifier;
 use crate::substring::Substring;
 use itertools::EitherOrBoth::Both;
 use itertools::Itertools;
-use ndarray::{Array1, Array2};
 use petgraph::prelude::EdgeRef;
 use std::cmp::Reverse;
-use std::collections::BTreeSet;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::rc::Rc;
 
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum Expression<'a> {
     Alternation(Vec<Expression<'a>>, bool, bool, bool),
     CharacterClass(BTreeSet<char>, bool),
-    Concatenation(Box<Expression<'a>>, Box<Expression<'a>>, bool, bool, bool),
+    Concatenation(Rc<Expression<'a>>, Rc<Expression<'a>>, bool, bool, bool),
     Literal(GraphemeCluster<'a>, bool, bool),
-    Repetition(Box<Expression<'a>>, Quantifier, bool, bool, bool),
+    Repetition(Rc<Expression<'a>>, Quantifier, bool, bool, bool),
 }
 
 impl<'a> Expression<'a> {
-    pub(crate) fn from(dfa: Dfa, config: &'a RegExpConfig) -> Self {
+    pub(crate) fn from(
+        dfa: Dfa,
+        is_positional_char_class_inferred: bool,
+        config: &'a RegExpConfig,
+    ) -> Self {
         let states = dfa.states_in_depth_first_order();
-        let state_count = dfa.state_count();
-
-        let mut a = Array2::<Option<Expression>>::default((state_count, state_count));
-        let mut b = Array1::<Option<Expression>>::default(state_count);
+        let state_indices: HashMap<_, _> = states
+            .iter()
+            .enumerate()
+            .map(|(idx, state)| (*state, idx))
+            .collect();
+        let state_count = states.len();
+
+        // The transitions between states are stored sparsely as rows of target states,
+        // together with the source states of each column. Both are ordered by state index,
+        // so that subexpressions are created in the same order as for a dense matrix.
+        let mut a = vec![BTreeMap::<usize, Expression>::new(); state_count];
+        let mut sources = vec![BTreeSet::<usize>::new(); state_count];
+        let mut b = vec![None; state_count];
 
         for (i, state) in states.iter().enumerate() {
             if dfa.is_final_state(*state) {
@@ -57,51 +70,58 @@ impl<'a> Expression<'a> {
                     GraphemeCluster::new(edge.weight().clone(), config),
                     config,
                 );
-                let j = states.iter().position(|&it| it == edge.target()).unwrap();
+                let j = state_indices[&edge.target()];
+                let expr = Self::union(&a[i].remove(&j), &Some(literal), config);
 
-                a[(i, j)] = if a[(i, j)].is_some() {
-                    Self::union(&a[(i, j)], &Some(literal), config)
-                } else {
-                    Some(literal)
-                }
+                Self::set_transition(&mut a, &mut sources, i, j, expr);
             }
         }
 
         for n in (0..state_count).rev() {
-            if a[(n, n)].is_some() {
-                b[n] = Self::concatenate(
-                    &Self::repeat_zero_or_more_times(&a[(n, n)], config),
-                    &b[n],
-                    config,
-                );
-                for j in 0..n {
-                    a[(n, j)] = Self::concatenate(
-                        &Self::repeat_zero_or_more_times(&a[(n, n)], config),
-                        &a[(n, j)],
+            if let Some(expr) = a[n].remove(&n) {
+                let repetition = Self::repeat_zero_or_more_times(&Some(expr), config);
+                b[n] = Self::concatenate(&repetition, &b[n], config);
+
+                for (_, expr) in a[n].range_mut(..n) {
+                    *expr = Self::concatenate(&repetition, &Some(expr.clone()), config).unwrap();
+                }
+            }
+
+            let row = std::mem::take(&mut a[n]);
+            let row = row.range(..n).collect_vec();
+
+            for i in std::mem::take(&mut sources[n])
+                .into_iter()
+                .filter(|&i| i < n)
+            {
+                let expr = a[i].remove(&n);
+                b[i] = Self::union(&b[i], &Self::concatenate(&expr, &b[n], config), config);
+
+                for &(&j, expr_n_j) in row.iter() {
+                    let union = Self::union(
+                        &a[i].remove(&j),
+                        &Self::concatenate(&expr, &Some(expr_n_j.clone()), config),
                         config,
                     );
+                    Self::set_transition(&mut a, &mut sources, i, j, union);
                 }
             }
 
-            for i in 0..n {
-                if a[(i, n)].is_some() {
-                    b[i] =
-                        Self::union(&b[i], &Self::concatenate(&a[(i, n)], &b[n], config), config);
-                    for j in 0..n {
-                        a[(i, j)] = Self::union(
-                            &a[(i, j)],
-                            &Self::concatenate(&a[(i, n)], &a[(n, j)], config),
-                            config,
-                        );
-                    }
-                }
+            if n > 0 {
+                b[n] = None;
             }
         }
 
-        if !b.is_empty() && b[0].is_some() {
-            b[0].as_ref().unwrap().clone()
+        let expr = if !b.is_empty() && b[0].is_some() {
+            b[0].take().unwrap()
         } else {
             Expression::new_literal(GraphemeCluster::from("", config), config)
+        };
+
+        if is_positional_char_class_inferred {
+            expr.infer_positional_char_classes(config)
+        } else {
+            expr
         }
     }
 
@@ -126,14 +146,14 @@ impl<'a> Expression<'a> {
         Expression::CharacterClass(union_set, config.is_output_colorized)
     }
 
-    fn new_concatenation(
+    pub(crate) fn new_concatenation(
         expr1: Expression<'a>,
         expr2: Expression<'a>,
         config: &RegExpConfig,
     ) -> Self {
         Expression::Concatenation(
-            Box::from(expr1),
-            Box::from(expr2),
+            Rc::new(expr1),
+            Rc::new(expr2),
             config.is_capturing_group_enabled,
             config.is_output_colorized,
             config.is_verbose_mode_enabled,
@@ -150,7 +170,7 @@ impl<'a> Expression<'a> {
 
     fn new_repetition(expr: Expression<'a>, quantifier: Quantifier, config: &RegExpConfig) -> Self {
         Expression::Repetition(
-            Box::from(expr),
+            Rc::new(expr),
             quantifier,
             config.is_capturing_group_enabled,
             config.is_output_colorized,
@@ -186,6 +206,17 @@ impl<'a> Expression<'a> {
         }
     }
 
+    pub(crate) fn contains_alternation(&self) -> bool {
+        match self {
+            Expression::Alternation(_, _, _, _) => true,
+            Expression::Concatenation(expr1, expr2, _, _, _) => {
+                expr1.contains_alternation() || expr2.contains_alternation()
+            }
+            Expression::Repetition(expr, _, _, _, _) => expr.contains_alternation(),
+            _ => false,
+        }
+    }
+
     pub(crate) fn precedence(&self) -> u8 {
         match self {
             Expression::Alternation(_, _, _, _) | Expression::CharacterClass(_, _) => 1,
@@ -199,12 +230,12 @@ impl<'a> Expression<'a> {
             Expression::Concatenation(expr1, expr2, _, _, _) => match substring {
                 Substring::Prefix => {
                     if let Expression::Literal(_, _, _) = **expr1 {
-                        expr1.remove_substring(substring, length)
+                        Rc::make_mut(expr1).remove_substring(substring, length)
                     }
                 }
                 Substring::Suffix => {
                     if let Expression::Literal(_, _, _) = **expr2 {
-                        expr2.remove_substring(substring, length)
+                        Rc::make_mut(expr2).remove_substring(substring, length)
                     }
                 }
             },
@@ -235,6 +266,24 @@ impl<'a> Expression<'a> {
         }
     }
 
+    fn set_transition(
+        a: &mut [BTreeMap<usize, Expression<'a>>],
+        sources: &mut [BTreeSet<usize>],
+        i: usize,
+        j: usize,
+        expr: Option<Expression<'a>>,
+    ) {
+        match expr {
+            Some(expr) => {
+                a[i].insert(j, expr);
+                sources[j].insert(i);
+            }
+            None => {
+                a[i].remove(&j);
+            }
+        }
+    }
+
     fn repeat_zero_or_more_times(
         expr: &Option<Expression<'a>>,
         config: &'a RegExpConfig,
@@ -283,7 +332,7 @@ impl<'a> Expression<'a> {
                 );
                 return Some(Expression::new_concatenation(
                     literal,
-                    *second.clone(),
+                    second.as_ref().clone(),
                     config,
                 ));
             }
@@ -300,7 +349,7 @@ impl<'a> Expression<'a> {
                     config,
                 );
                 return Some(Expression::new_concatenation(
-                    *first.clone(),
+                    first.as_ref().clone(),
                     literal,
                     config,
                 ));
@@ -346,7 +395,7 @@ impl<'a> Expression<'a> {
                     if let Expression::Repetition(expr, quantifier, _, _, _) = &expr1 {
                         if quantifier == &Quantifier::QuestionMark {
                             let alternation = Expression::new_alternation(
-                                vec![*expr.clone(), expr2.clone()],
+                                vec![expr.as_ref().clone(), expr2.clone()],
                                 config,
                             );
                             result = Some(Expression::new_repetition(
@@ -362,7 +411,7 @@ impl<'a> Expression<'a> {
                     if let Expression::Repetition(expr, quantifier, _, _, _) = &expr2 {
                         if quantifier == &Quantifier::QuestionMark {
                             let alternation = Expression::new_alternation(
-                                vec![expr1.clone(), *expr.clone()],
+                                vec![expr1.clone(), expr.as_ref().clone()],
                                 config,
                             );
                             result = Some(Expression::new_repetition(
@@ -440,6 +489,189 @@ impl<'a> Expression<'a> {
         }
     }
 
+    /// Replaces alternations of equal length which only consist of single characters
+    /// with one character class per position. This is done after all unions have been
+    /// created, so that the tolerance is compared against the original alternatives
+    /// instead of alternatives which have already been generalized.
+    fn infer_positional_char_classes(self, config: &'a RegExpConfig) -> Self {
+        if let Some(expr) = Self::merge_positional_char_sets(&self, config) {
+            return expr;
+        }
+
+        match self {
+            Expression::Alternation(
+                options,
+                is_capturing_group_enabled,
+                is_output_colorized,
+                is_verbose_mode_enabled,
+            ) => Expression::Alternation(
+                options
+                    .into_iter()
+                    .map(|option| option.infer_positional_char_classes(config))
+                    .collect_vec(),
+                is_capturing_group_enabled,
+                is_output_colorized,
+                is_verbose_mode_enabled,
+            ),
+            Expression::Concatenation(
+                expr1,
+                expr2,
+                is_capturing_group_enabled,
+                is_output_colorized,
+                is_verbose_mode_enabled,
+            ) => Expression::Concatenation(
+                Rc::new(Rc::unwrap_or_clone(expr1).infer_positional_char_classes(config)),
+                Rc::new(Rc::unwrap_or_clone(expr2).infer_positional_char_classes(config)),
+                is_capturing_group_enabled,
+                is_output_colorized,
+                is_verbose_mode_enabled,
+            ),
+            Expression::Repetition(
+                expr,
+                quantifier,
+                is_capturing_group_enabled,
+                is_output_colorized,
+                is_verbose_mode_enabled,
+            ) => Expression::Repetition(
+                Rc::new(Rc::unwrap_or_clone(expr).infer_positional_char_classes(config)),
+                quantifier,
+                is_capturing_group_enabled,
+                is_output_colorized,
+                is_verbose_mode_enabled,
+            ),
+            _ => self,
+        }
+    }
+
+    /// Merges the alternatives of the given alternation into one character class per position.
+    /// The merge is refused if the character classes match more strings than the tolerance
+    /// allows for the strings matched by the alternatives.
+    fn merge_positional_char_sets(
+        alternation: &Expression<'a>,
+        config: &'a RegExpConfig,
+    ) -> Option<Expression<'a>> {
+        if !matches!(alternation, Expression::Alternation(_, _, _, _)) {
+            return None;
+        }
+
+        let char_sets = alternation.positional_char_sets()?;
+        let length = char_sets[0].len();
+        if length == 0 || char_sets.iter().any(|it| it.len() != length) {
+            return None;
+        }
+
+        let merged_char_sets = (0..length)
+            .map(|i| {
+                char_sets
+                    .iter()
+                    .flat_map(|it| it[i].iter().copied())
+                    .collect::<BTreeSet<char>>()
+            })
+            .collect_vec();
+
+        // Characters within character classes are not escaped,
+        // so the merge would silently undo the escaping of non-ASCII characters.
+        if config.is_non_ascii_char_escaped
+            && merged_char_sets
+                .iter()
+                .any(|char_set| char_set.len() > 1 && char_set.iter().any(|c| !c.is_ascii()))
+        {
+            return None;
+        }
+
+        let matched_strings_count = char_sets
+            .iter()
+            .map(|it| Self::count_matched_strings(it))
+            .fold(0, u128::saturating_add);
+
+        if Self::count_matched_strings(&merged_char_sets)
+            > matched_strings_count.saturating_mul(config.positional_char_class_tolerance as u128)
+        {
+            return None;
+        }
+
+        let mut exprs = vec![];
+        for (is_single_char, group) in &merged_char_sets
+            .into_iter()
+            .chunk_by(|char_set| char_set.len() == 1)
+        {
+            if is_single_char {
+                let graphemes = group
+                    .map(|char_set| {
+                        Grapheme::from(
+                            &char_set.first().unwrap().to_string(),
+                            config.is_capturing_group_enabled,
+                            config.is_output_colorized,
+                            config.is_verbose_mode_enabled,
+                        )
+                    })
+                    .collect_vec();
+                exprs.push(Expression::new_literal(
+                    GraphemeCluster::from_graphemes(graphemes, config),
+                    config,
+                ));
+            } else {
+                exprs.extend(group.map(|char_set| {
+                    Expression::CharacterClass(char_set, config.is_output_colorized)
+                }));
+            }
+        }
+
+        exprs
+            .into_iter()
+            .reduce(|expr1, expr2| Expression::new_concatenation(expr1, expr2, config))
+    }
+
+    /// Returns the character sets per position for each alternative of this expression
+    /// if it consists of single characters only.
+    fn positional_char_sets(&self) -> Option<Vec<Vec<BTreeSet<char>>>> {
+        match self {
+            Expression::Alternation(options, _, _, _) => options
+                .iter()
+                .map(|option| option.positional_char_sets())
+                .collect::<Option<Vec<_>>>()
+                .map(|it| it.concat()),
+            Expression::CharacterClass(char_set, _) => Some(vec![vec![char_set.clone()]]),
+            Expression::Concatenation(expr1, expr2, _, _, _) => {
+                let first_char_sets = expr1.positional_char_sets()?;
+                let second_char_sets = expr2.positional_char_sets()?;
+                Some(
+                    first_char_sets
+                        .iter()
+                        .cartesian_product(second_char_sets.iter())
+                        .map(|(first, second)| [first.clone(), second.clone()].concat())
+                        .collect_vec(),
+                )
+            }
+            Expression::Literal(cluster, _, _) => cluster
+                .graphemes()
+                .iter()
+                .map(|grapheme| {
+                    let value = grapheme.value();
+                    let mut chars = value.chars();
+                    match (chars.next(), chars.next()) {
+                        (Some(c), None)
+                            if !grapheme.has_repetitions()
+                                && grapheme.minimum() == 1
+                                && grapheme.maximum() == 1 =>
+                        {
+                            Some(btreeset![c])
+                        }
+                        _ => None,
+                    }
+                })
+                .collect::<Option<Vec<_>>>()
+                .map(|it| vec![it]),
+            Expression::Repetition(_, _, _, _, _) => None,
+        }
+    }
+
+    fn count_matched_strings(char_sets: &[BTreeSet<char>]) -> u128 {
+        char_sets
+            .iter()
+            .fold(1, |count, it| count.saturating_mul(it.len() as u128))
+    }
+
     fn extract_character_set(expr: Expression) -> BTreeSet<char> {
         match expr {
             Expression::Literal(cluster, _, _) => {
@@ -511,6 +743,7 @@ impl<'a> Expression<'a> {
 }
 
 #[cfg(test)]
+#[allow(clippy::useless_vec)]
 mod tests {
     use super::*;
 
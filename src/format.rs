@@ -138,59 +138,33 @@ fn format_character_class(
     is_output_colorized: bool,
 ) -> Result {
     let chars_to_escape = ['[', ']', '\\', '-', '^', '$'];
-    let escaped_char_set = char_set
-        .iter()
-        .map(|c| {
-            if chars_to_escape.contains(c) {
-                format!("{}{}", "\\", c)
-            } else if c == &'\n' {
-                "\\n".to_string()
-            } else if c == &'\r' {
-                "\\r".to_string()
-            } else if c == &'\t' {
-                "\\t".to_string()
-            } else {
-                c.to_string()
-            }
-        })
-        .collect_vec();
-    let char_positions = char_set
-        .iter()
-        .map(|&it| get_codepoint_position(it))
-        .collect_vec();
-
-    let mut subsets = vec![];
-    let mut subset = vec![];
-
-    for ((first_c, first_pos), (second_c, second_pos)) in
-        escaped_char_set.iter().zip(char_positions).tuple_windows()
-    {
-        if subset.is_empty() {
-            subset.push(first_c);
-        }
-        if second_pos == first_pos + 1 {
-            subset.push(second_c);
+    let escape = |c: &char| {
+        if chars_to_escape.contains(c) {
+            format!("{}{}", "\\", c)
+        } else if c == &'\n' {
+            "\\n".to_string()
+        } else if c == &'\r' {
+            "\\r".to_string()
+        } else if c == &'\t' {
+            "\\t".to_string()
         } else {
-            subsets.push(subset);
-            subset = vec![second_c];
+            c.to_string()
         }
-    }
-
-    subsets.push(subset);
+    };
 
     let mut char_class_strs = vec![];
 
-    for subset in subsets.iter() {
+    for subset in group_consecutive_chars(char_set).iter() {
         if subset.len() <= 2 {
             for c in subset.iter() {
-                char_class_strs.push((*c).to_string());
+                char_class_strs.push(escape(c));
             }
         } else {
             char_class_strs.push(format!(
                 "{}{}{}",
-                subset.first().unwrap(),
+                escape(subset.first().unwrap()),
                 Component::Hyphen.to_repr(is_output_colorized),
-                subset.last().unwrap()
+                escape(subset.last().unwrap())
             ));
         }
     }
@@ -204,6 +178,27 @@ fn format_character_class(
     )
 }
 
+/// Splits the given characters into groups of consecutive code points.
+pub(crate) fn group_consecutive_chars(char_set: &BTreeSet<char>) -> Vec<Vec<char>> {
+    let mut subsets = vec![];
+    let mut subset = vec![];
+
+    for (first_c, second_c) in char_set.iter().tuple_windows() {
+        if subset.is_empty() {
+            subset.push(*first_c);
+        }
+        if get_codepoint_position(*second_c) == get_codepoint_position(*first_c) + 1 {
+            subset.push(*second_c);
+        } else {
+            subsets.push(subset);
+            subset = vec![*second_c];
+        }
+    }
+
+    subsets.push(subset);
+    subsets
+}
+
 fn format_concatenation(
     f: &mut Formatter<'_>,
     expr: &Expression,
@@ -0,0 +1,119 @@
+/*
+ * Copyright © 2019-today Peter M. Stahl pemistahl@gmail.com
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either expressed or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::config::RegExpConfig;
+use std::fmt::{Display, Formatter, Result};
+
+/// This enum specifies the generalizations which are applied one after another
+/// if the regular expression would exceed the maximum length set with
+/// [`RegExpBuilder::with_max_length`](crate::RegExpBuilder::with_max_length).
+///
+/// Except for [`Repetitions`](Self::Repetitions), each of them lets the regular expression
+/// match strings which are not among the test cases.
+#[derive(Clone, Copy, Debug, Hash, Ord, PartialOrd, Eq, PartialEq)]
+pub enum Generalization {
+    /// Repeated substrings are converted to `{min,max}` quantifier notation.
+    Repetitions,
+
+    /// Alternatives of equal length are merged into one character class per position.
+    PositionalCharClasses,
+
+    /// Unicode decimal digits are converted to `\d`.
+    Digits,
+
+    /// Unicode whitespace characters are converted to `\s`.
+    Whitespace,
+
+    /// Unicode word characters are converted to `\w`.
+    Words,
+
+    /// Alternatives of equal length are merged into one character class per position,
+    /// regardless of how many additional strings the character classes match.
+    UnlimitedPositionalCharClasses,
+}
+
+impl Generalization {
+    /// All generalizations, ordered from the least to the most loss of precision.
+    pub(crate) const ALL: [Generalization; 6] = [
+        Generalization::Repetitions,
+        Generalization::PositionalCharClasses,
+        Generalization::Digits,
+        Generalization::Whitespace,
+        Generalization::Words,
+        Generalization::UnlimitedPositionalCharClasses,
+    ];
+
+    pub(crate) fn is_applied(&self, config: &RegExpConfig) -> bool {
+        match self {
+            Generalization::Repetitions => config.is_repetition_converted,
+            Generalization::PositionalCharClasses => config.is_positional_char_class_inferred,
+            Generalization::Digits => config.is_digit_converted,
+            Generalization::Whitespace => config.is_space_converted,
+            Generalization::Words => config.is_word_converted,
+            Generalization::UnlimitedPositionalCharClasses => {
+                config.is_positional_char_class_inferred
+                    && config.positional_char_class_tolerance == u32::MAX
+            }
+        }
+    }
+
+    pub(crate) fn apply(&self, config: &mut RegExpConfig) {
+        match self {
+            Generalization::Repetitions => config.is_repetition_converted = true,
+            Generalization::PositionalCharClasses => {
+                config.is_positional_char_class_inferred = true
+            }
+            Generalization::Digits => config.is_digit_converted = true,
+            Generalization::Whitespace => config.is_space_converted = true,
+            Generalization::Words => config.is_word_converted = true,
+            Generalization::UnlimitedPositionalCharClasses => {
+                config.is_positional_char_class_inferred = true;
+                config.positional_char_class_tolerance = u32::MAX;
+            }
+        }
+        config.generalizations.push(*self);
+    }
+
+    pub(crate) fn name(&self) -> &'static str {
+        match self {
+            Generalization::Repetitions => "repetitions",
+            Generalization::PositionalCharClasses => "positional_char_classes",
+            Generalization::Digits => "digits",
+            Generalization::Whitespace => "whitespace",
+            Generalization::Words => "words",
+            Generalization::UnlimitedPositionalCharClasses => "unlimited_positional_char_classes",
+        }
+    }
+}
+
+impl Display for Generalization {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Generalization::Repetitions => "repeated substrings converted to quantifiers",
+                Generalization::PositionalCharClasses =>
+                    "alternatives merged into character classes per position",
+                Generalization::Digits => "digits converted to \\d",
+                Generalization::Whitespace => "whitespace converted to \\s",
+                Generalization::Words => "word characters converted to \\w",
+                Generalization::UnlimitedPositionalCharClasses =>
+                    "alternatives merged into character classes per position without tolerance",
+            }
+        )
+    }
+}
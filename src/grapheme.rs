@@ -16,6 +16,8 @@
 
 use crate::component::Component;
 use itertools::Itertools;
+use lazy_static::lazy_static;
+use regex::Regex;
 use std::fmt::{Display, Formatter, Result};
 
 const CHARS_TO_ESCAPE: [&str; 14] = [
@@ -24,10 +26,16 @@ const CHARS_TO_ESCAPE: [&str; 14] = [
 
 const CHAR_CLASSES: [&str; 6] = ["\\d", "\\s", "\\w", "\\D", "\\S", "\\W"];
 
+lazy_static! {
+    static ref UNICODE_PROPERTY_CLASS: Regex = Regex::new("\\\\p\\{[A-Za-z_]+\\}").unwrap();
+}
+
 #[derive(Clone, Debug, Hash, Ord, PartialOrd, Eq, PartialEq)]
 pub struct Grapheme {
     pub(crate) chars: Vec<String>,
     pub(crate) repetitions: Vec<Grapheme>,
+    /// Whether this grapheme has been replaced with a character class created by [`case_class`].
+    pub(crate) is_case_class: bool,
     min: u32,
     max: u32,
     is_capturing_group_enabled: bool,
@@ -45,6 +53,7 @@ impl Grapheme {
         Self {
             chars: vec![s.to_string()],
             repetitions: vec![],
+            is_case_class: false,
             min: 1,
             max: 1,
             is_capturing_group_enabled,
@@ -64,6 +73,7 @@ impl Grapheme {
         Self {
             chars,
             repetitions: vec![],
+            is_case_class: false,
             min,
             max,
             is_capturing_group_enabled,
@@ -92,6 +102,10 @@ impl Grapheme {
         &mut self.repetitions
     }
 
+    pub(crate) fn contains_case_class(&self) -> bool {
+        self.is_case_class || self.repetitions.iter().any(|it| it.contains_case_class())
+    }
+
     pub(crate) fn minimum(&self) -> u32 {
         self.min
     }
@@ -130,27 +144,34 @@ impl Grapheme {
         is_non_ascii_char_escaped: bool,
         is_astral_code_point_converted_to_surrogate: bool,
     ) {
-        let characters = self.chars_mut();
+        // The brackets of case-insensitive character classes must not be escaped.
+        if !self.is_case_class {
+            let characters = self.chars_mut();
 
-        #[allow(clippy::needless_range_loop)]
-        for i in 0..characters.len() {
-            let mut character = characters[i].clone();
+            #[allow(clippy::needless_range_loop)]
+            for i in 0..characters.len() {
+                // The braces of Unicode property classes must not be escaped.
+                let mut character = String::new();
+                let mut start = 0;
 
-            for char_to_escape in CHARS_TO_ESCAPE.iter() {
-                character =
-                    character.replace(char_to_escape, &format!("{}{}", "\\", char_to_escape));
-            }
+                for class in UNICODE_PROPERTY_CLASS.find_iter(&characters[i]) {
+                    character.push_str(&escape_chars(&characters[i][start..class.start()]));
+                    character.push_str(class.as_str());
+                    start = class.end();
+                }
+                character.push_str(&escape_chars(&characters[i][start..]));
 
-            character = character
-                .replace('\n', "\\n")
-                .replace('\r', "\\r")
-                .replace('\t', "\\t");
+                character = character
+                    .replace('\n', "\\n")
+                    .replace('\r', "\\r")
+                    .replace('\t', "\\t");
 
-            if character == "\\" {
-                character = "\\\\".to_string();
-            }
+                if character == "\\" {
+                    character = "\\\\".to_string();
+                }
 
-            characters[i] = character;
+                characters[i] = character;
+            }
         }
 
         if is_non_ascii_char_escaped {
@@ -176,10 +197,36 @@ impl Grapheme {
     }
 }
 
+/// Returns the character class which matches both cases of the given letter, e.g. `[hH]`.
+pub(crate) fn case_class(c: char) -> Option<String> {
+    let mut lowercase = c.to_lowercase();
+    let mut uppercase = c.to_uppercase();
+    match (
+        lowercase.next(),
+        lowercase.next(),
+        uppercase.next(),
+        uppercase.next(),
+    ) {
+        (Some(lower), None, Some(upper), None) if lower != upper && (c == lower || c == upper) => {
+            Some(format!("[{}{}]", lower, upper))
+        }
+        _ => None,
+    }
+}
+
+fn escape_chars(s: &str) -> String {
+    let mut escaped = s.to_string();
+    for char_to_escape in CHARS_TO_ESCAPE.iter() {
+        escaped = escaped.replace(char_to_escape, &format!("{}{}", "\\", char_to_escape));
+    }
+    escaped
+}
+
 impl Display for Grapheme {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result {
         let is_single_char = self.char_count(false) == 1
-            || (self.chars.len() == 1 && self.chars[0].matches('\\').count() == 1);
+            || (self.chars.len() == 1 && self.chars[0].matches('\\').count() == 1)
+            || self.is_case_class;
         let is_range = self.min < self.max;
         let is_repetition = self.min > 1;
         let mut value = if self.repetitions.is_empty() {
@@ -187,8 +234,14 @@ impl Display for Grapheme {
         } else {
             self.repetitions.iter().map(|it| it.to_string()).join("")
         };
-        value = Component::CharClass(value.clone())
-            .to_repr(self.is_output_colorized && CHAR_CLASSES.contains(&&*value));
+        value = Component::CharClass(value.clone()).to_repr(
+            self.is_output_colorized
+                && (CHAR_CLASSES.contains(&&*value)
+                    || self.is_case_class
+                    || UNICODE_PROPERTY_CLASS
+                        .find(&value)
+                        .is_some_and(|it| it.range() == (0..value.len()))),
+        );
 
         if !is_range && is_repetition && is_single_char {
             write!(
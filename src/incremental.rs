@@ -0,0 +1,364 @@
+/*
+ * Copyright © 2019-today Peter M. Stahl pemistahl@gmail.com
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either expressed or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::builder::RegExpBuilder;
+use crate::cluster::GraphemeCluster;
+use crate::config::RegExpConfig;
+use crate::dfa::Dfa;
+use crate::error::GrexError;
+use crate::grapheme::Grapheme;
+use crate::regexp::RegExp;
+use itertools::Itertools;
+use std::collections::{HashMap, HashSet};
+
+/// This struct builds regular expressions from test cases which are inserted and removed
+/// over time. It is created with [`RegExpBuilder::to_incremental`](crate::RegExpBuilder::to_incremental).
+///
+/// Instead of building the DFA from scratch for every regular expression,
+/// it keeps the DFA between calls of [`build`](Self::build). Inserting or removing
+/// a test case only segments this single test case and re-minimizes only the states
+/// on its path.
+///
+/// This applies to all settings except for negative test cases, the conversion of
+/// repetitions and numeric ranges, case-insensitive character classes, disabling
+/// both anchors and the maximum length. As these need to know all test cases at once,
+/// the regular expression is built from scratch with them by [`RegExpBuilder`].
+pub struct IncrementalRegExpBuilder {
+    test_cases: HashSet<String>,
+    negative_test_cases: Vec<String>,
+    config: RegExpConfig,
+    trie: Option<Trie>,
+}
+
+impl IncrementalRegExpBuilder {
+    pub(crate) fn new(
+        test_cases: &[String],
+        negative_test_cases: &[String],
+        config: RegExpConfig,
+    ) -> Self {
+        let is_dfa_kept = negative_test_cases.is_empty()
+            && !config.is_repetition_converted
+            && !config.is_numeric_range_converted
+            && !config.is_case_insensitive_char_class_enabled
+            && (!config.is_start_anchor_disabled || !config.is_end_anchor_disabled)
+            && config.maximum_length.is_none();
+
+        let mut builder = Self {
+            test_cases: HashSet::new(),
+            negative_test_cases: negative_test_cases.to_vec(),
+            config,
+            trie: is_dfa_kept.then(Trie::new),
+        };
+
+        for test_case in test_cases {
+            builder.insert(test_case.clone());
+        }
+
+        builder
+    }
+
+    /// Inserts a test case to build the regular expression from.
+    ///
+    /// Returns `false` if the test case has been inserted before.
+    pub fn insert<T: Into<String>>(&mut self, test_case: T) -> bool {
+        let test_case = test_case.into();
+        if self.test_cases.contains(&test_case) {
+            return false;
+        }
+        if let Some(trie) = &mut self.trie {
+            trie.insert(graphemes(&test_case, &self.config));
+        }
+        self.test_cases.insert(test_case)
+    }
+
+    /// Removes a previously inserted test case.
+    ///
+    /// Returns `false` if the test case has not been inserted before.
+    pub fn remove(&mut self, test_case: &str) -> bool {
+        if !self.test_cases.remove(test_case) {
+            return false;
+        }
+        if let Some(trie) = &mut self.trie {
+            trie.remove(&graphemes(test_case, &self.config));
+        }
+        true
+    }
+
+    /// Returns the number of inserted test cases.
+    pub fn len(&self) -> usize {
+        self.test_cases.len()
+    }
+
+    /// Returns `true` if no test cases are inserted.
+    pub fn is_empty(&self) -> bool {
+        self.test_cases.is_empty()
+    }
+
+    /// Builds the actual regular expression from the currently inserted test cases.
+    ///
+    /// ⚠ Panics if [`try_build`](Self::try_build) returns an error.
+    pub fn build(&self) -> String {
+        self.try_build().unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// Builds the actual regular expression from the currently inserted test cases.
+    ///
+    /// Returns the same errors as [`RegExpBuilder::try_build`](crate::RegExpBuilder::try_build).
+    /// In addition, [`GrexError::MissingTestCases`] is returned if all test cases
+    /// have been removed.
+    pub fn try_build(&self) -> Result<String, GrexError> {
+        if self.test_cases.is_empty() {
+            return Err(GrexError::MissingTestCases);
+        }
+        self.config.dialect.check_support(&self.config)?;
+
+        match &self.trie {
+            Some(trie) => {
+                let dfa =
+                    Dfa::from_transitions(&trie.minimal_states(), trie.node_count(), &self.config);
+                RegExp::from_dfa(dfa, self.test_cases.len(), &self.config)
+                    .map(|regexp| regexp.to_string())
+            }
+            None => {
+                let mut builder = RegExpBuilder::try_from(&self.test_cases.iter().collect_vec())?;
+                builder.negative_test_cases = self.negative_test_cases.clone();
+                builder.config = self.config.clone();
+                builder.try_build()
+            }
+        }
+    }
+}
+
+/// Segments a single test case in the same way as [`RegExp::from`] does with all of them.
+fn graphemes(test_case: &str, config: &RegExpConfig) -> Vec<Grapheme> {
+    let mut test_cases = vec![test_case.to_string()];
+
+    if config.is_case_insensitive_matching {
+        RegExp::convert_for_case_insensitive_matching(&mut test_cases);
+    }
+
+    let mut cluster = GraphemeCluster::from(&test_cases[0], config);
+
+    if config.is_char_class_feature_enabled() {
+        cluster.convert_to_char_classes(&[]);
+    }
+
+    cluster.graphemes().clone()
+}
+
+/// The right language of a state, given by its finality and its transitions
+/// to the equivalence classes of its child states.
+type Signature = (bool, Vec<(Grapheme, usize)>);
+
+const ROOT: usize = 0;
+
+struct Node {
+    parent: Option<usize>,
+    children: Vec<(Grapheme, usize)>,
+    test_case_count: usize,
+    class: Option<usize>,
+}
+
+/// A trie of test cases whose nodes are assigned to equivalence classes of the minimal DFA.
+///
+/// As the trie is acyclic, two nodes are equivalent if and only if they are both final
+/// or non-final and their transitions lead to the same equivalence classes. A change
+/// of a node can therefore only affect the classes of its ancestors.
+struct Trie {
+    nodes: Vec<Option<Node>>,
+    free_node_indices: Vec<usize>,
+    register: HashMap<Signature, usize>,
+    classes: HashMap<usize, (Signature, usize)>,
+    next_class: usize,
+}
+
+impl Trie {
+    fn new() -> Self {
+        let mut trie = Self {
+            nodes: vec![],
+            free_node_indices: vec![],
+            register: HashMap::new(),
+            classes: HashMap::new(),
+            next_class: 0,
+        };
+        trie.add_node(None);
+        trie.update_classes(ROOT);
+        trie
+    }
+
+    fn insert(&mut self, graphemes: Vec<Grapheme>) {
+        let mut current_node = ROOT;
+
+        for grapheme in graphemes {
+            current_node = match self.find_child(current_node, &grapheme) {
+                Some(child) => child,
+                None => {
+                    let child = self.add_node(Some(current_node));
+                    self.node_mut(current_node).children.push((grapheme, child));
+                    child
+                }
+            };
+        }
+
+        self.node_mut(current_node).test_case_count += 1;
+        self.update_classes(current_node);
+    }
+
+    fn remove(&mut self, graphemes: &[Grapheme]) {
+        let mut current_node = ROOT;
+
+        for grapheme in graphemes {
+            current_node = self.find_child(current_node, grapheme).unwrap();
+        }
+
+        self.node_mut(current_node).test_case_count -= 1;
+
+        while current_node != ROOT
+            && self.node(current_node).test_case_count == 0
+            && self.node(current_node).children.is_empty()
+        {
+            let node = self.nodes[current_node].take().unwrap();
+            let parent = node.parent.unwrap();
+            self.node_mut(parent)
+                .children
+                .retain(|(_, child)| *child != current_node);
+            self.release_class(node.class.unwrap());
+            self.free_node_indices.push(current_node);
+            current_node = parent;
+        }
+
+        self.update_classes(current_node);
+    }
+
+    fn node_count(&self) -> usize {
+        self.nodes.len() - self.free_node_indices.len()
+    }
+
+    /// Returns the states of the minimal DFA, the initial state coming first.
+    fn minimal_states(&self) -> Vec<(bool, Vec<(Grapheme, usize)>)> {
+        let root_class = self.node(ROOT).class.unwrap();
+        let mut state_indices = HashMap::from([(root_class, 0)]);
+        let mut classes = vec![root_class];
+        let mut states = vec![];
+
+        while states.len() < classes.len() {
+            let (is_final, transitions) = &self.classes[&classes[states.len()]].0;
+            let transitions = transitions
+                .iter()
+                .map(|(grapheme, class)| {
+                    let state_idx = *state_indices.entry(*class).or_insert_with(|| {
+                        classes.push(*class);
+                        classes.len() - 1
+                    });
+                    (grapheme.clone(), state_idx)
+                })
+                .collect_vec();
+            states.push((*is_final, transitions));
+        }
+
+        states
+    }
+
+    /// Recomputes the equivalence classes of the given node and its ancestors,
+    /// stopping at the first node whose class does not change.
+    fn update_classes(&mut self, node_idx: usize) {
+        let mut current_node = Some(node_idx);
+
+        while let Some(node_idx) = current_node {
+            let node = self.node(node_idx);
+            let signature: Signature = (
+                node.test_case_count > 0,
+                node.children
+                    .iter()
+                    .map(|(grapheme, child)| (grapheme.clone(), self.node(*child).class.unwrap()))
+                    .sorted()
+                    .collect_vec(),
+            );
+            let old_class = node.class;
+            let new_class = self.acquire_class(signature);
+
+            if let Some(old_class) = old_class {
+                self.release_class(old_class);
+            }
+            if old_class == Some(new_class) {
+                break;
+            }
+
+            let node = self.node_mut(node_idx);
+            node.class = Some(new_class);
+            current_node = node.parent;
+        }
+    }
+
+    fn acquire_class(&mut self, signature: Signature) -> usize {
+        let class = match self.register.get(&signature) {
+            Some(&class) => class,
+            None => {
+                let class = self.next_class;
+                self.next_class += 1;
+                self.register.insert(signature.clone(), class);
+                self.classes.insert(class, (signature, 0));
+                class
+            }
+        };
+        self.classes.get_mut(&class).unwrap().1 += 1;
+        class
+    }
+
+    fn release_class(&mut self, class: usize) {
+        let (_, node_count) = self.classes.get_mut(&class).unwrap();
+        *node_count -= 1;
+        if *node_count == 0 {
+            let (signature, _) = self.classes.remove(&class).unwrap();
+            self.register.remove(&signature);
+        }
+    }
+
+    fn find_child(&self, node_idx: usize, grapheme: &Grapheme) -> Option<usize> {
+        self.node(node_idx)
+            .children
+            .iter()
+            .find(|(label, _)| label == grapheme)
+            .map(|(_, child)| *child)
+    }
+
+    fn add_node(&mut self, parent: Option<usize>) -> usize {
+        let node = Node {
+            parent,
+            children: vec![],
+            test_case_count: 0,
+            class: None,
+        };
+        match self.free_node_indices.pop() {
+            Some(idx) => {
+                self.nodes[idx] = Some(node);
+                idx
+            }
+            None => {
+                self.nodes.push(Some(node));
+                self.nodes.len() - 1
+            }
+        }
+    }
+
+    fn node(&self, idx: usize) -> &Node {
+        self.nodes[idx].as_ref().unwrap()
+    }
+
+    fn node_mut(&mut self, idx: usize) -> &mut Node {
+        self.nodes[idx].as_mut().unwrap()
+    }
+}
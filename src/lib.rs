@@ -65,16 +65,24 @@
 //! - character classes
 //! - detection of common prefixes and suffixes
 //! - detection of repeated substrings and conversion to `{min,max}` quantifier notation
+//! - conversion of numbers to expressions matching exactly their numeric range
+//! - conversion to Unicode general categories and scripts such as `\p{Lu}` or `\p{Greek}`
+//! - inference of one character class per position from alternatives of equal length
 //! - alternation using `|` operator
 //! - optionality using `?` quantifier
 //! - escaping of non-ascii characters, with optional conversion of astral code points to surrogate pairs
-//! - case-sensitive or case-insensitive matching
+//! - case-sensitive or case-insensitive matching, either globally or per letter such as `[hH]`
 //! - capturing or non-capturing groups
 //! - optional anchors `^` and `$`
+//! - exclusion of negative test cases which must not be matched
+//! - incremental building from test cases which are inserted and removed over time
+//! - optional maximum length reached by progressively generalizing the expression
 //! - fully compliant to [Unicode Standard 15.0](https://unicode.org/versions/Unicode15.0.0)
 //! - fully compatible with [*regex* crate 1.9.0+](https://crates.io/crates/regex)
 //! - correctly handles graphemes consisting of multiple Unicode symbols
-//! - reads input strings from the command-line or from a file
+//! - reads input strings from the command-line, from multiple files and directories or streamed line by line from standard input
+//! - reads input files as lines, NUL-delimited, CSV and TSV columns, JSON arrays or JSON lines
+//! - builds regular expressions incrementally in an interactive session
 //! - produces more readable expressions indented on multiple using optional verbose mode
 //!
 //! ## 4. How to use?
@@ -249,6 +257,308 @@
 //! assert_eq!(regexp, "a(?:aa?)?");
 //! ```
 //!
+//! ### 4.9 Exclude negative test cases
+//!
+//! Conversions which generalize the test cases, such as the conversion to character classes,
+//! may lead to expressions that match more than desired. Test cases which must not be matched
+//! can be specified as well. Wherever a conversion would lead to a match of one of them,
+//! it is backed off again.
+//!
+//! ```
+//! use grex::RegExpBuilder;
+//!
+//! let regexp = RegExpBuilder::from(&["a1", "b2"])
+//!     .with_negative_examples(&["c3"])
+//!     .with_conversion_of_words()
+//!     .build();
+//! assert_eq!(regexp, "^[ab]\\w$");
+//! ```
+//!
+//! ### 4.10 Handle errors
+//!
+//! The methods shown above panic if invalid input is provided, e.g. an empty collection of
+//! test cases or a file that cannot be read. For each of them, a fallible counterpart is
+//! available which returns a [`GrexError`] instead.
+//!
+//! ```
+//! use grex::{GrexError, RegExpBuilder};
+//!
+//! let test_cases: Vec<&str> = vec![];
+//! let result = RegExpBuilder::try_from(&test_cases);
+//! assert_eq!(result.err(), Some(GrexError::MissingTestCases));
+//!
+//! let regexp = RegExpBuilder::try_from(&["a", "aa", "aaa"])
+//!     .and_then(|mut builder| builder.try_build());
+//! assert_eq!(regexp, Ok("^a(?:aa?)?$".to_string()));
+//! ```
+//!
+//! ### 4.11 Target other regex dialects
+//!
+//! By default, the resulting regular expression follows the syntax of the *regex* crate.
+//! Other regex engines differ in their escape sequences, flags and anchors. Some of them,
+//! such as JavaScript or Go, match only ASCII characters with `\d`, `\w` and `\s`, so other
+//! characters are not converted to these classes. Verbose mode is not available for JavaScript
+//! and Go.
+//!
+//! ```
+//! use grex::{Dialect, RegExpBuilder};
+//!
+//! let regexp = RegExpBuilder::from(&["a", "B", "ä"])
+//!     .with_case_insensitive_matching()
+//!     .with_escaping_of_non_ascii_chars(false)
+//!     .with_dialect(Dialect::EcmaScript)
+//!     .build();
+//! assert_eq!(regexp, "/^(?:[ab]|\\u00e4)$/iu");
+//!
+//! let regexp = RegExpBuilder::from(&["1", "٣"])
+//!     .with_conversion_of_digits()
+//!     .with_dialect(Dialect::Python)
+//!     .build();
+//! assert_eq!(regexp, "^\\d\\Z");
+//! ```
+//!
+//! POSIX extended and basic regular expressions, as understood by `grep`, `sed` and `awk`,
+//! only know capturing groups and bracket expressions such as `[[:digit:]]`. Case-insensitive
+//! matching and the escaping of non-ASCII characters cannot be expressed in them, and basic
+//! regular expressions do not support alternation either. Such settings result in an error.
+//!
+//! ```
+//! use grex::{Dialect, RegExpBuilder};
+//!
+//! let regexp = RegExpBuilder::from(&["a1", "b22"])
+//!     .with_conversion_of_digits()
+//!     .with_dialect(Dialect::PosixExtended)
+//!     .build();
+//! assert_eq!(regexp, "^(b[[:digit:]]|a)[[:digit:]]$");
+//! ```
+//!
+//! ### 4.12 Inspect the syntax tree
+//!
+//! Instead of a string, the regular expression can also be built as a syntax tree
+//! which can be inspected or translated into other notations. The tree consists of
+//! alternations, character classes, concatenations, literals and repetitions with
+//! explicit bounds. Anchors and flags are not part of it.
+//!
+//! ```
+//! use grex::{Ast, CharClass, RegExpBuilder};
+//!
+//! let ast = RegExpBuilder::from(&["a1", "aa2"])
+//!     .with_conversion_of_digits()
+//!     .build_ast();
+//! assert_eq!(
+//!     ast,
+//!     Ast::Concat(vec![
+//!         Ast::Literal("a".to_string()),
+//!         Ast::Repeat {
+//!             node: Box::new(Ast::Literal("a".to_string())),
+//!             min: 0,
+//!             max: Some(1)
+//!         },
+//!         Ast::Class(CharClass::Digit)
+//!     ])
+//! );
+//! ```
+//!
+//! ### 4.13 Obtain a report
+//!
+//! When grex is used within automated pipelines, it can be helpful to know how the regular
+//! expression has been generated. A report contains the regular expression together with the
+//! settings, the number of unique test cases and the number of DFA states before and after
+//! minimization. It also tells whether grex had to fall back to a plain alternation of the
+//! test cases because no generalized expression could be found.
+//!
+//! ```
+//! use grex::RegExpBuilder;
+//!
+//! let report = RegExpBuilder::from(&["abc", "xbc", "abc"]).build_report();
+//! assert_eq!(report.regexp(), "^[ax]bc$");
+//! assert_eq!(report.test_case_count(), 2);
+//! assert_eq!(report.dfa_state_count(), 7);
+//! assert_eq!(report.minimized_dfa_state_count(), 4);
+//! assert!(!report.is_fallback_taken());
+//! ```
+//!
+//! The report can be serialized to JSON with `to_json()` which is also what the command-line
+//! tool prints with `--output-format json`.
+//!
+//! ### 4.14 Convert numeric ranges
+//!
+//! Numbers such as years or port numbers are usually meant to be matched within a certain range.
+//! Test cases which only differ in their numbers can be converted to an expression which matches
+//! exactly the range between their smallest and their largest number. Only runs of ASCII digits
+//! without leading zeros are treated as numbers.
+//!
+//! ```
+//! use grex::RegExpBuilder;
+//!
+//! let regexp = RegExpBuilder::from(&["1980", "2029", "1999"])
+//!     .with_conversion_of_numeric_ranges()
+//!     .build();
+//! assert_eq!(regexp, "^(?:19[89][0-9]|20[0-2][0-9])$");
+//!
+//! let regexp = RegExpBuilder::from(&["port 80", "port 443"])
+//!     .with_conversion_of_numeric_ranges()
+//!     .build();
+//! assert_eq!(regexp, "^port (?:[1-3][0-9][0-9]|4[0-3][0-9]|44[0-3]|[89][0-9])$");
+//! ```
+//!
+//! ### 4.15 Explain the regular expression
+//!
+//! If a regular expression is hard to read, it can be described in plain English.
+//! Each part of the expression is described on its own line, nested parts are indented
+//! in the same way as in verbose mode. The command-line tool prints the description
+//! below the expression with `--explain`.
+//!
+//! ```
+//! use grex::RegExpBuilder;
+//! use indoc::indoc;
+//!
+//! let explanation = RegExpBuilder::from(&["abcabc", "xyz", "a"])
+//!     .with_conversion_of_repetitions()
+//!     .explain();
+//! assert_eq!(explanation, indoc!(
+//!     r#"
+//!     the start of the string
+//!       one of the following alternatives:
+//!         the text "xyz"
+//!         or
+//!         the character "a"
+//!         or
+//!         the text "abc", exactly 2 times
+//!     the end of the string"#
+//! ));
+//! ```
+//!
+//! ### 4.16 Convert to Unicode property classes
+//!
+//! Characters can be generalized to the Unicode general category or the Unicode script
+//! they belong to, e.g. `\p{Lu}` for uppercase letters or `\p{Greek}` for Greek characters.
+//! Control, format, private use and unassigned characters are never converted to a general category,
+//! characters shared by several scripts such as ASCII digits and punctuation are never converted
+//! to a script.
+//!
+//! If several conversions apply to the same character, digits and whitespace are converted first,
+//! then general categories, then scripts and finally all remaining character classes.
+//! With case-insensitive matching, upper, lower and title case letters are not converted
+//! to their general category as `\p{Lu}` would also match lowercase letters then.
+//! Python and POSIX regular expressions support neither property, .NET does not support scripts.
+//!
+//! ```
+//! use grex::RegExpBuilder;
+//!
+//! let regexp = RegExpBuilder::from(&["Ab1", "Xy2"])
+//!     .with_conversion_of_general_categories()
+//!     .build();
+//! assert_eq!(regexp, "^\\p{Lu}\\p{Ll}\\p{Nd}$");
+//!
+//! let regexp = RegExpBuilder::from(&["αβγ", "абв"])
+//!     .with_conversion_of_scripts()
+//!     .with_conversion_of_repetitions()
+//!     .build();
+//! assert_eq!(regexp, "^(?:\\p{Greek}{3}|\\p{Cyrillic}{3})$");
+//! ```
+//!
+//! ### 4.17 Infer character classes per position
+//!
+//! Test cases of equal length which differ in several positions are usually combined
+//! into an alternation. Optionally, they can be generalized to one character class per position
+//! instead. As this matches more strings than the test cases, the merge is only done if the
+//! character classes match at most four times as many strings as the test cases. This tolerance
+//! can be changed with `with_positional_char_class_tolerance()` or `--positional-tolerance`.
+//! Character classes which would match a negative test case are not inferred.
+//!
+//! ```
+//! use grex::RegExpBuilder;
+//!
+//! let regexp = RegExpBuilder::from(&["a1x", "b2x", "c3x"])
+//!     .with_positional_char_classes()
+//!     .build();
+//! assert_eq!(regexp, "^[a-c][1-3]x$");
+//!
+//! let regexp = RegExpBuilder::from(&["a1x", "b2x", "c3x"])
+//!     .with_positional_char_classes()
+//!     .with_positional_char_class_tolerance(2)
+//!     .build();
+//! assert_eq!(regexp, "^(?:[ab][12]|c3)x$");
+//! ```
+//!
+//! ### 4.18 Case-insensitive character classes
+//!
+//! Instead of making the whole regular expression case-insensitive with the `(?i)` flag,
+//! test cases which only differ in the case of some letters can be merged by converting
+//! exactly these letters to character classes. All other letters stay case-sensitive.
+//! This is useful for regex engines which do not support the `(?i)` flag.
+//!
+//! ```
+//! use grex::RegExpBuilder;
+//!
+//! let regexp = RegExpBuilder::from(&["Hello", "hello", "world"])
+//!     .with_case_insensitive_char_classes()
+//!     .build();
+//! assert_eq!(regexp, "^(?:[hH]ello|world)$");
+//! ```
+//!
+//! ### 4.19 Build incrementally
+//!
+//! If regular expressions are built repeatedly from test cases which change only slightly,
+//! an `IncrementalRegExpBuilder` avoids building everything from scratch. It keeps the DFA
+//! between builds, so inserting or removing a test case only updates the states affected by it.
+//!
+//! ```
+//! use grex::RegExpBuilder;
+//!
+//! let mut builder = RegExpBuilder::from(&["a1", "b2"])
+//!     .with_conversion_of_digits()
+//!     .to_incremental();
+//! assert_eq!(builder.build(), "^[ab]\\d$");
+//!
+//! builder.insert("c3");
+//! builder.remove("a1");
+//! assert_eq!(builder.build(), "^[bc]\\d$");
+//! ```
+//!
+//! ### 4.20 Stream test cases
+//!
+//! Large sets of test cases need not be collected into a slice first. `RegExpBuilder::from_iter`
+//! takes them from any iterator, `RegExpBuilder::from_reader` reads them line by line from any
+//! buffered reader. Duplicate test cases are dropped right away, but all distinct test cases are
+//! kept in memory until the regular expression is built, as the settings which determine how they
+//! are converted are only known by then. Both have fallible variants `try_from_iter` and
+//! `try_from_reader`.
+//!
+//! ```
+//! use grex::RegExpBuilder;
+//! use std::io::Cursor;
+//!
+//! let regexp = RegExpBuilder::from_iter((1..=3).map(|n| "a".repeat(n))).build();
+//! assert_eq!(regexp, "^a(?:aa?)?$");
+//!
+//! let regexp = RegExpBuilder::from_reader(Cursor::new("a\naa\naaa\na\n")).build();
+//! assert_eq!(regexp, "^a(?:aa?)?$");
+//! ```
+//!
+//! ### 4.21 Limit the length
+//!
+//! If the regular expression must not exceed a certain number of characters, `with_max_length`
+//! generalizes it step by step until it fits. Repeated substrings are converted to quantifiers
+//! first, followed by positional character classes, `\d`, `\s` and `\w`, losing more precision
+//! with each step. Steps which would not shorten the expression are skipped and settings enabled
+//! explicitly are kept. The report lists the generalizations applied, and `try_build` returns an
+//! error if even the most general expression is too long.
+//!
+//! ```
+//! use grex::{Generalization, RegExpBuilder};
+//!
+//! let report = RegExpBuilder::from(&["a1", "b22", "c333", "d4444", "e55555"])
+//!     .with_max_length(20)
+//!     .build_report();
+//! assert_eq!(report.regexp(), "^\\w\\d{1,5}$");
+//! assert_eq!(
+//!     report.generalizations(),
+//!     &[Generalization::Repetitions, Generalization::Digits, Generalization::Words]
+//! );
+//! ```
+//!
 //! ### 5. How does it work?
 //!
 //! 1. A [deterministic finite automaton](https://en.wikipedia.org/wiki/Deterministic_finite_automaton) (DFA)
@@ -261,19 +571,29 @@
 //! [Brzozowski's algebraic method](http://cs.stackexchange.com/questions/2016/how-to-convert-finite-automata-to-regular-expressions#2392),
 //! resulting in the final regular expression.
 
+#![allow(clippy::doc_lazy_continuation)]
+
 #[macro_use]
 mod macros;
 
+mod ast;
 mod builder;
 mod cluster;
 mod component;
 mod config;
 mod dfa;
+mod dialect;
+mod error;
+mod explain;
 mod expression;
 mod format;
+mod generalization;
 mod grapheme;
+mod incremental;
+mod numeric;
 mod quantifier;
 mod regexp;
+mod report;
 mod substring;
 mod unicode_tables;
 
@@ -283,7 +603,13 @@ mod python;
 #[cfg(target_family = "wasm")]
 mod wasm;
 
+pub use ast::{Ast, CharClass};
 pub use builder::RegExpBuilder;
+pub use dialect::Dialect;
+pub use error::GrexError;
+pub use generalization::Generalization;
+pub use incremental::IncrementalRegExpBuilder;
+pub use report::Report;
 
 #[cfg(target_family = "wasm")]
-pub use wasm::RegExpBuilder as WasmRegExpBuilder;
+pub use wasm::{BuildResult, RegExpBuilder as WasmRegExpBuilder, RegExpBuilderOptions};
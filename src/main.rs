@@ -16,12 +16,21 @@
 
 #[cfg(not(target_family = "wasm"))]
 mod cli {
+    use clap::builder::{PossibleValuesParser, TypedValueParser};
     use clap::ArgAction;
+    use clap::ArgGroup;
     use clap::Parser;
-    use grex::RegExpBuilder;
+    use clap::ValueEnum;
+    use clap::{Args, Subcommand};
+    use grex::{Dialect, IncrementalRegExpBuilder, RegExpBuilder};
     use itertools::Itertools;
-    use std::io::{stdin, BufRead, Error, ErrorKind, IsTerminal, Read};
-    use std::path::PathBuf;
+    use regex::Regex;
+    use rustyline::error::ReadlineError;
+    use rustyline::DefaultEditor;
+    use std::collections::HashMap;
+    use std::fs::File;
+    use std::io::{stdin, BufRead, BufReader, Error, ErrorKind, IsTerminal};
+    use std::path::{Path, PathBuf};
 
     #[derive(Parser)]
     #[command(
@@ -31,25 +40,32 @@ mod cli {
                  Source code at https://github.com/pemistahl/grex\n\n\
                  grex generates regular expressions from user-provided test cases.",
         version,
-        override_usage = "grex [OPTIONS] {INPUT...|--file <FILE>}",
+        override_usage = "grex [OPTIONS] {INPUT...|--file <FILE>...|--interactive}\n       \
+                          grex verify --regex <REGEX> {--positives <FILE>|--negatives <FILE>}",
         help_template = "{name} {version}\n{author}\n{about}\n\n{usage-heading} {usage}\n\n{all-args}",
         disable_help_flag = true,
-        disable_version_flag = true
+        disable_version_flag = true,
+        args_conflicts_with_subcommands = true,
+        subcommand_negates_reqs = true,
+        disable_help_subcommand = true
     )]
     pub(crate) struct Cli {
+        #[command(subcommand)]
+        pub(crate) command: Option<Command>,
+
         // --------------------
         // INPUT
         // --------------------
         /// One or more test cases separated by blank space
         ///
         /// Use a hyphen `-` to read test cases from standard input.
+        /// Precede the test cases with `--` if the first one is `verify`, e.g. `grex -- verify`.
         ///
-        /// Conflicts with --file.
+        /// Can be combined with --file.
         #[arg(
             value_name = "INPUT",
             allow_hyphen_values = true,
-            required_unless_present = "file",
-            conflicts_with = "file",
+            required_unless_present_any = ["file", "interactive"],
             help_heading = "Input",
             display_order = 1
         )]
@@ -60,19 +76,138 @@ mod cli {
         /// Lines may be ended with either a newline `\n` or a carriage return with a line feed `\r\n`.
         /// The final line ending is optional.
         ///
-        /// Use a hyphen `-` to read the filename from standard input.
+        /// Use --input-format to read test cases in other formats.
+        ///
+        /// Can be given multiple times and combined with INPUT...
+        /// Directories are read recursively, glob patterns such as `examples/*.txt`
+        /// are expanded to all matching files.
         ///
-        /// Conflicts with INPUT...
+        /// Use a hyphen `-` to read the filenames from standard input, one per line.
         #[arg(
             name = "file",
             value_name = "FILE",
             short,
             long,
-            required_unless_present = "input",
+            action = ArgAction::Append,
+            required_unless_present_any = ["input", "interactive"],
             help_heading = "Input",
             display_order = 2
         )]
-        file_path: Option<PathBuf>,
+        file_paths: Vec<PathBuf>,
+
+        /// Reads the entire content of each file given with --file as a single test case.
+        ///
+        /// A single final line ending is removed from the content.
+        #[arg(
+            name = "whole-files",
+            long,
+            requires = "file",
+            conflicts_with_all = ["input-format", "column", "header", "json-path"],
+            help_heading = "Input",
+            display_order = 3
+        )]
+        are_whole_files_read: bool,
+
+        /// Specifies a test case which must not be matched by the resulting regular expression.
+        ///
+        /// Conversions to character classes and quantifiers are backed off wherever they
+        /// would lead to a match of one of these test cases.
+        ///
+        /// Can be given multiple times.
+        #[arg(
+            name = "exclude",
+            value_name = "TEST_CASE",
+            long,
+            allow_hyphen_values = true,
+            help_heading = "Input",
+            display_order = 4
+        )]
+        negative_input: Vec<String>,
+
+        /// Reads test cases on separate lines from a file which must not be matched
+        /// by the resulting regular expression.
+        ///
+        /// Lines may be ended with either a newline `\n` or a carriage return with a line feed `\r\n`.
+        /// The final line ending is optional.
+        #[arg(
+            name = "exclude-file",
+            value_name = "FILE",
+            long,
+            help_heading = "Input",
+            display_order = 5
+        )]
+        negative_file_path: Option<PathBuf>,
+
+        /// Starts an interactive session for building the regular expression incrementally.
+        ///
+        /// Test cases can be added and removed and options can be switched on and off,
+        /// the regular expression is regenerated after each change. Test cases and options
+        /// given on the command-line are used as the initial state of the session.
+        ///
+        /// Type `:help` in the session for a list of all commands.
+        #[arg(
+            name = "interactive",
+            long,
+            conflicts_with = "output-format",
+            help_heading = "Input",
+            display_order = 6
+        )]
+        pub(crate) is_interactive: bool,
+
+        /// Specifies the format of the test cases read from a file or standard input.
+        ///
+        /// lines: one test case per line,
+        /// nul: test cases separated by NUL characters,
+        /// csv and tsv: one column of comma- or tab-separated values,
+        /// json: an array of values,
+        /// jsonl: one JSON value per line.
+        ///
+        /// Applies to --exclude-file as well.
+        #[arg(
+            name = "input-format",
+            value_name = "FORMAT",
+            long,
+            value_enum,
+            default_value_t = InputFormat::Lines,
+            help_heading = "Input",
+            display_order = 7
+        )]
+        input_format: InputFormat,
+
+        /// Selects the column of csv or tsv input to read test cases from.
+        ///
+        /// A number selects the column by its position, counted from 1.
+        /// A name selects the column of this name in the header row, which implies --header.
+        /// Defaults to the first column.
+        #[arg(
+            name = "column",
+            value_name = "NAME|INDEX",
+            long,
+            help_heading = "Input",
+            display_order = 8
+        )]
+        column: Option<String>,
+
+        /// Treats the first row of csv or tsv input as header and skips it.
+        ///
+        /// Without it, all rows are read unless --column selects a column by name.
+        #[arg(name = "header", long, help_heading = "Input", display_order = 9)]
+        has_header: bool,
+
+        /// Selects the value of json or jsonl input to read test cases from.
+        ///
+        /// The path consists of object keys and array indices separated by dots,
+        /// such as `user.emails.0`. For json input, it is applied to each element
+        /// of the top-level array. Without a path, the values themselves are read.
+        /// Only strings and numbers are valid test cases.
+        #[arg(
+            name = "json-path",
+            value_name = "PATH",
+            long,
+            help_heading = "Input",
+            display_order = 10
+        )]
+        json_path: Option<String>,
 
         // --------------------
         // DIGIT OPTIONS
@@ -97,6 +232,14 @@ mod cli {
         #[arg(name = "non-digits", short = 'D', long, help_heading = "Digit Options")]
         is_non_digit_converted: bool,
 
+        /// Converts numbers to an expression matching exactly their numeric range.
+        ///
+        /// Test cases which only differ in their numbers are grouped together.
+        /// For each group, the range between the smallest and the largest number is matched.
+        /// Only runs of ASCII digits without leading zeros are treated as numbers.
+        #[arg(name = "numeric-ranges", long, help_heading = "Digit Options")]
+        is_numeric_range_converted: bool,
+
         // --------------------
         // WHITESPACE OPTIONS
         // --------------------
@@ -139,6 +282,51 @@ mod cli {
         #[arg(name = "non-words", short = 'W', long, help_heading = "Word Options")]
         is_non_word_converted: bool,
 
+        // --------------------
+        // UNICODE PROPERTY OPTIONS
+        // --------------------
+        /// Converts any character to the class of its Unicode general category, e.g. \p{Lu}.
+        ///
+        /// Control, format, private use and unassigned characters are not converted.
+        /// --digits and --spaces take precedence over this option if both are set.
+        /// Takes precedence over --scripts and the remaining conversion options if both are set.
+        #[arg(name = "categories", long, help_heading = "Unicode Property Options")]
+        is_general_category_converted: bool,
+
+        /// Converts any character to the class of its Unicode script, e.g. \p{Greek}.
+        ///
+        /// Characters shared by several scripts, such as ASCII digits, are not converted.
+        /// --digits, --spaces and --categories take precedence over this option if both are set.
+        /// Takes precedence over --words and the remaining conversion options if both are set.
+        #[arg(name = "scripts", long, help_heading = "Unicode Property Options")]
+        is_script_converted: bool,
+
+        // --------------------
+        // CHARACTER CLASS OPTIONS
+        // --------------------
+        /// Merges alternatives of equal length into one character class per position.
+        ///
+        /// For example, the test cases a1x, b2x and c3x result in [a-c][1-3]x.
+        #[arg(
+            name = "positional-classes",
+            long,
+            help_heading = "Character Class Options",
+            display_order = 1
+        )]
+        is_positional_char_class_inferred: bool,
+
+        /// Specifies how many strings the character classes may match for each merged alternative
+        /// if --positional-classes is set.
+        #[arg(
+            name = "positional-tolerance",
+            value_name = "FACTOR",
+            long,
+            default_value_t = 4,
+            value_parser = positional_tolerance_parser,
+            help_heading = "Character Class Options"
+        )]
+        positional_char_class_tolerance: u32,
+
         // --------------------
         // ESCAPING OPTIONS
         // --------------------
@@ -244,6 +432,51 @@ mod cli {
         #[arg(name = "colorize", short, long, help_heading = "Display Options")]
         is_output_colorized: bool,
 
+        /// Targets the resulting regular expression at the syntax of the given regex engine.
+        ///
+        /// The ecmascript dialect produces a regular expression literal such as `/^a$/u`.
+        #[arg(
+            name = "dialect",
+            value_name = "DIALECT",
+            long,
+            default_value = "rust",
+            value_parser = dialect_parser(),
+            help_heading = "Display Options"
+        )]
+        dialect: Dialect,
+
+        /// Describes each part of the resulting regular expression in English.
+        ///
+        /// The description is printed below the regular expression.
+        /// Its indentation follows the layout of verbose mode.
+        ///
+        /// Conflicts with --output-format.
+        #[arg(
+            name = "explain",
+            long,
+            conflicts_with = "output-format",
+            help_heading = "Display Options"
+        )]
+        is_explanation_printed: bool,
+
+        /// Specifies how the resulting regular expression is printed.
+        ///
+        /// The json format prints an object which additionally contains the settings,
+        /// the number of unique test cases, the number of DFA states before and after
+        /// minimization and whether grex fell back to a plain alternation of the test cases.
+        ///
+        /// Conflicts with --colorize.
+        #[arg(
+            name = "output-format",
+            value_name = "FORMAT",
+            long,
+            value_enum,
+            default_value_t = OutputFormat::Text,
+            conflicts_with = "colorize",
+            help_heading = "Display Options"
+        )]
+        output_format: OutputFormat,
+
         // ---------------------
         // MISCELLANEOUS OPTIONS
         // ---------------------
@@ -267,6 +500,33 @@ mod cli {
         )]
         is_group_captured: bool,
 
+        /// Converts letters to character classes such as [hH] where test cases differ in case only
+        #[arg(
+            name = "case-classes",
+            long,
+            help_heading = "Miscellaneous Options",
+            conflicts_with = "ignore-case",
+            display_order = 3
+        )]
+        is_case_class_enabled: bool,
+
+        /// Limits the resulting regular expression to the given number of characters.
+        ///
+        /// If the exact regular expression is longer, it is generalized step by step
+        /// by converting repetitions, merging alternatives into character classes per position
+        /// and converting digits, whitespace and word characters to \d, \s and \w.
+        /// Steps which would not shorten the expression are skipped.
+        /// The applied generalizations are printed to standard error.
+        #[arg(
+            name = "max-length",
+            value_name = "LENGTH",
+            long,
+            value_parser = repetition_options_parser,
+            help_heading = "Miscellaneous Options",
+            display_order = 4
+        )]
+        maximum_length: Option<u32>,
+
         /// Prints help information
         #[arg(
             name = "help",
@@ -274,7 +534,7 @@ mod cli {
             long,
             action = ArgAction::Help,
             help_heading = "Miscellaneous Options",
-            display_order = 3
+            display_order = 5
         )]
         help: Option<String>,
 
@@ -285,137 +545,1062 @@ mod cli {
             long,
             action = ArgAction::Version,
             help_heading = "Miscellaneous Options",
-            display_order = 4
+            display_order = 6
         )]
         version: Option<String>,
     }
 
-    pub(crate) fn obtain_input(cli: &Cli) -> Result<Vec<String>, Error> {
-        let is_stdin_available = !stdin().is_terminal();
+    #[derive(Subcommand)]
+    pub(crate) enum Command {
+        /// Verifies a regular expression against test cases read from files
+        ///
+        /// Every line of the positive file must be matched by the regular expression,
+        /// no line of the negative file must be matched. All failing lines are reported
+        /// with their line numbers and the exit code is non-zero if any line fails.
+        Verify(VerifyArgs),
+    }
 
-        if !cli.input.is_empty() {
-            let is_single_item = cli.input.len() == 1;
-            let is_hyphen = cli.input.first().unwrap() == "-";
+    #[derive(Args)]
+    #[command(group(
+        ArgGroup::new("test-cases")
+            .args(["positives", "negatives"])
+            .required(true)
+            .multiple(true)
+    ))]
+    pub(crate) struct VerifyArgs {
+        /// The regular expression to verify in the syntax of the regex crate
+        #[arg(long, value_name = "REGEX", allow_hyphen_values = true)]
+        regex: String,
 
-            if is_single_item && is_hyphen && is_stdin_available {
-                Ok(stdin()
-                    .lock()
-                    .lines()
-                    .map(|line| line.unwrap())
-                    .collect_vec())
-            } else {
-                Ok(cli.input.clone())
-            }
-        } else if let Some(file_path) = &cli.file_path {
-            let is_hyphen = file_path.as_os_str() == "-";
-            let path = if is_hyphen && is_stdin_available {
-                let mut stdin_file_path = String::new();
-                stdin().read_to_string(&mut stdin_file_path)?;
-                PathBuf::from(stdin_file_path.trim())
-            } else {
-                file_path.to_path_buf()
+        /// Reads test cases on separate lines from a file which must be matched
+        #[arg(long, value_name = "FILE")]
+        positives: Option<PathBuf>,
+
+        /// Reads test cases on separate lines from a file which must not be matched
+        #[arg(long, value_name = "FILE")]
+        negatives: Option<PathBuf>,
+
+        /// Prints help information
+        #[arg(name = "help", short = 'h', long, action = ArgAction::Help)]
+        help: Option<String>,
+    }
+
+    #[derive(Clone, Copy, PartialEq, ValueEnum)]
+    pub(crate) enum OutputFormat {
+        Text,
+        Json,
+    }
+
+    #[derive(Clone, Copy, PartialEq, ValueEnum)]
+    pub(crate) enum InputFormat {
+        Lines,
+        Nul,
+        Csv,
+        Tsv,
+        Json,
+        Jsonl,
+    }
+
+    type Records<'a> = Box<dyn Iterator<Item = Result<String, Error>> + 'a>;
+
+    /// The test cases given on the command line, or a reader of lines or records
+    /// which is consumed while building the regular expression.
+    pub(crate) enum Input<'a> {
+        TestCases(Vec<String>),
+        Lines(Box<dyn BufRead>),
+        Records(Records<'a>),
+    }
+
+    impl<'a> Input<'a> {
+        fn into_builder(self) -> Result<RegExpBuilder, String> {
+            let builder = match self {
+                Input::TestCases(test_cases) => RegExpBuilder::try_from(&test_cases),
+                Input::Lines(reader) => RegExpBuilder::try_from_reader(reader),
+                Input::Records(records) => {
+                    let mut read_error = None;
+                    let builder =
+                        RegExpBuilder::try_from_iter(records.map_while(|record| {
+                            record.map_err(|error| read_error = Some(error)).ok()
+                        }));
+                    if let Some(error) = read_error {
+                        return Err(describe_input_error(error));
+                    }
+                    builder
+                }
             };
-            match std::fs::read_to_string(path) {
-                Ok(file_content) => Ok(file_content.lines().map(|it| it.to_string()).collect_vec()),
-                Err(error) => Err(error),
+            builder.map_err(|error| format!("error: {}", error))
+        }
+
+        fn into_test_cases(self) -> Result<Vec<String>, Error> {
+            self.into_records().collect()
+        }
+
+        fn into_records(self) -> Records<'a> {
+            match self {
+                Input::TestCases(test_cases) => Box::new(test_cases.into_iter().map(Ok)),
+                Input::Lines(reader) => Box::new(reader.lines()),
+                Input::Records(records) => records,
             }
+        }
+    }
+
+    pub(crate) fn obtain_input(cli: &Cli) -> Result<Input<'_>, Error> {
+        check_input_format_options(cli)?;
+
+        let is_stdin_available = !stdin().is_terminal();
+        let is_hyphen = cli.input.len() == 1 && cli.input[0] == "-";
+
+        let input = if is_hyphen && is_stdin_available {
+            Some(read_input(Box::new(stdin().lock()), cli)?)
+        } else if !cli.input.is_empty() {
+            Some(Input::TestCases(cli.input.clone()))
         } else {
-            Err(Error::new(
+            None
+        };
+
+        let file_paths = resolve_file_paths(&cli.file_paths, is_stdin_available)?;
+
+        match (input, file_paths.len()) {
+            (Some(input), 0) => Ok(input),
+            (None, 1) => read_file(&file_paths[0], cli),
+            (None, 0) if cli.is_interactive || !cli.file_paths.is_empty() => {
+                Ok(Input::TestCases(vec![]))
+            }
+            (None, 0) => Err(Error::new(
                 ErrorKind::InvalidInput,
                 "error: no valid input could be found whatsoever",
-            ))
+            )),
+            (input, _) => {
+                // Files are opened one after another while their test cases are consumed,
+                // so that any number of files can be read without exhausting file handles.
+                let files = file_paths.into_iter().flat_map(move |file_path| {
+                    read_file(&file_path, cli).map_or_else(
+                        |error| Box::new(std::iter::once(Err(error))) as Records,
+                        Input::into_records,
+                    )
+                });
+                let records = input.map(Input::into_records).into_iter().flatten();
+                Ok(Input::Records(Box::new(records.chain(files))))
+            }
         }
     }
 
-    pub(crate) fn handle_input(
-        cli: &Cli,
-        input: Result<Vec<String>, Error>,
-    ) -> Result<(), Box<dyn std::error::Error>> {
-        match input {
-            Ok(test_cases) => {
-                let mut builder = RegExpBuilder::from(&test_cases);
+    /// Expands the paths given with --file to the files they denote.
+    /// Directories are read recursively, glob patterns are expanded
+    /// if no file or directory of that name exists.
+    fn resolve_file_paths(
+        file_paths: &[PathBuf],
+        is_stdin_available: bool,
+    ) -> Result<Vec<PathBuf>, Error> {
+        let mut resolved_file_paths = vec![];
 
-                if cli.is_digit_converted {
-                    builder.with_conversion_of_digits();
+        for file_path in file_paths {
+            if file_path.as_os_str() == "-" && is_stdin_available {
+                for line in stdin().lock().lines() {
+                    let line = line?;
+                    if !line.trim().is_empty() {
+                        resolve_file_path(Path::new(line.trim()), &mut resolved_file_paths)?;
+                    }
                 }
+            } else {
+                resolve_file_path(file_path, &mut resolved_file_paths)?;
+            }
+        }
 
-                if cli.is_non_digit_converted {
-                    builder.with_conversion_of_non_digits();
-                }
+        Ok(resolved_file_paths)
+    }
 
-                if cli.is_space_converted {
-                    builder.with_conversion_of_whitespace();
-                }
+    fn resolve_file_path(
+        file_path: &Path,
+        resolved_file_paths: &mut Vec<PathBuf>,
+    ) -> Result<(), Error> {
+        if file_path.is_dir() {
+            return collect_directory_files(file_path, resolved_file_paths);
+        }
 
-                if cli.is_non_space_converted {
-                    builder.with_conversion_of_non_whitespace();
-                }
+        let pattern = file_path.to_string_lossy();
+        let is_glob = !file_path.exists() && pattern.contains(['*', '?', '[']);
 
-                if cli.is_word_converted {
-                    builder.with_conversion_of_words();
-                }
+        if !is_glob {
+            resolved_file_paths.push(file_path.to_path_buf());
+            return Ok(());
+        }
 
-                if cli.is_non_word_converted {
-                    builder.with_conversion_of_non_words();
-                }
+        let matching_paths = glob::glob(&pattern)
+            .map_err(|error| {
+                Error::other(format!("invalid glob pattern '{}': {}", pattern, error))
+            })?
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(glob::GlobError::into_error)?;
+
+        if matching_paths.is_empty() {
+            return Err(Error::other(format!(
+                "no files match the pattern '{}'",
+                pattern
+            )));
+        }
+
+        for path in matching_paths {
+            if path.is_dir() {
+                collect_directory_files(&path, resolved_file_paths)?;
+            } else {
+                resolved_file_paths.push(path);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn collect_directory_files(
+        directory: &Path,
+        file_paths: &mut Vec<PathBuf>,
+    ) -> Result<(), Error> {
+        let mut entries = std::fs::read_dir(directory)?.collect::<Result<Vec<_>, _>>()?;
+        entries.sort_by_key(|entry| entry.path());
 
-                if cli.is_repetition_converted {
-                    builder.with_conversion_of_repetitions();
+        for entry in entries {
+            if entry.file_type()?.is_dir() {
+                collect_directory_files(&entry.path(), file_paths)?;
+            } else if entry.path().is_file() {
+                file_paths.push(entry.path());
+            }
+        }
+
+        Ok(())
+    }
+
+    fn read_file<'a>(file_path: &Path, cli: &'a Cli) -> Result<Input<'a>, Error> {
+        if cli.are_whole_files_read {
+            let mut file_content = std::fs::read_to_string(file_path)?;
+            if file_content.ends_with('\n') {
+                file_content.pop();
+                if file_content.ends_with('\r') {
+                    file_content.pop();
                 }
+            }
+            return Ok(Input::TestCases(vec![file_content]));
+        }
+
+        let file = File::open(file_path)?;
+        read_input(Box::new(BufReader::new(file)), cli)
+    }
+
+    pub(crate) fn obtain_negative_input(cli: &Cli) -> Result<Vec<String>, Error> {
+        let mut negative_test_cases = cli.negative_input.clone();
+
+        if let Some(file_path) = &cli.negative_file_path {
+            let file = File::open(file_path)?;
+            for record in read_records(Box::new(BufReader::new(file)), cli)? {
+                negative_test_cases.push(record?);
+            }
+        }
+
+        Ok(negative_test_cases)
+    }
+
+    fn check_input_format_options(cli: &Cli) -> Result<(), Error> {
+        let is_delimited = matches!(cli.input_format, InputFormat::Csv | InputFormat::Tsv);
+        let is_json = matches!(cli.input_format, InputFormat::Json | InputFormat::Jsonl);
+
+        if cli.column.is_some() && !is_delimited {
+            return Err(Error::other(
+                "--column can only be used with input format csv or tsv",
+            ));
+        }
+        if cli.has_header && !is_delimited {
+            return Err(Error::other(
+                "--header can only be used with input format csv or tsv",
+            ));
+        }
+        if cli.json_path.is_some() && !is_json {
+            return Err(Error::other(
+                "--json-path can only be used with input format json or jsonl",
+            ));
+        }
+        Ok(())
+    }
+
+    fn read_input<'a>(reader: Box<dyn BufRead>, cli: &'a Cli) -> Result<Input<'a>, Error> {
+        match cli.input_format {
+            InputFormat::Lines => Ok(Input::Lines(reader)),
+            _ => read_records(reader, cli).map(Input::Records),
+        }
+    }
+
+    fn read_records<'a>(reader: Box<dyn BufRead>, cli: &'a Cli) -> Result<Records<'a>, Error> {
+        let json_path = cli.json_path.clone();
+
+        match cli.input_format {
+            InputFormat::Lines => Ok(Box::new(reader.lines())),
+            InputFormat::Nul => Ok(Box::new(reader.split(b'\0').map(|record| {
+                String::from_utf8(record?).map_err(|_| Error::from(ErrorKind::InvalidData))
+            }))),
+            InputFormat::Csv => read_column(reader, ',', cli.column.as_deref(), cli.has_header),
+            InputFormat::Tsv => read_column(reader, '\t', cli.column.as_deref(), cli.has_header),
+            InputFormat::Json => {
+                let document: serde_json::Value =
+                    serde_json::from_reader(reader).map_err(|error| {
+                        Error::other(format!("the input is not valid JSON: {}", error))
+                    })?;
+                let serde_json::Value::Array(elements) = document else {
+                    return Err(Error::other("the JSON input is not an array"));
+                };
+                Ok(Box::new(elements.into_iter().enumerate().map(
+                    move |(idx, element)| {
+                        select_json_value(&element, json_path.as_deref()).ok_or_else(|| {
+                            describe_missing_json_value(
+                                &format!("element {}", idx + 1),
+                                json_path.as_deref(),
+                            )
+                        })
+                    },
+                )))
+            }
+            InputFormat::Jsonl => Ok(Box::new(
+                reader
+                    .lines()
+                    .enumerate()
+                    .filter(|(_, line)| !matches!(line, Ok(line) if line.trim().is_empty()))
+                    .map(move |(idx, line)| {
+                        let location = format!("line {}", idx + 1);
+                        let value: serde_json::Value =
+                            serde_json::from_str(&line?).map_err(|error| {
+                                Error::other(format!("{} is not valid JSON: {}", location, error))
+                            })?;
+                        select_json_value(&value, json_path.as_deref()).ok_or_else(|| {
+                            describe_missing_json_value(&location, json_path.as_deref())
+                        })
+                    }),
+            )),
+        }
+    }
+
+    /// Reads the given column of comma- or tab-separated values.
+    /// The first row is skipped as header if requested or if the column is given by name,
+    /// which is looked up in this row then.
+    fn read_column(
+        reader: Box<dyn BufRead>,
+        delimiter: char,
+        column: Option<&str>,
+        has_header: bool,
+    ) -> Result<Records<'static>, Error> {
+        let mut rows = DelimitedRows {
+            reader,
+            delimiter,
+            line_number: 0,
+        };
+
+        let column = column.map(|it| (it, it.parse::<usize>()));
+        let header = if has_header || matches!(column, Some((_, Err(_)))) {
+            rows.next().transpose()?.map(|(_, fields)| fields)
+        } else {
+            None
+        };
+
+        let column_idx = match column {
+            None => 0,
+            Some((_, Ok(0))) => return Err(Error::other("column indices start at 1")),
+            Some((_, Ok(column_number))) => column_number - 1,
+            Some((column_name, Err(_))) => header
+                .unwrap_or_default()
+                .iter()
+                .position(|field| field == column_name)
+                .ok_or_else(|| {
+                    Error::other(format!(
+                        "column '{}' could not be found in the header",
+                        column_name
+                    ))
+                })?,
+        };
+
+        Ok(Box::new(rows.map(move |row| {
+            let (line_number, fields) = row?;
+            fields.into_iter().nth(column_idx).ok_or_else(|| {
+                Error::other(format!(
+                    "row in line {} has no column {}",
+                    line_number,
+                    column_idx + 1
+                ))
+            })
+        })))
+    }
+
+    /// Splits comma- or tab-separated values into rows of fields, skipping empty lines.
+    /// Fields may be enclosed in double quotes, which allows them to contain delimiters,
+    /// line breaks and double quotes, the latter written as two double quotes `""`.
+    struct DelimitedRows {
+        reader: Box<dyn BufRead>,
+        delimiter: char,
+        line_number: usize,
+    }
 
-                if cli.is_case_ignored {
-                    builder.with_case_insensitive_matching();
+    impl Iterator for DelimitedRows {
+        type Item = Result<(usize, Vec<String>), Error>;
+
+        fn next(&mut self) -> Option<Self::Item> {
+            let mut line = String::new();
+
+            loop {
+                match self.reader.read_line(&mut line) {
+                    Ok(0) => return None,
+                    Ok(_) => self.line_number += 1,
+                    Err(error) => return Some(Err(error)),
+                }
+                if !line.trim_end_matches(['\r', '\n']).is_empty() {
+                    break;
                 }
+                line.clear();
+            }
+
+            let first_line_number = self.line_number;
+            let mut fields = vec![];
+            let mut field = String::new();
+            let mut is_quoted = false;
+
+            loop {
+                let mut chars = line.chars().peekable();
 
-                if cli.is_group_captured {
-                    builder.with_capturing_groups();
+                while let Some(c) = chars.next() {
+                    if is_quoted {
+                        if c != '"' {
+                            field.push(c);
+                        } else if chars.peek() == Some(&'"') {
+                            chars.next();
+                            field.push(c);
+                        } else {
+                            is_quoted = false;
+                        }
+                    } else if c == '"' && field.is_empty() {
+                        is_quoted = true;
+                    } else if c == self.delimiter {
+                        fields.push(std::mem::take(&mut field));
+                    } else if c == '\n' || (c == '\r' && chars.peek() == Some(&'\n')) {
+                        continue;
+                    } else {
+                        field.push(c);
+                    }
                 }
 
-                if cli.is_non_ascii_char_escaped {
-                    builder.with_escaping_of_non_ascii_chars(
-                        cli.is_astral_code_point_converted_to_surrogate,
-                    );
+                if !is_quoted {
+                    break;
                 }
 
-                if cli.is_verbose_mode_enabled {
-                    builder.with_verbose_mode();
+                line.clear();
+                match self.reader.read_line(&mut line) {
+                    Ok(0) => {
+                        return Some(Err(Error::other(format!(
+                            "quoted field in line {} is not closed",
+                            first_line_number
+                        ))))
+                    }
+                    Ok(_) => self.line_number += 1,
+                    Err(error) => return Some(Err(error)),
                 }
+            }
+
+            fields.push(field);
+            Some(Ok((first_line_number, fields)))
+        }
+    }
+
+    /// Follows the given path of object keys and array indices and returns the value
+    /// found there if it is a string or a number.
+    fn select_json_value(value: &serde_json::Value, json_path: Option<&str>) -> Option<String> {
+        let mut value = value;
+
+        for key in json_path.into_iter().flat_map(|path| path.split('.')) {
+            value = match value {
+                serde_json::Value::Object(object) => object.get(key)?,
+                serde_json::Value::Array(array) => array.get(key.parse::<usize>().ok()?)?,
+                _ => return None,
+            };
+        }
+
+        match value {
+            serde_json::Value::String(string) => Some(string.clone()),
+            serde_json::Value::Number(number) => Some(number.to_string()),
+            _ => None,
+        }
+    }
+
+    fn describe_missing_json_value(location: &str, json_path: Option<&str>) -> Error {
+        match json_path {
+            Some(json_path) => Error::other(format!(
+                "{} has no string or number at JSON path '{}'",
+                location, json_path
+            )),
+            None => Error::other(format!("{} is no string or number", location)),
+        }
+    }
+
+    pub(crate) fn handle_input(
+        cli: &Cli,
+        input: Result<Input<'_>, Error>,
+        negative_input: Result<Vec<String>, Error>,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        match input.and_then(|input| Ok((input, negative_input?))) {
+            Ok((input, negative_test_cases)) => {
+                let mut builder = input.into_builder()?;
 
-                if cli.is_caret_anchor_disabled {
-                    builder.without_start_anchor();
+                configure_builder(cli, &mut builder, &negative_test_cases)?;
+
+                if cli.is_output_colorized {
+                    builder.with_syntax_highlighting();
                 }
 
-                if cli.is_dollar_sign_anchor_disabled {
-                    builder.without_end_anchor();
+                builder.with_dialect(cli.dialect);
+
+                match cli.output_format {
+                    OutputFormat::Text => {
+                        let report = builder
+                            .try_build_report()
+                            .map_err(|error| format!("error: {}", error))?;
+                        println!("{}", report.regexp());
+
+                        if !report.generalizations().is_empty() {
+                            eprintln!(
+                                "note: generalized to stay within {} characters: {}",
+                                cli.maximum_length.unwrap(),
+                                report.generalizations().iter().join(", ")
+                            );
+                        }
+
+                        if cli.is_explanation_printed {
+                            println!("\n{}", report.explanation());
+                        }
+                    }
+                    OutputFormat::Json => {
+                        let report = builder
+                            .try_build_report()
+                            .map_err(|error| format!("error: {}", error))?;
+                        println!("{}", report.to_json());
+                    }
                 }
+                Ok(())
+            }
+            Err(error) => Err(describe_input_error(error).into()),
+        }
+    }
+
+    pub(crate) fn handle_interactive_session(
+        mut cli: Cli,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let test_cases = obtain_input(&cli)
+            .and_then(Input::into_test_cases)
+            .map_err(describe_input_error)?;
+        let negative_test_cases = obtain_negative_input(&cli).map_err(describe_input_error)?;
+        let mut session = Session::new(test_cases, negative_test_cases);
+        let mut editor = if stdin().is_terminal() {
+            println!("Type test cases to add them, `:help` for all commands or `:quit` to exit.");
+            Some(DefaultEditor::new()?)
+        } else {
+            None
+        };
+
+        if !session.test_cases.is_empty() {
+            session.print_state(&cli);
+        }
 
-                if cli.are_anchors_disabled {
-                    builder.without_anchors();
+        let mut lines = stdin().lines();
+
+        loop {
+            let line = match &mut editor {
+                Some(editor) => match editor.readline("grex> ") {
+                    Ok(line) => {
+                        editor.add_history_entry(&line)?;
+                        line
+                    }
+                    Err(ReadlineError::Eof | ReadlineError::Interrupted) => break,
+                    Err(error) => return Err(error.into()),
+                },
+                None => match lines.next() {
+                    Some(line) => line?,
+                    None => break,
+                },
+            };
+
+            match parse_session_command(&line) {
+                Ok(SessionCommand::Add(test_case)) => session.add(test_case),
+                Ok(SessionCommand::Exclude(test_case)) => session.exclude(test_case),
+                Ok(SessionCommand::Remove(test_case)) => {
+                    if !session.remove(test_case) {
+                        eprintln!("error: test case '{}' could not be found", test_case);
+                        continue;
+                    }
+                }
+                Ok(SessionCommand::Clear) => {
+                    session.clear();
+                    continue;
+                }
+                Ok(SessionCommand::SetOption(name, is_enabled)) => {
+                    if let Err(error) = set_session_option(&mut cli, name, is_enabled) {
+                        eprintln!("{}", error);
+                        continue;
+                    }
+                    session.invalidate();
+                }
+                Ok(SessionCommand::List) => {}
+                Ok(SessionCommand::Help) => {
+                    println!("{}", SESSION_HELP);
+                    continue;
                 }
+                Ok(SessionCommand::Quit) => break,
+                Ok(SessionCommand::Skip) => continue,
+                Err(error) => {
+                    eprintln!("{}", error);
+                    continue;
+                }
+            }
+
+            session.print_state(&cli);
+        }
+
+        Ok(())
+    }
+
+    pub(crate) fn handle_verification(args: &VerifyArgs) -> Result<(), Box<dyn std::error::Error>> {
+        let regex = Regex::new(&args.regex).map_err(|error| format!("error: {}", error))?;
+        let test_cases = read_lines(args.positives.as_ref())?;
+        let negative_test_cases = read_lines(args.negatives.as_ref())?;
+        let mismatches = verify(&regex, &test_cases, &negative_test_cases);
+        let test_case_count = test_cases.len() + negative_test_cases.len();
+
+        for mismatch in mismatches.iter() {
+            let (file_path, description) = if mismatch.is_negative {
+                (&args.negatives, "negative test case is matched")
+            } else {
+                (&args.positives, "test case is not matched")
+            };
+            println!(
+                "{}:{}: {}: {}",
+                file_path.as_ref().unwrap().display(),
+                mismatch.line_number,
+                description,
+                mismatch.test_case
+            );
+        }
+
+        if mismatches.is_empty() {
+            println!("all {} test cases passed", test_case_count);
+            Ok(())
+        } else {
+            Err(format!(
+                "error: {} of {} test cases failed",
+                mismatches.len(),
+                test_case_count
+            )
+            .into())
+        }
+    }
+
+    /// A test case which is not handled correctly by a regular expression.
+    struct Mismatch {
+        /// The position of the test case in its file or session, counted from 1.
+        line_number: usize,
+        test_case: String,
+        is_negative: bool,
+    }
+
+    /// Returns each test case which the regular expression does not match
+    /// and each negative test case which it matches.
+    fn verify(
+        regex: &Regex,
+        test_cases: &[String],
+        negative_test_cases: &[String],
+    ) -> Vec<Mismatch> {
+        let mismatches = test_cases
+            .iter()
+            .enumerate()
+            .filter(|(_, test_case)| !regex.is_match(test_case))
+            .map(|(i, test_case)| Mismatch {
+                line_number: i + 1,
+                test_case: test_case.clone(),
+                is_negative: false,
+            });
+
+        let negative_mismatches = negative_test_cases
+            .iter()
+            .enumerate()
+            .filter(|(_, test_case)| regex.is_match(test_case))
+            .map(|(i, test_case)| Mismatch {
+                line_number: i + 1,
+                test_case: test_case.clone(),
+                is_negative: true,
+            });
+
+        mismatches.chain(negative_mismatches).collect()
+    }
+
+    const SESSION_HELP: &str = "\
+<TEST_CASE>           adds a test case which must be matched
+:add <TEST_CASE>      adds a test case which must be matched, even if it starts with a colon
+:exclude <TEST_CASE>  adds a test case which must not be matched
+:remove <TEST_CASE>   removes a test case
+:clear                removes all test cases
+:list                 prints the regular expression and all test cases
+:<OPTION> on|off      switches an option on or off, e.g. `:digits on` or `:anchors off`
+:help                 prints this help
+:quit                 ends the session
+
+Options: digits, non-digits, numeric-ranges, spaces, non-spaces, words, non-words,
+categories, scripts, positional-classes, repetitions, escape, with-surrogates,
+anchors, start-anchor, end-anchor, verbose, colorize, explain, ignore-case,
+case-classes, capture-groups";
+
+    enum SessionCommand<'a> {
+        Add(&'a str),
+        Exclude(&'a str),
+        Remove(&'a str),
+        Clear,
+        SetOption(&'a str, bool),
+        List,
+        Help,
+        Quit,
+        Skip,
+    }
+
+    fn parse_session_command(line: &str) -> Result<SessionCommand<'_>, String> {
+        if line.is_empty() {
+            return Ok(SessionCommand::Skip);
+        }
+
+        let command = match line.strip_prefix(':') {
+            Some(command) => command,
+            None => return Ok(SessionCommand::Add(line)),
+        };
+
+        let (name, argument) = command.split_once(' ').unwrap_or((command, ""));
+
+        match (name, argument) {
+            ("add", test_case) => Ok(SessionCommand::Add(test_case)),
+            ("exclude", test_case) => Ok(SessionCommand::Exclude(test_case)),
+            ("remove", test_case) => Ok(SessionCommand::Remove(test_case)),
+            ("clear", "") => Ok(SessionCommand::Clear),
+            ("list", "") => Ok(SessionCommand::List),
+            ("help", "") => Ok(SessionCommand::Help),
+            ("quit", "") | ("exit", "") => Ok(SessionCommand::Quit),
+            (option, "on") => Ok(SessionCommand::SetOption(option, true)),
+            (option, "off") => Ok(SessionCommand::SetOption(option, false)),
+            _ => Err(format!(
+                "error: unknown command '{}', type `:help` for a list of all commands",
+                line
+            )),
+        }
+    }
+
+    fn set_session_option(cli: &mut Cli, name: &str, is_enabled: bool) -> Result<(), String> {
+        let (flag, is_negated) = match name {
+            "digits" => (&mut cli.is_digit_converted, false),
+            "non-digits" => (&mut cli.is_non_digit_converted, false),
+            "numeric-ranges" => (&mut cli.is_numeric_range_converted, false),
+            "spaces" => (&mut cli.is_space_converted, false),
+            "non-spaces" => (&mut cli.is_non_space_converted, false),
+            "words" => (&mut cli.is_word_converted, false),
+            "non-words" => (&mut cli.is_non_word_converted, false),
+            "categories" => (&mut cli.is_general_category_converted, false),
+            "scripts" => (&mut cli.is_script_converted, false),
+            "positional-classes" => (&mut cli.is_positional_char_class_inferred, false),
+            "repetitions" => (&mut cli.is_repetition_converted, false),
+            "escape" => (&mut cli.is_non_ascii_char_escaped, false),
+            "with-surrogates" => (&mut cli.is_astral_code_point_converted_to_surrogate, false),
+            "anchors" => (&mut cli.are_anchors_disabled, true),
+            "start-anchor" => (&mut cli.is_caret_anchor_disabled, true),
+            "end-anchor" => (&mut cli.is_dollar_sign_anchor_disabled, true),
+            "verbose" => (&mut cli.is_verbose_mode_enabled, false),
+            "colorize" => (&mut cli.is_output_colorized, false),
+            "explain" => (&mut cli.is_explanation_printed, false),
+            "ignore-case" => (&mut cli.is_case_ignored, false),
+            "case-classes" => (&mut cli.is_case_class_enabled, false),
+            "capture-groups" => (&mut cli.is_group_captured, false),
+            _ => return Err(format!("error: unknown option '{}'", name)),
+        };
+        *flag = is_enabled != is_negated;
+        Ok(())
+    }
+
+    /// The test cases of an interactive session together with the regular expressions
+    /// built from them. Adding a test case inserts it into incremental builders, so only
+    /// this test case needs to be segmented and checked against the new regular expression.
+    /// All other changes build the regular expressions from scratch and check all test cases.
+    struct Session {
+        test_cases: Vec<String>,
+        negative_test_cases: Vec<String>,
+        /// The builder for the selected dialect and the builder for the same regular expression
+        /// in the syntax of the regex crate, as the selected dialect may not be supported by it.
+        builders: Option<(IncrementalRegExpBuilder, IncrementalRegExpBuilder)>,
+        /// Whether the test cases checked so far are matched by the current regular expression.
+        matches: HashMap<String, bool>,
+        negative_matches: HashMap<String, bool>,
+    }
+
+    impl Session {
+        fn new(test_cases: Vec<String>, negative_test_cases: Vec<String>) -> Self {
+            Self {
+                test_cases,
+                negative_test_cases,
+                builders: None,
+                matches: HashMap::new(),
+                negative_matches: HashMap::new(),
+            }
+        }
+
+        fn add(&mut self, test_case: &str) {
+            if self.test_cases.iter().any(|it| it == test_case) {
+                return;
+            }
+            self.test_cases.push(test_case.to_string());
+            if let Some((builder, verification_builder)) = &mut self.builders {
+                builder.insert(test_case);
+                verification_builder.insert(test_case);
+            }
+        }
+
+        fn exclude(&mut self, test_case: &str) {
+            if !self.negative_test_cases.iter().any(|it| it == test_case) {
+                self.negative_test_cases.push(test_case.to_string());
+                self.invalidate();
+            }
+        }
+
+        /// Returns `false` if the test case could not be found.
+        fn remove(&mut self, test_case: &str) -> bool {
+            let test_case_count = self.test_cases.len() + self.negative_test_cases.len();
+            self.test_cases.retain(|it| it != test_case);
+            self.negative_test_cases.retain(|it| it != test_case);
+            if self.test_cases.len() + self.negative_test_cases.len() == test_case_count {
+                return false;
+            }
+            self.invalidate();
+            true
+        }
+
+        fn clear(&mut self) {
+            self.test_cases.clear();
+            self.negative_test_cases.clear();
+            self.invalidate();
+        }
+
+        /// Discards the regular expressions so that they are built from scratch again.
+        fn invalidate(&mut self) {
+            self.builders = None;
+            self.matches.clear();
+            self.negative_matches.clear();
+        }
+
+        fn print_state(&mut self, cli: &Cli) {
+            if let Err(error) = self.try_print_state(cli) {
+                eprintln!("{}", error);
+            }
+        }
+
+        /// Prints the regular expression followed by all test cases.
+        fn try_print_state(&mut self, cli: &Cli) -> Result<(), String> {
+            if self.builders.is_none() {
+                let mut builder =
+                    configured_builder(cli, &self.test_cases, &self.negative_test_cases)?;
+                let verification_builder =
+                    builder.clone().with_dialect(Dialect::Rust).to_incremental();
 
                 if cli.is_output_colorized {
                     builder.with_syntax_highlighting();
                 }
 
+                builder.with_dialect(cli.dialect);
+                self.builders = Some((builder.to_incremental(), verification_builder));
+            }
+
+            let (builder, verification_builder) = self.builders.as_ref().unwrap();
+            let regexp = builder
+                .try_build()
+                .map_err(|error| format!("error: {}", error))?;
+            println!("{}", regexp);
+
+            if cli.is_explanation_printed {
+                let mut builder =
+                    configured_builder(cli, &self.test_cases, &self.negative_test_cases)?;
+                let explanation = builder
+                    .with_dialect(cli.dialect)
+                    .try_explain()
+                    .map_err(|error| format!("error: {}", error))?;
+                println!("\n{}", explanation);
+            }
+
+            let regex = verification_builder
+                .try_build()
+                .map_err(|error| format!("error: {}", error))
+                .and_then(|regexp| {
+                    Regex::new(&regexp).map_err(|error| format!("error: {}", error))
+                })?;
+
+            for test_case in self.test_cases.iter() {
+                let is_matched = *self
+                    .matches
+                    .entry(test_case.clone())
+                    .or_insert_with(|| regex.is_match(test_case));
+                let status = if is_matched { "matched" } else { "not matched" };
+                println!("  [+] {}: {}", test_case, status);
+            }
+
+            for test_case in self.negative_test_cases.iter() {
+                let is_matched = *self
+                    .negative_matches
+                    .entry(test_case.clone())
+                    .or_insert_with(|| regex.is_match(test_case));
+                let status = if is_matched { "matched" } else { "not matched" };
+                println!("  [-] {}: {}", test_case, status);
+            }
+
+            Ok(())
+        }
+    }
+
+    fn configured_builder(
+        cli: &Cli,
+        test_cases: &[String],
+        negative_test_cases: &[String],
+    ) -> Result<RegExpBuilder, String> {
+        let mut builder =
+            RegExpBuilder::try_from(test_cases).map_err(|error| format!("error: {}", error))?;
+        configure_builder(cli, &mut builder, negative_test_cases)?;
+        Ok(builder)
+    }
+
+    fn configure_builder(
+        cli: &Cli,
+        builder: &mut RegExpBuilder,
+        negative_test_cases: &[String],
+    ) -> Result<(), String> {
+        if !negative_test_cases.is_empty() {
+            builder.with_negative_examples(negative_test_cases);
+        }
+
+        if cli.is_digit_converted {
+            builder.with_conversion_of_digits();
+        }
+
+        if cli.is_non_digit_converted {
+            builder.with_conversion_of_non_digits();
+        }
+
+        if cli.is_numeric_range_converted {
+            builder.with_conversion_of_numeric_ranges();
+        }
+
+        if cli.is_space_converted {
+            builder.with_conversion_of_whitespace();
+        }
+
+        if cli.is_non_space_converted {
+            builder.with_conversion_of_non_whitespace();
+        }
+
+        if cli.is_word_converted {
+            builder.with_conversion_of_words();
+        }
+
+        if cli.is_non_word_converted {
+            builder.with_conversion_of_non_words();
+        }
+
+        if cli.is_general_category_converted {
+            builder.with_conversion_of_general_categories();
+        }
+
+        if cli.is_script_converted {
+            builder.with_conversion_of_scripts();
+        }
+
+        if cli.is_positional_char_class_inferred {
+            builder.with_positional_char_classes();
+        }
+
+        if cli.is_repetition_converted {
+            builder.with_conversion_of_repetitions();
+        }
+
+        if cli.is_case_ignored {
+            builder.with_case_insensitive_matching();
+        }
+
+        if cli.is_case_class_enabled {
+            builder.with_case_insensitive_char_classes();
+        }
+
+        if cli.is_group_captured {
+            builder.with_capturing_groups();
+        }
+
+        if cli.is_non_ascii_char_escaped {
+            builder
+                .with_escaping_of_non_ascii_chars(cli.is_astral_code_point_converted_to_surrogate);
+        }
+
+        if cli.is_verbose_mode_enabled {
+            builder.with_verbose_mode();
+        }
+
+        if cli.is_caret_anchor_disabled {
+            builder.without_start_anchor();
+        }
+
+        if cli.is_dollar_sign_anchor_disabled {
+            builder.without_end_anchor();
+        }
+
+        if cli.are_anchors_disabled {
+            builder.without_anchors();
+        }
+
+        builder
+            .try_with_minimum_repetitions(cli.minimum_repetitions)
+            .and_then(|builder| {
+                builder.try_with_minimum_substring_length(cli.minimum_substring_length)
+            })
+            .and_then(|builder| {
                 builder
-                    .with_minimum_repetitions(cli.minimum_repetitions)
-                    .with_minimum_substring_length(cli.minimum_substring_length);
+                    .try_with_positional_char_class_tolerance(cli.positional_char_class_tolerance)
+            })
+            .map_err(|error| format!("error: {}", error))?;
 
-                let regexp = builder.build();
+        if let Some(maximum_length) = cli.maximum_length {
+            builder
+                .try_with_max_length(maximum_length as usize)
+                .map_err(|error| format!("error: {}", error))?;
+        }
 
-                println!("{}", regexp);
-                Ok(())
+        Ok(())
+    }
+
+    fn describe_input_error(error: Error) -> String {
+        match error.kind() {
+            ErrorKind::NotFound => "error: the specified file could not be found".to_string(),
+            ErrorKind::InvalidData => {
+                "error: the specified file's encoding is not valid UTF-8".to_string()
             }
-            Err(error) => match error.kind() {
-                ErrorKind::NotFound => Err("error: the specified file could not be found".into()),
-                ErrorKind::InvalidData => {
-                    Err("error: the specified file's encoding is not valid UTF-8".into())
-                }
-                ErrorKind::PermissionDenied => {
-                    Err("permission denied: the specified file could not be opened".into())
-                }
-                _ => Err(format!("error: {}", error).into()),
+            ErrorKind::PermissionDenied => {
+                "permission denied: the specified file could not be opened".to_string()
+            }
+            _ => format!("error: {}", error),
+        }
+    }
+
+    fn read_lines(file_path: Option<&PathBuf>) -> Result<Vec<String>, String> {
+        match file_path {
+            Some(file_path) => match std::fs::read_to_string(file_path) {
+                Ok(file_content) => Ok(file_content.lines().map(|it| it.to_string()).collect_vec()),
+                Err(error) => Err(match error.kind() {
+                    ErrorKind::NotFound => format!(
+                        "error: the specified file could not be found: {}",
+                        file_path.display()
+                    ),
+                    ErrorKind::InvalidData => format!(
+                        "error: the specified file's encoding is not valid UTF-8: {}",
+                        file_path.display()
+                    ),
+                    _ => format!("error: {}", error),
+                }),
             },
+            None => Ok(vec![]),
         }
     }
 
@@ -431,13 +1616,47 @@ mod cli {
             Err(_) => Err(String::from("Value is not a valid unsigned integer")),
         }
     }
+
+    fn positional_tolerance_parser(value: &str) -> Result<u32, String> {
+        match value.parse::<u32>() {
+            Ok(0) => Err(String::from("Tolerance factor must be greater than zero")),
+            Ok(parsed_value) => Ok(parsed_value),
+            Err(_) => Err(String::from(
+                "Tolerance factor is not a valid unsigned integer",
+            )),
+        }
+    }
+
+    fn dialect_parser() -> impl TypedValueParser<Value = Dialect> {
+        PossibleValuesParser::new([
+            "rust",
+            "pcre",
+            "ecmascript",
+            "python",
+            "go",
+            "dotnet",
+            "java",
+            "posix-ere",
+            "posix-bre",
+        ])
+        .map(|value| value.parse::<Dialect>().unwrap())
+    }
 }
 
 #[cfg(not(target_family = "wasm"))]
 fn main() {
     use clap::Parser;
     let cli = cli::Cli::parse();
-    if let Err(e) = cli::handle_input(&cli, cli::obtain_input(&cli)) {
+    let result = match cli.command {
+        Some(cli::Command::Verify(ref args)) => cli::handle_verification(args),
+        None if cli.is_interactive => cli::handle_interactive_session(cli),
+        None => cli::handle_input(
+            &cli,
+            cli::obtain_input(&cli),
+            cli::obtain_negative_input(&cli),
+        ),
+    };
+    if let Err(e) = result {
         eprintln!("{}", e);
         std::process::exit(1);
     }
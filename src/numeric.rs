@@ -0,0 +1,274 @@
+/*
+ * Copyright © 2019-today Peter M. Stahl pemistahl@gmail.com
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either expressed or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::cluster::GraphemeCluster;
+use crate::config::RegExpConfig;
+use crate::expression::Expression;
+use crate::grapheme::Grapheme;
+use itertools::Itertools;
+use std::collections::{BTreeSet, HashMap};
+
+/// Numbers with more digits than this do not fit into `u64` and are kept as they are.
+const MAXIMUM_DIGIT_COUNT: usize = 19;
+
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+enum Segment {
+    Text(String),
+    Number,
+}
+
+/// This struct represents test cases which only differ in their numbers,
+/// such as `port 80` and `port 443`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) struct NumericPattern {
+    segments: Vec<Segment>,
+    ranges: Vec<(u64, u64)>,
+    test_cases: Vec<String>,
+}
+
+impl NumericPattern {
+    /// Groups the test cases by their text around numbers. Only groups of at least
+    /// two test cases are returned as patterns, all other test cases are returned unchanged.
+    pub(crate) fn partition(test_cases: &[String]) -> (Vec<NumericPattern>, Vec<String>) {
+        let mut patterns: Vec<NumericPattern> = vec![];
+        let mut indices = HashMap::new();
+        let mut remaining_test_cases = vec![];
+
+        for test_case in test_cases {
+            let Some((segments, numbers)) = split_numbers(test_case) else {
+                remaining_test_cases.push(test_case.clone());
+                continue;
+            };
+            let idx = *indices.entry(segments.clone()).or_insert_with(|| {
+                patterns.push(NumericPattern {
+                    segments,
+                    ranges: numbers.iter().map(|&number| (number, number)).collect_vec(),
+                    test_cases: vec![],
+                });
+                patterns.len() - 1
+            });
+            let pattern = &mut patterns[idx];
+            for (range, number) in pattern.ranges.iter_mut().zip(numbers) {
+                range.0 = range.0.min(number);
+                range.1 = range.1.max(number);
+            }
+            pattern.test_cases.push(test_case.clone());
+        }
+
+        let (patterns, single_patterns): (Vec<_>, Vec<_>) = patterns
+            .into_iter()
+            .partition(|pattern| pattern.test_cases.len() > 1);
+
+        for pattern in single_patterns {
+            remaining_test_cases.extend(pattern.test_cases);
+        }
+
+        (patterns, remaining_test_cases)
+    }
+
+    pub(crate) fn test_cases(&self) -> &[String] {
+        &self.test_cases
+    }
+
+    pub(crate) fn to_expression<'a>(&self, config: &'a RegExpConfig) -> Expression<'a> {
+        let mut ranges = self.ranges.iter();
+        self.segments
+            .iter()
+            .map(|segment| match segment {
+                Segment::Text(text) => {
+                    Expression::new_literal(GraphemeCluster::from(text, config), config)
+                }
+                Segment::Number => {
+                    let &(min, max) = ranges.next().unwrap();
+                    range_expression(min, max, config)
+                }
+            })
+            .reduce(|expr1, expr2| Expression::new_concatenation(expr1, expr2, config))
+            .unwrap()
+    }
+}
+
+/// Splits a test case into its text and its maximal runs of ASCII digits.
+/// Returns `None` if the test case does not contain any convertible number.
+fn split_numbers(test_case: &str) -> Option<(Vec<Segment>, Vec<u64>)> {
+    let mut segments = vec![];
+    let mut numbers = vec![];
+    let mut text = String::new();
+
+    for (is_digit_run, run) in &test_case.chars().chunk_by(|c| c.is_ascii_digit()) {
+        let run = run.collect::<String>();
+        let has_leading_zero = run.len() > 1 && run.starts_with('0');
+
+        if is_digit_run && !has_leading_zero && run.len() <= MAXIMUM_DIGIT_COUNT {
+            if !text.is_empty() {
+                segments.push(Segment::Text(std::mem::take(&mut text)));
+            }
+            segments.push(Segment::Number);
+            numbers.push(run.parse::<u64>().unwrap());
+        } else {
+            text.push_str(&run);
+        }
+    }
+
+    if !text.is_empty() {
+        segments.push(Segment::Text(text));
+    }
+
+    if numbers.is_empty() {
+        None
+    } else {
+        Some((segments, numbers))
+    }
+}
+
+/// Creates an expression which matches exactly the numbers from `min` to `max`.
+fn range_expression(min: u64, max: u64, config: &RegExpConfig) -> Expression<'_> {
+    let exprs = split_range(min, max)
+        .into_iter()
+        .map(|digit_ranges| digit_ranges_expression(&digit_ranges, config))
+        .collect_vec();
+
+    if exprs.len() == 1 {
+        exprs.into_iter().next().unwrap()
+    } else {
+        Expression::new_alternation(exprs, config)
+    }
+}
+
+/// Splits the range from `min` to `max` into sequences of digit ranges,
+/// e.g. 1980 to 2029 into `19[8-9][0-9]` and `20[0-2][0-9]`.
+fn split_range(min: u64, max: u64) -> Vec<Vec<(u8, u8)>> {
+    let min_digits = min.to_string().into_bytes();
+    let max_digits = max.to_string().into_bytes();
+
+    if min_digits.len() < max_digits.len() {
+        let upper_bound = 10u64.pow(min_digits.len() as u32);
+        let mut ranges = split_range(min, upper_bound - 1);
+        ranges.extend(split_range(upper_bound, max));
+        ranges
+    } else {
+        split_digits(&min_digits, &max_digits)
+    }
+}
+
+fn split_digits(min_digits: &[u8], max_digits: &[u8]) -> Vec<Vec<(u8, u8)>> {
+    let (first_min, rest_min) = min_digits.split_first().unwrap();
+    let (first_max, rest_max) = max_digits.split_first().unwrap();
+
+    if rest_min.is_empty() {
+        return vec![vec![(*first_min, *first_max)]];
+    }
+
+    if first_min == first_max {
+        return split_digits(rest_min, rest_max)
+            .into_iter()
+            .map(|ranges| prepend((*first_min, *first_max), ranges))
+            .collect_vec();
+    }
+
+    let is_lower_bound_complete = rest_min.iter().all(|&it| it == b'0');
+    let is_upper_bound_complete = rest_max.iter().all(|&it| it == b'9');
+    let mut ranges = vec![];
+    let mut start = *first_min;
+    let mut end = *first_max;
+
+    if !is_lower_bound_complete {
+        let nines = vec![b'9'; rest_min.len()];
+        ranges.extend(
+            split_digits(rest_min, &nines)
+                .into_iter()
+                .map(|it| prepend((*first_min, *first_min), it)),
+        );
+        start += 1;
+    }
+
+    if !is_upper_bound_complete {
+        end -= 1;
+    }
+
+    if start <= end {
+        let mut middle_ranges = vec![(start, end)];
+        middle_ranges.extend(vec![(b'0', b'9'); rest_min.len()]);
+        ranges.push(middle_ranges);
+    }
+
+    if !is_upper_bound_complete {
+        let zeros = vec![b'0'; rest_max.len()];
+        ranges.extend(
+            split_digits(&zeros, rest_max)
+                .into_iter()
+                .map(|it| prepend((*first_max, *first_max), it)),
+        );
+    }
+
+    ranges
+}
+
+fn prepend(digit_range: (u8, u8), digit_ranges: Vec<(u8, u8)>) -> Vec<(u8, u8)> {
+    let mut ranges = vec![digit_range];
+    ranges.extend(digit_ranges);
+    ranges
+}
+
+fn digit_ranges_expression<'a>(
+    digit_ranges: &[(u8, u8)],
+    config: &'a RegExpConfig,
+) -> Expression<'a> {
+    let mut exprs = vec![];
+    let mut graphemes = vec![];
+
+    for &(first, last) in digit_ranges {
+        let is_digit_class =
+            (first, last) == (b'0', b'9') && config.dialect.is_digit_class_ascii_only();
+        if first == last || is_digit_class {
+            let value = if first == last {
+                (first as char).to_string()
+            } else {
+                "\\d".to_string()
+            };
+            graphemes.push(Grapheme::from(
+                &value,
+                config.is_capturing_group_enabled,
+                config.is_output_colorized,
+                config.is_verbose_mode_enabled,
+            ));
+        } else {
+            if !graphemes.is_empty() {
+                exprs.push(Expression::new_literal(
+                    GraphemeCluster::from_graphemes(std::mem::take(&mut graphemes), config),
+                    config,
+                ));
+            }
+            let char_set = (first..=last).map(char::from).collect::<BTreeSet<_>>();
+            exprs.push(Expression::CharacterClass(
+                char_set,
+                config.is_output_colorized,
+            ));
+        }
+    }
+
+    if !graphemes.is_empty() {
+        exprs.push(Expression::new_literal(
+            GraphemeCluster::from_graphemes(graphemes, config),
+            config,
+        ));
+    }
+
+    exprs
+        .into_iter()
+        .reduce(|expr1, expr2| Expression::new_concatenation(expr1, expr2, config))
+        .unwrap()
+}
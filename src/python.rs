@@ -15,48 +15,139 @@
  */
 
 use crate::builder::{
-    RegExpBuilder, MINIMUM_REPETITIONS_MESSAGE, MINIMUM_SUBSTRING_LENGTH_MESSAGE,
-    MISSING_TEST_CASES_MESSAGE,
+    RegExpBuilder, MAXIMUM_LENGTH_MESSAGE, MINIMUM_REPETITIONS_MESSAGE,
+    MINIMUM_SUBSTRING_LENGTH_MESSAGE, POSITIONAL_CHAR_CLASS_TOLERANCE_MESSAGE,
 };
-use crate::config::RegExpConfig;
-use lazy_static::lazy_static;
-use pyo3::exceptions::PyValueError;
+use crate::dialect::Dialect;
+use crate::error::GrexError;
+use crate::report::Report;
+use itertools::Itertools;
+use pyo3::exceptions::{PyTypeError, PyValueError};
 use pyo3::prelude::*;
-use pyo3::types::PyType;
-use regex::{Captures, Regex};
+use pyo3::types::{PyByteArray, PyBytes, PyString, PyType};
+use std::num::NonZeroUsize;
+use std::thread;
 
 #[pymodule]
-fn grex(_py: Python<'_>, m: &PyModule) -> PyResult<()> {
+fn grex(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_class::<RegExpBuilder>()?;
+    m.add_class::<RegExpMetadata>()?;
     Ok(())
 }
 
+/// This class contains a regular expression for Python's `re` module
+/// together with information about how it has been generated.
+///
+/// It is an immutable extension class rather than a dataclass. Its attributes are
+/// read-only and two instances are equal if all their attributes are equal,
+/// but functions of module `dataclasses` such as `asdict` cannot be applied to it.
+///
+/// Attributes:
+///     pattern (str): The regular expression without inline flags
+///     flags (int): The flags of module `re` to compile the pattern with
+///     test_case_count (int): The number of unique test cases
+///     dfa_state_count (int): The number of states of the DFA before minimization
+///     minimized_dfa_state_count (int): The number of states of the DFA after minimization
+///     is_fallback_taken (bool): Whether the pattern is a plain alternation of the test cases
+///     generalizations (list[str]): The generalizations applied to stay within the maximum length
+#[pyclass(frozen, eq, get_all, module = "grex")]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RegExpMetadata {
+    pattern: String,
+    flags: i32,
+    test_case_count: usize,
+    dfa_state_count: usize,
+    minimized_dfa_state_count: usize,
+    is_fallback_taken: bool,
+    generalizations: Vec<String>,
+}
+
+#[pymethods]
+impl RegExpMetadata {
+    fn __repr__(&self, py: Python<'_>) -> PyResult<String> {
+        Ok(format!(
+            "RegExpMetadata(pattern={}, flags={}, test_case_count={}, dfa_state_count={}, \
+            minimized_dfa_state_count={}, is_fallback_taken={}, generalizations={})",
+            self.pattern.to_object(py).bind(py).repr()?,
+            self.flags,
+            self.test_case_count,
+            self.dfa_state_count,
+            self.minimized_dfa_state_count,
+            if self.is_fallback_taken {
+                "True"
+            } else {
+                "False"
+            },
+            self.generalizations.to_object(py).bind(py).repr()?
+        ))
+    }
+}
+
 #[pymethods]
 impl RegExpBuilder {
     #[new]
-    fn new(test_cases: Vec<String>) -> PyResult<Self> {
-        if test_cases.is_empty() {
-            Err(PyValueError::new_err(MISSING_TEST_CASES_MESSAGE))
-        } else {
-            Ok(Self {
-                test_cases,
-                config: RegExpConfig::new(),
-            })
+    #[pyo3(signature = (test_cases, encoding = "utf-8"))]
+    fn new(test_cases: &Bound<'_, PyAny>, encoding: &str) -> PyResult<Self> {
+        let mut error = None;
+        let builder = Self::try_from_iter(
+            extract_test_cases(test_cases, encoding)?
+                .map_while(|test_case| test_case.map_err(|err| error = Some(err)).ok()),
+        );
+        match error {
+            Some(error) => Err(error),
+            None => builder.map_err(to_value_error),
         }
     }
 
     /// Specify the test cases to build the regular expression from.
     ///
     /// The test cases need not be sorted because `RegExpBuilder` sorts them internally.
+    /// They are taken one by one from any iterable, such as a list, a generator or
+    /// a pandas series, without collecting them into a list first. Duplicates are dropped.
+    ///
+    /// Test cases of type `bytes` or `bytearray` are decoded with the given encoding.
+    /// If a file object is given, each of its lines is a test case, without the line ending.
     ///
     /// Args:
-    ///     test_cases (list[str]): The list of test cases
+    ///     test_cases (Iterable[str | bytes | bytearray]): The test cases or a file object
+    ///     encoding (str): Either 'utf-8' or 'latin-1', defaults to 'utf-8'
     ///
     /// Raises:
-    ///     ValueError: if `test_cases` is empty
+    ///     ValueError: if `test_cases` is empty, if `encoding` is not supported
+    ///         or if a test case cannot be decoded
+    ///     TypeError: if `test_cases` is not iterable or contains items of other types
     #[classmethod]
-    fn from_test_cases(_cls: &PyType, test_cases: Vec<String>) -> PyResult<Self> {
-        Self::new(test_cases)
+    #[pyo3(signature = (test_cases, encoding = "utf-8"))]
+    fn from_test_cases(
+        _cls: &Bound<'_, PyType>,
+        test_cases: &Bound<'_, PyAny>,
+        encoding: &str,
+    ) -> PyResult<Self> {
+        Self::new(test_cases, encoding)
+    }
+
+    /// Specify negative test cases which must not be matched by the regular expression.
+    ///
+    /// Conversions which generalize the test cases, such as the conversion to character classes,
+    /// are backed off wherever they would lead to a match of one of the negative test cases.
+    /// The negative test cases are accepted in the same forms as in `from_test_cases`.
+    ///
+    /// Args:
+    ///     negative_test_cases (Iterable[str | bytes | bytearray]): The negative test cases or a file object
+    ///     encoding (str): Either 'utf-8' or 'latin-1', defaults to 'utf-8'
+    ///
+    /// Raises:
+    ///     ValueError: if `encoding` is not supported or if a negative test case cannot be decoded
+    ///     TypeError: if `negative_test_cases` is not iterable or contains items of other types
+    #[pyo3(name = "with_negative_examples", signature = (negative_test_cases, encoding = "utf-8"))]
+    fn py_with_negative_examples<'py>(
+        mut self_: PyRefMut<'py, Self>,
+        negative_test_cases: &Bound<'py, PyAny>,
+        encoding: &str,
+    ) -> PyResult<PyRefMut<'py, Self>> {
+        self_.negative_test_cases =
+            extract_test_cases(negative_test_cases, encoding)?.collect::<PyResult<Vec<_>>>()?;
+        Ok(self_)
     }
 
     /// Convert any Unicode decimal digit to character class `\d`.
@@ -128,6 +219,38 @@ impl RegExpBuilder {
         self_
     }
 
+    /// Convert any character to the character class of its Unicode general category,
+    /// e.g. `A` to `\p{Lu}` and `!` to `\p{Po}`. Control, format, private use
+    /// and unassigned characters are not converted.
+    ///
+    /// This method takes precedence over `with_conversion_of_scripts` and all remaining
+    /// character class conversions except for `with_conversion_of_digits` and
+    /// `with_conversion_of_whitespace` if both are set.
+    ///
+    /// Module `re` does not support general categories, so `compile` and `build_with_metadata`
+    /// raise an error. The expression returned by `build` can be used with module `regex`.
+    #[pyo3(name = "with_conversion_of_general_categories")]
+    fn py_with_conversion_of_general_categories(mut self_: PyRefMut<Self>) -> PyRefMut<Self> {
+        self_.config.is_general_category_converted = true;
+        self_
+    }
+
+    /// Convert any character to the character class of its Unicode script,
+    /// e.g. `α` to `\p{Greek}` and `漢` to `\p{Han}`. Characters shared by several scripts,
+    /// such as ASCII digits and punctuation, are not converted.
+    ///
+    /// This method takes precedence over `with_conversion_of_words` and all remaining
+    /// character class conversions except for `with_conversion_of_digits`,
+    /// `with_conversion_of_whitespace` and `with_conversion_of_general_categories` if both are set.
+    ///
+    /// Module `re` does not support scripts, so `compile` and `build_with_metadata`
+    /// raise an error. The expression returned by `build` can be used with module `regex`.
+    #[pyo3(name = "with_conversion_of_scripts")]
+    fn py_with_conversion_of_scripts(mut self_: PyRefMut<Self>) -> PyRefMut<Self> {
+        self_.config.is_script_converted = true;
+        self_
+    }
+
     /// Detect repeated non-overlapping substrings and convert them to `{min,max}` quantifier notation.
     #[pyo3(name = "with_conversion_of_repetitions")]
     fn py_with_conversion_of_repetitions(mut self_: PyRefMut<Self>) -> PyRefMut<Self> {
@@ -135,6 +258,47 @@ impl RegExpBuilder {
         self_
     }
 
+    /// Convert the numbers among the test cases to an expression matching exactly
+    /// the range between the smallest and the largest of them.
+    #[pyo3(name = "with_conversion_of_numeric_ranges")]
+    fn py_with_conversion_of_numeric_ranges(mut self_: PyRefMut<Self>) -> PyRefMut<Self> {
+        self_.config.is_numeric_range_converted = true;
+        self_
+    }
+
+    /// Merge alternatives of equal length which only consist of single characters
+    /// into one character class per position.
+    #[pyo3(name = "with_positional_char_classes")]
+    fn py_with_positional_char_classes(mut self_: PyRefMut<Self>) -> PyRefMut<Self> {
+        self_.config.is_positional_char_class_inferred = true;
+        self_
+    }
+
+    /// Specify how many strings the character classes inferred by `with_positional_char_classes`
+    /// may match for each string matched by the merged alternatives.
+    ///
+    /// If the tolerance is not explicitly set with this method, a default value of 4 will be used.
+    ///
+    /// Args:
+    ///     factor (int): The maximum number of strings matched per merged alternative
+    ///
+    /// Raises:
+    ///     ValueError: if `factor` is zero
+    #[pyo3(name = "with_positional_char_class_tolerance")]
+    fn py_with_positional_char_class_tolerance(
+        mut self_: PyRefMut<Self>,
+        factor: i32,
+    ) -> PyResult<PyRefMut<Self>> {
+        if factor <= 0 {
+            Err(PyValueError::new_err(
+                POSITIONAL_CHAR_CLASS_TOLERANCE_MESSAGE,
+            ))
+        } else {
+            self_.config.positional_char_class_tolerance = factor as u32;
+            Ok(self_)
+        }
+    }
+
     /// Enable case-insensitive matching of test cases so that letters match both upper and lower case.
     #[pyo3(name = "with_case_insensitive_matching")]
     fn py_with_case_insensitive_matching(mut self_: PyRefMut<Self>) -> PyRefMut<Self> {
@@ -142,6 +306,14 @@ impl RegExpBuilder {
         self_
     }
 
+    /// Merge test cases which only differ in the case of some letters by converting
+    /// exactly these letters to character classes such as `[hH]`.
+    #[pyo3(name = "with_case_insensitive_char_classes")]
+    fn py_with_case_insensitive_char_classes(mut self_: PyRefMut<Self>) -> PyRefMut<Self> {
+        self_.config.is_case_insensitive_char_class_enabled = true;
+        self_
+    }
+
     /// Replace non-capturing groups by capturing ones.
     #[pyo3(name = "with_capturing_groups")]
     fn py_with_capturing_groups(mut self_: PyRefMut<Self>) -> PyRefMut<Self> {
@@ -193,6 +365,26 @@ impl RegExpBuilder {
         }
     }
 
+    /// Specify the maximum number of characters of the resulting regular expression.
+    ///
+    /// If the exact regular expression is longer, it is generalized step by step,
+    /// e.g. by converting digits to `\d`, until it fits.
+    ///
+    /// Args:
+    ///     length (int): The maximum length of the regular expression
+    ///
+    /// Raises:
+    ///     ValueError: if `length` is zero
+    #[pyo3(name = "with_max_length")]
+    fn py_with_max_length(mut self_: PyRefMut<Self>, length: i32) -> PyResult<PyRefMut<Self>> {
+        if length <= 0 {
+            Err(PyValueError::new_err(MAXIMUM_LENGTH_MESSAGE))
+        } else {
+            self_.config.maximum_length = Some(length as usize);
+            Ok(self_)
+        }
+    }
+
     /// Convert non-ASCII characters to unicode escape sequences.
     ///
     /// The parameter `use_surrogate_pairs` specifies whether to convert astral code planes
@@ -244,32 +436,239 @@ impl RegExpBuilder {
     }
 
     /// Build the actual regular expression using the previously given settings.
+    ///
+    /// Raises:
+    ///     ValueError: if some negative test cases cannot be excluded
+    ///         or if the maximum length cannot be reached
     #[pyo3(name = "build")]
-    fn py_build(&mut self) -> String {
-        let regexp = self.build();
+    fn py_build(&mut self, py: Python<'_>) -> PyResult<String> {
+        py.allow_threads(|| self.try_build_for_python())
+            .map_err(to_value_error)
+    }
+
+    /// Build one regular expression for each of the given collections of test cases,
+    /// using the previously given settings. The test cases of this builder itself are ignored.
+    ///
+    /// The regular expressions are built in parallel on as many threads as there are CPU cores.
+    /// Each collection of test cases is accepted in the same forms as in `from_test_cases`.
+    ///
+    /// Args:
+    ///     test_case_collections (Iterable[Iterable[str | bytes | bytearray]]): The collections of test cases
+    ///     encoding (str): Either 'utf-8' or 'latin-1', defaults to 'utf-8'
+    ///
+    /// Raises:
+    ///     ValueError: if a collection of test cases is empty or under the same conditions as `build`
+    ///     TypeError: if a collection of test cases is not iterable or contains items of other types
+    #[pyo3(signature = (test_case_collections, encoding = "utf-8"))]
+    fn build_many(
+        &self,
+        py: Python<'_>,
+        test_case_collections: &Bound<'_, PyAny>,
+        encoding: &str,
+    ) -> PyResult<Vec<String>> {
+        let mut builders = vec![];
+        for test_cases in test_case_collections.iter()? {
+            let mut builder = Self::new(&test_cases?, encoding)?;
+            builder.negative_test_cases = self.negative_test_cases.clone();
+            builder.config = self.config.clone();
+            builders.push(builder);
+        }
+        py.allow_threads(|| build_in_parallel(&mut builders))
+            .map_err(to_value_error)
+    }
+
+    /// Build the regular expression using the previously given settings
+    /// and compile it with module `re`.
+    ///
+    /// The expression follows the semantics of module `re`, e.g. it is anchored
+    /// at the end with `\Z` because `$` also matches before a final line break.
+    /// Case-insensitive matching and verbose mode are passed as `re.IGNORECASE`
+    /// and `re.VERBOSE` instead of inline flags.
+    ///
+    /// Raises:
+    ///     ValueError: if some negative test cases cannot be excluded,
+    ///         if the maximum length cannot be reached or if a setting is not supported by module `re`
+    fn compile<'py>(&mut self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        let metadata = self.build_with_metadata(py)?;
+        py.import_bound("re")?
+            .call_method1("compile", (metadata.pattern, metadata.flags))
+    }
+
+    /// Build the regular expression for module `re` using the previously given settings
+    /// and return it together with information about how it has been generated.
+    ///
+    /// The pattern is the same as the one compiled by `compile`.
+    ///
+    /// Raises:
+    ///     ValueError: under the same conditions as `compile`
+    fn build_with_metadata(&mut self, py: Python<'_>) -> PyResult<RegExpMetadata> {
+        let report = self.build_python_report(py)?;
+        let re = py.import_bound("re")?;
+        let mut flags = 0;
+        if report.config.is_case_insensitive_matching {
+            flags |= re.getattr("IGNORECASE")?.extract::<i32>()?;
+        }
+        if report.config.is_verbose_mode_enabled {
+            flags |= re.getattr("VERBOSE")?.extract::<i32>()?;
+        }
+        Ok(RegExpMetadata {
+            pattern: remove_inline_flags(&report),
+            flags,
+            test_case_count: report.test_case_count(),
+            dfa_state_count: report.dfa_state_count(),
+            minimized_dfa_state_count: report.minimized_dfa_state_count(),
+            is_fallback_taken: report.is_fallback_taken(),
+            generalizations: report
+                .generalizations()
+                .iter()
+                .map(|generalization| generalization.name().to_string())
+                .collect_vec(),
+        })
+    }
+}
+
+impl RegExpBuilder {
+    fn try_build_for_python(&mut self) -> Result<String, GrexError> {
+        let regexp = self.try_build()?;
         if self.config.is_non_ascii_char_escaped {
-            replace_unicode_escape_sequences(regexp)
+            Ok(Dialect::Python.convert_syntax(&regexp))
         } else {
-            regexp
+            Ok(regexp)
         }
     }
+
+    /// Builds the regular expression in the Python dialect without holding the GIL.
+    fn build_python_report(&self, py: Python<'_>) -> PyResult<Report> {
+        let mut builder = self.clone();
+        builder.config.dialect = Dialect::Python;
+        py.allow_threads(|| builder.try_build_report())
+            .map_err(to_value_error)
+    }
+}
+
+/// Builds the regular expressions of all builders, distributing them evenly across
+/// as many threads as there are CPU cores. The results keep the order of the builders.
+fn build_in_parallel(builders: &mut [RegExpBuilder]) -> Result<Vec<String>, GrexError> {
+    let thread_count = thread::available_parallelism().map_or(1, NonZeroUsize::get);
+    let chunk_size = builders.len().div_ceil(thread_count).max(1);
+
+    thread::scope(|scope| {
+        builders
+            .chunks_mut(chunk_size)
+            .map(|chunk| {
+                scope.spawn(|| {
+                    chunk
+                        .iter_mut()
+                        .map(|builder| builder.try_build_for_python())
+                        .collect::<Result<Vec<_>, _>>()
+                })
+            })
+            .collect_vec()
+            .into_iter()
+            .map(|handle| handle.join().unwrap())
+            .flatten_ok()
+            .collect()
+    })
+}
+
+/// This enum specifies the encodings which test cases
+/// of type `bytes` or `bytearray` can be decoded with.
+#[derive(Clone, Copy)]
+enum Encoding {
+    Utf8,
+    Latin1,
 }
 
-/// Replaces Rust Unicode escape sequences to Python Unicode escape sequences.
-fn replace_unicode_escape_sequences(regexp: String) -> String {
-    lazy_static! {
-        static ref FOUR_CHARS_ESCAPE_SEQUENCE: Regex = Regex::new(r"\\u\{([0-9a-f]{4})\}").unwrap();
-        static ref FIVE_CHARS_ESCAPE_SEQUENCE: Regex = Regex::new(r"\\u\{([0-9a-f]{5})\}").unwrap();
+impl Encoding {
+    fn from_name(name: &str) -> PyResult<Self> {
+        match name.to_lowercase().replace('_', "-").as_str() {
+            "utf-8" | "utf8" => Ok(Encoding::Utf8),
+            "latin-1" | "latin1" | "iso-8859-1" => Ok(Encoding::Latin1),
+            _ => Err(PyValueError::new_err(format!(
+                "Encoding '{}' is not supported, use either 'utf-8' or 'latin-1'",
+                name
+            ))),
+        }
     }
-    let mut replacement = FOUR_CHARS_ESCAPE_SEQUENCE
-        .replace_all(&regexp, |caps: &Captures| format!("\\u{}", &caps[1]))
-        .to_string();
+}
 
-    replacement = FIVE_CHARS_ESCAPE_SEQUENCE
-        .replace_all(&replacement, |caps: &Captures| {
-            format!("\\U000{}", &caps[1])
-        })
-        .to_string();
+/// Returns an iterator which converts the items of a Python iterable to test cases
+/// one at a time. Lines of file objects are returned without their line endings.
+fn extract_test_cases<'py>(
+    test_cases: &Bound<'py, PyAny>,
+    encoding: &str,
+) -> PyResult<impl Iterator<Item = PyResult<String>> + 'py> {
+    // A single string is iterable as well but would be split into its characters.
+    if test_cases.is_instance_of::<PyString>()
+        || test_cases.is_instance_of::<PyBytes>()
+        || test_cases.is_instance_of::<PyByteArray>()
+    {
+        return Err(PyTypeError::new_err(
+            "Test cases must be given as an iterable, not as a single string",
+        ));
+    }
+    let encoding = Encoding::from_name(encoding)?;
+    let is_file = test_cases.hasattr("readline")?;
+
+    Ok(test_cases.iter()?.enumerate().map(move |(index, item)| {
+        let mut test_case = extract_test_case(&item?, encoding, index + 1)?;
+        if is_file && test_case.ends_with('\n') {
+            test_case.pop();
+            if test_case.ends_with('\r') {
+                test_case.pop();
+            }
+        }
+        Ok(test_case)
+    }))
+}
+
+fn extract_test_case(
+    item: &Bound<'_, PyAny>,
+    encoding: Encoding,
+    position: usize,
+) -> PyResult<String> {
+    if let Ok(string) = item.downcast::<PyString>() {
+        Ok(string.to_cow()?.into_owned())
+    } else if let Ok(bytes) = item.downcast::<PyBytes>() {
+        decode_test_case(bytes.as_bytes(), encoding, position)
+    } else if let Ok(byte_array) = item.downcast::<PyByteArray>() {
+        decode_test_case(&byte_array.to_vec(), encoding, position)
+    } else {
+        Err(PyTypeError::new_err(format!(
+            "Test case {} must be of type str, bytes or bytearray, not {}",
+            position,
+            item.get_type().name()?
+        )))
+    }
+}
+
+fn decode_test_case(bytes: &[u8], encoding: Encoding, position: usize) -> PyResult<String> {
+    match encoding {
+        Encoding::Utf8 => String::from_utf8(bytes.to_vec()).map_err(|_| {
+            PyValueError::new_err(format!(
+                "Test case {} is not valid UTF-8, use encoding 'latin-1' to decode it",
+                position
+            ))
+        }),
+        Encoding::Latin1 => Ok(bytes.iter().map(|&byte| char::from(byte)).collect()),
+    }
+}
+
+/// Removes the inline flags such as `(?i)` from the start of the regular expression
+/// because they are passed to module `re` separately.
+fn remove_inline_flags(report: &Report) -> String {
+    let flags = Dialect::Python.flags(&report.config);
+    if flags.is_empty() {
+        return report.regexp.clone();
+    }
+    let inline_flags = format!("(?{})", flags);
+    if let Some(regexp) = report.regexp.strip_prefix(&inline_flags) {
+        regexp.strip_prefix('\n').unwrap_or(regexp).to_string()
+    } else {
+        report.regexp.clone()
+    }
+}
 
-    replacement
+fn to_value_error(error: GrexError) -> PyErr {
+    PyValueError::new_err(error.to_string())
 }
@@ -14,30 +14,57 @@
  * limitations under the License.
  */
 
+use crate::ast::Ast;
 use crate::cluster::GraphemeCluster;
 use crate::component::Component;
 use crate::config::RegExpConfig;
 use crate::dfa::Dfa;
+use crate::dialect::Dialect;
+use crate::error::GrexError;
+use crate::explain::explain;
 use crate::expression::Expression;
+use crate::numeric::NumericPattern;
+use crate::report::Report;
 use itertools::Itertools;
 use regex::Regex;
 use std::cmp::Ordering;
+use std::collections::HashMap;
 use std::fmt::{Display, Formatter, Result};
+use std::rc::Rc;
 
 pub struct RegExp<'a> {
     ast: Expression<'a>,
     config: &'a RegExpConfig,
+    test_case_count: usize,
+    dfa_state_counts: (usize, usize),
+    is_fallback_taken: bool,
 }
 
 impl<'a> RegExp<'a> {
-    pub(crate) fn from(test_cases: &'a mut Vec<String>, config: &'a RegExpConfig) -> Self {
+    pub(crate) fn from(
+        test_cases: &'a mut Vec<String>,
+        negative_test_cases: &[String],
+        config: &'a RegExpConfig,
+    ) -> std::result::Result<Self, GrexError> {
         if config.is_case_insensitive_matching {
             Self::convert_for_case_insensitive_matching(test_cases);
         }
         Self::sort(test_cases);
-        let grapheme_clusters = Self::grapheme_clusters(test_cases, config);
-        let mut dfa = Dfa::from(&grapheme_clusters, true, config);
-        let mut ast = Expression::from(dfa, config);
+        let negative_grapheme_clusters =
+            Self::negative_grapheme_clusters(negative_test_cases, config);
+        let (numeric_exprs, remaining_test_cases) =
+            Self::numeric_ranges(test_cases, negative_test_cases, config);
+        let grapheme_clusters =
+            Self::grapheme_clusters(&remaining_test_cases, &negative_grapheme_clusters, config);
+        let mut dfa = Dfa::from(&grapheme_clusters, true, true, config);
+        let mut dfa_state_counts = (dfa.unminimized_state_count(), dfa.state_count());
+        let mut is_fallback_taken = false;
+        let mut ast = Self::with_numeric_ranges(
+            Expression::from(dfa, config.is_positional_char_class_inferred, config),
+            &numeric_exprs,
+            &grapheme_clusters,
+            config,
+        );
 
         if config.is_start_anchor_disabled && config.is_end_anchor_disabled {
             let mut regex = Self::convert_expr_to_regex(&ast, config);
@@ -50,33 +77,150 @@ impl<'a> RegExp<'a> {
             if !Self::is_each_test_case_matched_after_rotating_alternations(
                 &regex, &mut ast, test_cases,
             ) {
-                dfa = Dfa::from(&grapheme_clusters, false, config);
-                ast = Expression::from(dfa, config);
+                dfa = Dfa::from(&grapheme_clusters, false, true, config);
+                dfa_state_counts = (dfa.unminimized_state_count(), dfa.state_count());
+                ast = Self::with_numeric_ranges(
+                    Expression::from(dfa, config.is_positional_char_class_inferred, config),
+                    &numeric_exprs,
+                    &grapheme_clusters,
+                    config,
+                );
                 regex = Self::convert_expr_to_regex(&ast, config);
 
                 if !Self::regex_matches_all_test_cases(&regex, test_cases) {
-                    let mut exprs = vec![];
-                    for cluster in grapheme_clusters {
-                        let literal = Expression::new_literal(cluster, config);
-                        exprs.push(literal);
-                    }
-                    ast = Expression::new_alternation(exprs, config);
+                    ast = Self::with_numeric_ranges(
+                        Self::literal_alternation(grapheme_clusters.clone(), config),
+                        &numeric_exprs,
+                        &grapheme_clusters,
+                        config,
+                    );
+                    is_fallback_taken = true;
                 }
             }
         }
 
-        Self { ast, config }
+        let mut regexp = Self {
+            ast,
+            config,
+            test_case_count: test_cases.len(),
+            dfa_state_counts,
+            is_fallback_taken,
+        };
+
+        if !negative_test_cases.is_empty()
+            && !regexp.is_each_test_case_matched_correctly(test_cases, negative_test_cases)
+        {
+            // Merging adjacent repetition ranges within the DFA or inferring positional
+            // character classes may accept negative test cases, so both are not done here.
+            dfa = Dfa::from(&grapheme_clusters, true, false, config);
+            regexp.dfa_state_counts = (dfa.unminimized_state_count(), dfa.state_count());
+            regexp.ast = Self::with_numeric_ranges(
+                Expression::from(dfa, false, config),
+                &numeric_exprs,
+                &grapheme_clusters,
+                config,
+            );
+            regexp.is_fallback_taken = false;
+
+            if !regexp.is_each_test_case_matched_correctly(test_cases, negative_test_cases) {
+                regexp.ast = Self::with_numeric_ranges(
+                    Self::literal_alternation(grapheme_clusters.clone(), config),
+                    &numeric_exprs,
+                    &grapheme_clusters,
+                    config,
+                );
+                regexp.is_fallback_taken = true;
+            }
+
+            if !regexp.is_each_test_case_matched_correctly(test_cases, negative_test_cases) {
+                let literal_clusters = test_cases
+                    .iter()
+                    .map(|it| GraphemeCluster::from(it, config))
+                    .collect_vec();
+                regexp.ast = Self::literal_alternation(literal_clusters, config);
+            }
+
+            if !regexp.is_each_test_case_matched_correctly(test_cases, negative_test_cases) {
+                return Err(GrexError::ConflictingTestCases);
+            }
+        }
+
+        regexp.check_support()?;
+
+        Ok(regexp)
+    }
+
+    /// Creates the regular expression from an already minimized DFA,
+    /// skipping the preprocessing of test cases done by [`from`](Self::from).
+    pub(crate) fn from_dfa(
+        dfa: Dfa<'a>,
+        test_case_count: usize,
+        config: &'a RegExpConfig,
+    ) -> std::result::Result<Self, GrexError> {
+        let dfa_state_counts = (dfa.unminimized_state_count(), dfa.state_count());
+        let regexp = Self {
+            ast: Expression::from(dfa, config.is_positional_char_class_inferred, config),
+            config,
+            test_case_count,
+            dfa_state_counts,
+            is_fallback_taken: false,
+        };
+
+        regexp.check_support()?;
+
+        Ok(regexp)
+    }
+
+    pub(crate) fn to_ast(&self) -> Ast {
+        Ast::from(&self.ast)
+    }
+
+    pub(crate) fn to_explanation(&self) -> String {
+        explain(&self.to_ast(), self.config)
+    }
+
+    pub(crate) fn to_report(&self) -> Report {
+        Report {
+            regexp: self.to_string(),
+            config: self.config.clone(),
+            test_case_count: self.test_case_count,
+            dfa_state_count: self.dfa_state_counts.0,
+            minimized_dfa_state_count: self.dfa_state_counts.1,
+            is_fallback_taken: self.is_fallback_taken,
+            ast: self.to_ast(),
+        }
+    }
+
+    fn check_support(&self) -> std::result::Result<(), GrexError> {
+        if self.config.dialect == Dialect::PosixBasic && self.ast.contains_alternation() {
+            return Err(GrexError::UnsupportedFeature {
+                feature: "Alternation",
+                dialect: self.config.dialect,
+            });
+        }
+        Ok(())
     }
 
-    fn convert_for_case_insensitive_matching(test_cases: &mut Vec<String>) {
+    pub(crate) fn convert_for_case_insensitive_matching(test_cases: &mut Vec<String>) {
         // Convert only those test cases to lowercase if
         // they keep their original number of characters.
         // Otherwise, "İ" -> "i\u{307}" would not match "İ".
+        // The standard library may also know newer case mappings than the regex crate,
+        // so each converted non-ASCII character is checked once.
+        let mut folded_chars = HashMap::new();
         *test_cases = test_cases
             .iter()
             .map(|it| {
                 let lower_test_case = it.to_lowercase();
-                if lower_test_case.chars().count() == it.chars().count() {
+                if lower_test_case.chars().count() == it.chars().count()
+                    && it.chars().zip(lower_test_case.chars()).all(|(c, lower_c)| {
+                        c == lower_c
+                            || c.is_ascii()
+                            || *folded_chars
+                                .entry((c, lower_c))
+                                .or_insert_with(|| Self::is_folded_by_regex_crate(c, lower_c))
+                    })
+                {
                     lower_test_case
                 } else {
                     it.to_string()
@@ -85,13 +229,30 @@ impl<'a> RegExp<'a> {
             .collect_vec();
     }
 
+    fn is_folded_by_regex_crate(c: char, lower_c: char) -> bool {
+        Regex::new(&format!("(?i)^{}$", regex::escape(&lower_c.to_string())))
+            .map(|regex| regex.is_match(&c.to_string()))
+            .unwrap_or(false)
+    }
+
     fn convert_expr_to_regex(expr: &Expression, config: &RegExpConfig) -> Regex {
-        if config.is_output_colorized {
+        Self::compile_regex(&expr.to_string(), config)
+    }
+
+    fn convert_to_regex(&self) -> Regex {
+        Self::compile_regex(&self.format(Dialect::Rust), self.config)
+    }
+
+    /// Compiles a regular expression in Rust syntax whose character classes
+    /// match the same characters as in the selected dialect.
+    fn compile_regex(regexp: &str, config: &RegExpConfig) -> Regex {
+        let regexp = if config.is_output_colorized {
             let color_replace_regex = Regex::new("\u{1b}\\[(?:\\d+;\\d+|0)m").unwrap();
-            Regex::new(&color_replace_regex.replace_all(&expr.to_string(), "")).unwrap()
+            color_replace_regex.replace_all(regexp, "").to_string()
         } else {
-            Regex::new(&expr.to_string()).unwrap()
-        }
+            regexp.to_string()
+        };
+        Regex::new(&config.dialect.emulate_char_classes(&regexp)).unwrap()
     }
 
     fn regex_matches_all_test_cases(regex: &Regex, test_cases: &[String]) -> bool {
@@ -100,6 +261,91 @@ impl<'a> RegExp<'a> {
             .all(|test_case| regex.find_iter(test_case).count() == 1)
     }
 
+    fn regex_matches_any_test_case(regex: &Regex, test_cases: &[String]) -> bool {
+        test_cases.iter().any(|test_case| regex.is_match(test_case))
+    }
+
+    fn is_each_test_case_matched_correctly(
+        &self,
+        test_cases: &[String],
+        negative_test_cases: &[String],
+    ) -> bool {
+        let regex = self.convert_to_regex();
+        Self::regex_matches_all_test_cases(&regex, test_cases)
+            && !Self::regex_matches_any_test_case(&regex, negative_test_cases)
+    }
+
+    /// Groups the test cases which only differ in their numbers and converts each group
+    /// to an expression matching the range of its numbers. Groups whose expression would
+    /// match a negative test case are returned as remaining test cases instead.
+    fn numeric_ranges(
+        test_cases: &[String],
+        negative_test_cases: &[String],
+        config: &'a RegExpConfig,
+    ) -> (Vec<Expression<'a>>, Vec<String>) {
+        if !config.is_numeric_range_converted {
+            return (vec![], test_cases.to_vec());
+        }
+
+        let (patterns, mut remaining_test_cases) = NumericPattern::partition(test_cases);
+        let caret = if config.is_start_anchor_disabled {
+            ""
+        } else {
+            "^"
+        };
+        let dollar_sign = if config.is_end_anchor_disabled {
+            ""
+        } else {
+            "$"
+        };
+        let mut exprs = vec![];
+
+        for pattern in patterns {
+            let expr = pattern.to_expression(config);
+            let regex = Self::compile_regex(&format!("{}{}{}", caret, expr, dollar_sign), config);
+
+            if Self::regex_matches_any_test_case(&regex, negative_test_cases) {
+                remaining_test_cases.extend_from_slice(pattern.test_cases());
+            } else {
+                exprs.push(expr);
+            }
+        }
+
+        Self::sort(&mut remaining_test_cases);
+        (exprs, remaining_test_cases)
+    }
+
+    fn with_numeric_ranges(
+        expr: Expression<'a>,
+        numeric_exprs: &[Expression<'a>],
+        grapheme_clusters: &[GraphemeCluster],
+        config: &'a RegExpConfig,
+    ) -> Expression<'a> {
+        if numeric_exprs.is_empty() {
+            return expr;
+        }
+        let mut exprs = numeric_exprs.to_vec();
+        if !grapheme_clusters.is_empty() {
+            exprs.push(expr);
+        }
+        if exprs.len() == 1 {
+            exprs.pop().unwrap()
+        } else {
+            Expression::new_alternation(exprs, config)
+        }
+    }
+
+    fn literal_alternation(
+        grapheme_clusters: Vec<GraphemeCluster<'a>>,
+        config: &'a RegExpConfig,
+    ) -> Expression<'a> {
+        let exprs = grapheme_clusters
+            .into_iter()
+            .map(|cluster| Expression::new_literal(cluster, config))
+            .collect_vec();
+        Expression::new_alternation(exprs, config)
+    }
+
     fn sort(test_cases: &mut Vec<String>) {
         test_cases.sort();
         test_cases.dedup();
@@ -110,7 +356,8 @@ impl<'a> RegExp<'a> {
     }
 
     fn grapheme_clusters(
-        test_cases: &'a [String],
+        test_cases: &[String],
+        negative_grapheme_clusters: &[GraphemeCluster],
         config: &'a RegExpConfig,
     ) -> Vec<GraphemeCluster<'a>> {
         let mut clusters = test_cases
@@ -118,9 +365,13 @@ impl<'a> RegExp<'a> {
             .map(|it| GraphemeCluster::from(it, config))
             .collect_vec();
 
+        if config.is_case_insensitive_char_class_enabled && !config.is_case_insensitive_matching {
+            clusters = GraphemeCluster::merge_case_variants(clusters);
+        }
+
         if config.is_char_class_feature_enabled() {
             for cluster in clusters.iter_mut() {
-                cluster.convert_to_char_classes();
+                cluster.convert_to_char_classes(negative_grapheme_clusters);
             }
         }
 
@@ -133,6 +384,22 @@ impl<'a> RegExp<'a> {
         clusters
     }
 
+    fn negative_grapheme_clusters(
+        negative_test_cases: &[String],
+        config: &'a RegExpConfig,
+    ) -> Vec<GraphemeCluster<'a>> {
+        let mut test_cases = negative_test_cases.to_vec();
+
+        if config.is_case_insensitive_matching {
+            Self::convert_for_case_insensitive_matching(&mut test_cases);
+        }
+
+        test_cases
+            .iter()
+            .map(|it| GraphemeCluster::from(it, config))
+            .collect_vec()
+    }
+
     fn is_each_test_case_matched_after_rotating_alternations(
         regex: &Regex,
         expr: &mut Expression,
@@ -144,8 +411,8 @@ impl<'a> RegExp<'a> {
             } else if let Expression::Alternation(options, _, _, _) = expr {
                 options.rotate_right(1);
             } else if let Expression::Concatenation(first, second, _, _, _) = expr {
-                let a: &mut Expression = first;
-                let b: &mut Expression = second;
+                let a = Rc::make_mut(first);
+                let b = Rc::make_mut(second);
 
                 if let Expression::Alternation(options, _, _, _) = a {
                     options.rotate_right(1);
@@ -158,18 +425,16 @@ impl<'a> RegExp<'a> {
     }
 }
 
-impl Display for RegExp<'_> {
-    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
-        let flag =
-            if self.config.is_case_insensitive_matching && self.config.is_verbose_mode_enabled {
-                Component::IgnoreCaseAndVerboseModeFlag.to_repr(self.config.is_output_colorized)
-            } else if self.config.is_case_insensitive_matching {
-                Component::IgnoreCaseFlag.to_repr(self.config.is_output_colorized)
-            } else if self.config.is_verbose_mode_enabled {
-                Component::VerboseModeFlag.to_repr(self.config.is_output_colorized)
-            } else {
-                String::new()
-            };
+impl RegExp<'_> {
+    fn format(&self, dialect: Dialect) -> String {
+        let flags = dialect.flags(self.config);
+
+        let inline_flags = if flags.is_empty() || dialect == Dialect::EcmaScript {
+            String::new()
+        } else {
+            Component::InlineFlags(flags.clone(), self.config.is_verbose_mode_enabled)
+                .to_repr(self.config.is_output_colorized)
+        };
 
         let caret = if self.config.is_start_anchor_disabled {
             String::new()
@@ -189,7 +454,7 @@ impl Display for RegExp<'_> {
             Expression::Alternation(_, _, _, _) => {
                 format!(
                     "{}{}{}{}",
-                    flag,
+                    inline_flags,
                     caret,
                     if self.config.is_capturing_group_enabled {
                         Component::CapturedParenthesizedExpression(
@@ -210,7 +475,7 @@ impl Display for RegExp<'_> {
                 )
             }
             _ => {
-                format!("{}{}{}{}", flag, caret, self.ast, dollar_sign)
+                format!("{}{}{}{}", inline_flags, caret, self.ast, dollar_sign)
             }
         };
 
@@ -231,17 +496,77 @@ impl Display for RegExp<'_> {
                     "\\s",
                 )
                 .replace(' ', "\\ ");
+            regexp = indent_regexp(regexp, self.config);
         }
 
-        write!(
-            f,
-            "{}",
-            if self.config.is_verbose_mode_enabled {
-                indent_regexp(regexp, self.config)
-            } else {
-                regexp
+        if dialect == Dialect::Rust {
+            return regexp;
+        }
+
+        regexp = dialect.convert_syntax(&regexp);
+
+        if !self.config.is_end_anchor_disabled {
+            // The dollar sign anchor is always the last one in the regular expression.
+            let position = regexp.rfind('$').unwrap();
+            regexp.replace_range(position..position + 1, dialect.end_anchor());
+        }
+
+        if dialect == Dialect::EcmaScript {
+            regexp = format!(
+                "/{}/{}",
+                regexp,
+                Component::Flags(flags).to_repr(self.config.is_output_colorized)
+            );
+        }
+
+        regexp
+    }
+
+    /// Returns the pattern and the flags to be passed to the `RegExp` constructor of JavaScript.
+    /// Unlike in the regular expression literal of [`Dialect::EcmaScript`], the unicode flag
+    /// is only set if the pattern requires it.
+    #[cfg(target_family = "wasm")]
+    pub(crate) fn to_javascript(&self) -> (String, String) {
+        let literal = self.format(Dialect::EcmaScript);
+        // The regular expression literal has the form `/pattern/flags`.
+        let flags_position = literal.rfind('/').unwrap();
+        let pattern = literal[1..flags_position].to_string();
+        let is_unicode_required = requires_unicode_flag(&pattern);
+        let flags = literal[flags_position + 1..]
+            .chars()
+            .filter(|&flag| flag != 'u' || is_unicode_required)
+            .collect();
+        (pattern, flags)
+    }
+}
+
+/// Returns whether a pattern in ECMAScript syntax is only valid or only matches
+/// as intended if the unicode flag `u` is set. This is the case for escape sequences
+/// of astral code points, Unicode property classes and literal astral characters,
+/// which would otherwise be treated as two separate UTF-16 code units.
+#[cfg(target_family = "wasm")]
+fn requires_unicode_flag(pattern: &str) -> bool {
+    let mut chars = pattern.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => {
+                let escaped_char = chars.next();
+                if matches!(escaped_char, Some('p' | 'P'))
+                    || (escaped_char == Some('u') && chars.clone().next() == Some('{'))
+                {
+                    return true;
+                }
             }
-        )
+            _ if c > '\u{ffff}' => return true,
+            _ => {}
+        }
+    }
+    false
+}
+
+impl Display for RegExp<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(f, "{}", self.format(self.config.dialect))
     }
 }
 
@@ -0,0 +1,221 @@
+/*
+ * Copyright © 2019-today Peter M. Stahl pemistahl@gmail.com
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either expressed or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::ast::Ast;
+use crate::config::RegExpConfig;
+use crate::explain::explain;
+use crate::generalization::Generalization;
+use itertools::Itertools;
+
+/// This struct contains the generated regular expression together with
+/// information about how it has been generated.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Report {
+    pub(crate) regexp: String,
+    pub(crate) config: RegExpConfig,
+    pub(crate) test_case_count: usize,
+    pub(crate) dfa_state_count: usize,
+    pub(crate) minimized_dfa_state_count: usize,
+    pub(crate) is_fallback_taken: bool,
+    pub(crate) ast: Ast,
+}
+
+impl Report {
+    /// Returns the generated regular expression.
+    pub fn regexp(&self) -> &str {
+        &self.regexp
+    }
+
+    /// Returns the number of unique test cases the regular expression has been generated from.
+    pub fn test_case_count(&self) -> usize {
+        self.test_case_count
+    }
+
+    /// Returns the number of states of the DFA before minimization.
+    pub fn dfa_state_count(&self) -> usize {
+        self.dfa_state_count
+    }
+
+    /// Returns the number of states of the DFA after minimization.
+    pub fn minimized_dfa_state_count(&self) -> usize {
+        self.minimized_dfa_state_count
+    }
+
+    /// Returns `true` if no generalized expression could be found for the test cases,
+    /// so that the regular expression is a plain alternation of them instead.
+    pub fn is_fallback_taken(&self) -> bool {
+        self.is_fallback_taken
+    }
+
+    /// Returns the generalizations which have been applied for the regular expression
+    /// to stay within the maximum length, in the order of their application.
+    /// The list is empty if no maximum length has been set or if it has not been exceeded.
+    pub fn generalizations(&self) -> &[Generalization] {
+        &self.config.generalizations
+    }
+
+    /// Describes the generated regular expression in English, in the same way as
+    /// [`RegExpBuilder::explain`](crate::RegExpBuilder::explain) does,
+    /// without building the regular expression again.
+    pub fn explanation(&self) -> String {
+        explain(&self.ast, &self.config)
+    }
+
+    /// Returns this report as a JSON object.
+    pub fn to_json(&self) -> String {
+        let config = &self.config;
+        let settings = [
+            ("dialect", json_string(&config.dialect.to_string())),
+            (
+                "minimum_repetitions",
+                config.minimum_repetitions.to_string(),
+            ),
+            (
+                "minimum_substring_length",
+                config.minimum_substring_length.to_string(),
+            ),
+            (
+                "maximum_length",
+                config
+                    .maximum_length
+                    .map_or_else(|| "null".to_string(), |length| length.to_string()),
+            ),
+            ("is_digit_converted", config.is_digit_converted.to_string()),
+            (
+                "is_non_digit_converted",
+                config.is_non_digit_converted.to_string(),
+            ),
+            ("is_space_converted", config.is_space_converted.to_string()),
+            (
+                "is_non_space_converted",
+                config.is_non_space_converted.to_string(),
+            ),
+            ("is_word_converted", config.is_word_converted.to_string()),
+            (
+                "is_non_word_converted",
+                config.is_non_word_converted.to_string(),
+            ),
+            (
+                "is_general_category_converted",
+                config.is_general_category_converted.to_string(),
+            ),
+            (
+                "is_script_converted",
+                config.is_script_converted.to_string(),
+            ),
+            (
+                "is_repetition_converted",
+                config.is_repetition_converted.to_string(),
+            ),
+            (
+                "is_numeric_range_converted",
+                config.is_numeric_range_converted.to_string(),
+            ),
+            (
+                "is_positional_char_class_inferred",
+                config.is_positional_char_class_inferred.to_string(),
+            ),
+            (
+                "positional_char_class_tolerance",
+                config.positional_char_class_tolerance.to_string(),
+            ),
+            (
+                "is_case_insensitive_matching",
+                config.is_case_insensitive_matching.to_string(),
+            ),
+            (
+                "is_case_insensitive_char_class_enabled",
+                config.is_case_insensitive_char_class_enabled.to_string(),
+            ),
+            (
+                "is_capturing_group_enabled",
+                config.is_capturing_group_enabled.to_string(),
+            ),
+            (
+                "is_non_ascii_char_escaped",
+                config.is_non_ascii_char_escaped.to_string(),
+            ),
+            (
+                "is_astral_code_point_converted_to_surrogate",
+                config
+                    .is_astral_code_point_converted_to_surrogate
+                    .to_string(),
+            ),
+            (
+                "is_verbose_mode_enabled",
+                config.is_verbose_mode_enabled.to_string(),
+            ),
+            (
+                "is_start_anchor_disabled",
+                config.is_start_anchor_disabled.to_string(),
+            ),
+            (
+                "is_end_anchor_disabled",
+                config.is_end_anchor_disabled.to_string(),
+            ),
+        ];
+        let fields = [
+            ("regexp", json_string(&self.regexp)),
+            ("config", json_object(&settings)),
+            ("test_case_count", self.test_case_count.to_string()),
+            ("dfa_state_count", self.dfa_state_count.to_string()),
+            (
+                "minimized_dfa_state_count",
+                self.minimized_dfa_state_count.to_string(),
+            ),
+            ("is_fallback_taken", self.is_fallback_taken.to_string()),
+            (
+                "generalizations",
+                format!(
+                    "[{}]",
+                    self.generalizations()
+                        .iter()
+                        .map(|generalization| json_string(generalization.name()))
+                        .join(",")
+                ),
+            ),
+        ];
+        json_object(&fields)
+    }
+}
+
+fn json_object(fields: &[(&str, String)]) -> String {
+    format!(
+        "{{{}}}",
+        fields
+            .iter()
+            .map(|(key, value)| format!("{}:{}", json_string(key), value))
+            .join(",")
+    )
+}
+
+fn json_string(s: &str) -> String {
+    let mut json = String::with_capacity(s.len() + 2);
+    json.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            '\r' => json.push_str("\\r"),
+            '\t' => json.push_str("\\t"),
+            c if c.is_control() => json.push_str(&format!("\\u{:04x}", c as u32)),
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+    json
+}
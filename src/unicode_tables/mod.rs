@@ -15,9 +15,11 @@
  */
 
 mod decimal;
+mod script;
 mod space;
 mod word;
 
 pub use decimal::DECIMAL_NUMBER;
+pub use script::BY_NAME as SCRIPTS;
 pub use space::WHITE_SPACE;
 pub use word::WORD;
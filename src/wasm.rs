@@ -17,12 +17,65 @@
 #![allow(non_snake_case)]
 
 use crate::builder::{
-    RegExpBuilder as Builder, MINIMUM_REPETITIONS_MESSAGE, MINIMUM_SUBSTRING_LENGTH_MESSAGE,
-    MISSING_TEST_CASES_MESSAGE,
+    RegExpBuilder as Builder, MAXIMUM_LENGTH_MESSAGE, MINIMUM_REPETITIONS_MESSAGE,
+    MINIMUM_SUBSTRING_LENGTH_MESSAGE, MISSING_TEST_CASES_MESSAGE,
+    POSITIONAL_CHAR_CLASS_TOLERANCE_MESSAGE,
 };
+use crate::dialect::Dialect;
 use itertools::Itertools;
+use js_sys::{Array, Object, Reflect};
+use std::str::FromStr;
 use wasm_bindgen::prelude::*;
 
+#[wasm_bindgen(typescript_custom_section)]
+const TYPESCRIPT_DEFINITIONS: &'static str = r#"
+/** The settings accepted by `RegExpBuilder.fromOptions`. All of them are optional. */
+export interface RegExpBuilderOptions {
+    digits?: boolean;
+    nonDigits?: boolean;
+    whitespace?: boolean;
+    nonWhitespace?: boolean;
+    words?: boolean;
+    nonWords?: boolean;
+    generalCategories?: boolean;
+    scripts?: boolean;
+    repetitions?: boolean;
+    minimumRepetitions?: number;
+    minimumSubstringLength?: number;
+    numericRanges?: boolean;
+    positionalCharClasses?: boolean;
+    positionalCharClassTolerance?: number;
+    caseInsensitive?: boolean;
+    caseInsensitiveCharClasses?: boolean;
+    capturingGroups?: boolean;
+    escapeNonAscii?: boolean;
+    surrogatePairs?: boolean;
+    verbose?: boolean;
+    anchors?: boolean;
+    startAnchor?: boolean;
+    endAnchor?: boolean;
+    maxLength?: number;
+    negativeTestCases?: string[];
+    dialect?: "rust" | "pcre" | "ecmascript" | "python" | "go" | "dotnet" | "java" | "posix-ere" | "posix-bre";
+}
+
+/** A regular expression which can be passed to `new RegExp(pattern, flags)`. */
+export interface BuildResult {
+    pattern: string;
+    flags: string;
+    unicode: boolean;
+}
+"#;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = "RegExpBuilderOptions")]
+    pub type RegExpBuilderOptions;
+
+    #[wasm_bindgen(typescript_type = "BuildResult")]
+    pub type BuildResult;
+}
+
 /// This class builds regular expressions from user-provided test cases.
 #[wasm_bindgen]
 #[derive(Clone)]
@@ -51,6 +104,29 @@ impl RegExpBuilder {
         })
     }
 
+    /// Specifies the test cases to build the regular expression from together with all
+    /// settings at once, e.g. `{ digits: true, anchors: false, dialect: "ecmascript" }`.
+    ///
+    /// Each setting corresponds to one of the methods of `RegExpBuilder`.
+    /// Settings which are left out keep their default values.
+    ///
+    /// ⚠ Throws an error if `testCases` is empty, if an option is unknown
+    /// or if the value of an option is invalid.
+    pub fn fromOptions(
+        testCases: Box<[JsValue]>,
+        options: Option<RegExpBuilderOptions>,
+    ) -> Result<RegExpBuilder, JsValue> {
+        let mut builder = Self::from(testCases)?;
+        if let Some(options) = options {
+            let options = Object::from(JsValue::from(options));
+            for name in Object::keys(&options).iter() {
+                let value = Reflect::get(&options, &name)?;
+                builder.set_option(&name.as_string().unwrap_or_default(), &value)?;
+            }
+        }
+        Ok(builder)
+    }
+
     /// Tells `RegExpBuilder` to convert any Unicode decimal digit to character class `\d`.
     ///
     /// This method takes precedence over `withConversionOfWords` if both are set.
@@ -208,7 +284,143 @@ impl RegExpBuilder {
     }
 
     /// Builds the actual regular expression using the previously given settings.
-    pub fn build(&mut self) -> String {
-        self.builder.build()
+    ///
+    /// Unless another dialect has been selected with `fromOptions`, the result uses
+    /// the syntax of Rust's regex crate. Use `buildResult` to obtain a pattern
+    /// in native ECMAScript syntax together with its flags.
+    ///
+    /// ⚠ Throws an error if some negative test cases cannot be excluded,
+    /// if the maximum length cannot be reached or if the selected dialect
+    /// does not support some of the settings.
+    pub fn build(&mut self) -> Result<String, JsValue> {
+        self.builder
+            .try_build()
+            .map_err(|error| JsValue::from(error.to_string()))
+    }
+
+    /// Builds the regular expression for JavaScript using the previously given settings
+    /// and returns it as `{ pattern, flags, unicode }`, ready to be passed to
+    /// `new RegExp(pattern, flags)`. The selected dialect is ignored.
+    ///
+    /// Inline flags such as `(?i)` are lifted into `flags`. The unicode flag `u` is only set
+    /// if the pattern needs it, e.g. because it contains characters outside of the
+    /// Basic Multilingual Plane. `unicode` tells whether it is set. Escaped astral code points
+    /// are written as `\u{...}` with the unicode flag or as surrogate pairs `\uXXXX\uXXXX`
+    /// if `withEscapingOfNonAsciiChars(true)` has been called.
+    ///
+    /// ⚠ Throws an error if some negative test cases cannot be excluded,
+    /// if the maximum length cannot be reached or if verbose mode is enabled.
+    pub fn buildResult(&mut self) -> Result<BuildResult, JsValue> {
+        let (pattern, flags) = self
+            .builder
+            .try_build_for_javascript()
+            .map_err(|error| JsValue::from(error.to_string()))?;
+        let is_unicode_flag_set = flags.contains('u');
+
+        let result = Object::new();
+        Reflect::set(&result, &"pattern".into(), &pattern.into())?;
+        Reflect::set(&result, &"flags".into(), &flags.into())?;
+        Reflect::set(&result, &"unicode".into(), &is_unicode_flag_set.into())?;
+        Ok(result.unchecked_into())
+    }
+}
+
+impl RegExpBuilder {
+    fn set_option(&mut self, name: &str, value: &JsValue) -> Result<(), JsValue> {
+        let config = &mut self.builder.config;
+        match name {
+            "digits" => config.is_digit_converted = bool_option(name, value)?,
+            "nonDigits" => config.is_non_digit_converted = bool_option(name, value)?,
+            "whitespace" => config.is_space_converted = bool_option(name, value)?,
+            "nonWhitespace" => config.is_non_space_converted = bool_option(name, value)?,
+            "words" => config.is_word_converted = bool_option(name, value)?,
+            "nonWords" => config.is_non_word_converted = bool_option(name, value)?,
+            "generalCategories" => config.is_general_category_converted = bool_option(name, value)?,
+            "scripts" => config.is_script_converted = bool_option(name, value)?,
+            "repetitions" => config.is_repetition_converted = bool_option(name, value)?,
+            "minimumRepetitions" => {
+                config.minimum_repetitions =
+                    positive_number_option(name, value, MINIMUM_REPETITIONS_MESSAGE)?
+            }
+            "minimumSubstringLength" => {
+                config.minimum_substring_length =
+                    positive_number_option(name, value, MINIMUM_SUBSTRING_LENGTH_MESSAGE)?
+            }
+            "numericRanges" => config.is_numeric_range_converted = bool_option(name, value)?,
+            "positionalCharClasses" => {
+                config.is_positional_char_class_inferred = bool_option(name, value)?
+            }
+            "positionalCharClassTolerance" => {
+                config.positional_char_class_tolerance =
+                    positive_number_option(name, value, POSITIONAL_CHAR_CLASS_TOLERANCE_MESSAGE)?
+            }
+            "caseInsensitive" => config.is_case_insensitive_matching = bool_option(name, value)?,
+            "caseInsensitiveCharClasses" => {
+                config.is_case_insensitive_char_class_enabled = bool_option(name, value)?
+            }
+            "capturingGroups" => config.is_capturing_group_enabled = bool_option(name, value)?,
+            "escapeNonAscii" => config.is_non_ascii_char_escaped = bool_option(name, value)?,
+            "surrogatePairs" => {
+                config.is_astral_code_point_converted_to_surrogate = bool_option(name, value)?
+            }
+            "verbose" => config.is_verbose_mode_enabled = bool_option(name, value)?,
+            "anchors" => {
+                let is_disabled = !bool_option(name, value)?;
+                config.is_start_anchor_disabled = is_disabled;
+                config.is_end_anchor_disabled = is_disabled;
+            }
+            "startAnchor" => config.is_start_anchor_disabled = !bool_option(name, value)?,
+            "endAnchor" => config.is_end_anchor_disabled = !bool_option(name, value)?,
+            "maxLength" => {
+                config.maximum_length = Some(positive_number_option::<usize>(
+                    name,
+                    value,
+                    MAXIMUM_LENGTH_MESSAGE,
+                )?)
+            }
+            "dialect" => {
+                let dialect = value
+                    .as_string()
+                    .ok_or_else(|| JsValue::from(format!("Option '{}' must be a string", name)))?;
+                config.dialect = Dialect::from_str(&dialect).map_err(JsValue::from)?;
+            }
+            "negativeTestCases" => {
+                self.builder.negative_test_cases = string_array_option(name, value)?
+            }
+            _ => return Err(JsValue::from(format!("Unknown option '{}'", name))),
+        }
+        Ok(())
+    }
+}
+
+fn bool_option(name: &str, value: &JsValue) -> Result<bool, JsValue> {
+    value
+        .as_bool()
+        .ok_or_else(|| JsValue::from(format!("Option '{}' must be a boolean", name)))
+}
+
+fn string_array_option(name: &str, value: &JsValue) -> Result<Vec<String>, JsValue> {
+    let strings = if Array::is_array(value) {
+        Array::from(value).iter().map(|it| it.as_string()).collect()
+    } else {
+        None
+    };
+    strings.ok_or_else(|| JsValue::from(format!("Option '{}' must be an array of strings", name)))
+}
+
+fn positive_number_option<T: TryFrom<u64>>(
+    name: &str,
+    value: &JsValue,
+    zero_message: &str,
+) -> Result<T, JsValue> {
+    let number = value
+        .as_f64()
+        .filter(|number| number.fract() == 0.0 && *number >= 0.0)
+        .ok_or_else(|| {
+            JsValue::from(format!("Option '{}' must be a non-negative integer", name))
+        })?;
+    if number == 0.0 {
+        return Err(JsValue::from(zero_message));
     }
+    T::try_from(number as u64).map_err(|_| JsValue::from(format!("Option '{}' is too large", name)))
 }
@@ -15,6 +15,7 @@
  */
 
 #![cfg(not(target_family = "wasm"))]
+#![allow(clippy::needless_borrows_for_generic_args)]
 
 use assert_cmd::Command;
 use indoc::indoc;
@@ -126,7 +127,7 @@ mod no_conversion {
             grex.args(&["-f", file.path().to_str().unwrap()]);
             grex.assert()
                 .success()
-                .stdout(predicate::eq("^(?:b\\\\n|äöü|[ac♥])$\n"));
+                .stdout(predicate::eq("^(?:b\\\\n|äöü|[ac♥])?$\n"));
         }
 
         #[test]
@@ -135,7 +136,7 @@ mod no_conversion {
             grex.write_stdin("a\nb\\n\n\nc\näöü\n♥")
                 .arg("-")
                 .assert()
-                .stdout(predicate::eq("^(?:b\\\\n|äöü|[ac♥])$\n"));
+                .stdout(predicate::eq("^(?:b\\\\n|äöü|[ac♥])?$\n"));
         }
 
         #[test]
@@ -147,7 +148,29 @@ mod no_conversion {
             grex.write_stdin(file.path().to_str().unwrap())
                 .args(&["-f", "-"])
                 .assert()
-                .stdout(predicate::eq("^(?:b\\\\n|äöü|[ac♥])$\n"));
+                .stdout(predicate::eq("^(?:b\\\\n|äöü|[ac♥])?$\n"));
+        }
+
+        #[test]
+        fn succeeds_with_duplicate_test_cases_from_stdin() {
+            let mut grex = init_command();
+            grex.write_stdin("a\nb\na\r\nb\n".repeat(1000))
+                .arg("-")
+                .assert()
+                .stdout(predicate::eq("^[ab]$\n"));
+        }
+
+        #[test]
+        fn fails_with_invalid_utf8_from_stdin() {
+            let mut grex = init_command();
+            grex.write_stdin(b"a\nb\xff\nc".as_slice())
+                .arg("-")
+                .assert()
+                .failure()
+                .stdout(predicate::str::is_empty())
+                .stderr(predicate::eq(
+                    "error: The specified file's encoding is not valid UTF-8 in line 2\n",
+                ));
         }
 
         #[test]
@@ -189,12 +212,27 @@ mod no_conversion {
         }
 
         #[test]
-        fn fails_with_first_file_input_and_then_direct_input() {
+        fn fails_when_file_is_empty() {
+            let file = NamedTempFile::new().unwrap();
+
             let mut grex = init_command();
-            grex.args(&["-f", "/path/to/some/file", TEST_CASE]);
-            grex.assert().failure().stderr(predicate::str::contains(
-                "the argument '--file <FILE>' cannot be used with '[INPUT]...'",
-            ));
+            grex.args(["-f", file.path().to_str().unwrap()]);
+            grex.assert()
+                .failure()
+                .stdout(predicate::str::is_empty())
+                .stderr(predicate::eq(
+                    "error: No test cases have been provided for regular expression generation\n",
+                ));
+        }
+
+        #[test]
+        fn succeeds_with_first_file_input_and_then_direct_input() {
+            let mut file = NamedTempFile::new().unwrap();
+            writeln!(file, "a\nb").unwrap();
+
+            let mut grex = init_command();
+            grex.args(["-f", file.path().to_str().unwrap(), "c"]);
+            grex.assert().success().stdout(predicate::eq("^[a-c]$\n"));
         }
     }
 
@@ -3968,6 +4006,1177 @@ mod word_non_word_conversion {
     }
 }
 
+mod negative_test_cases {
+    use super::*;
+
+    #[test]
+    fn succeeds_with_exclude_option() {
+        let mut grex = init_command();
+        grex.args(["--words", "--exclude", "c3", "a1", "b2"]);
+        grex.assert().success().stdout(predicate::eq("^[ab]\\w$\n"));
+    }
+
+    #[test]
+    fn succeeds_with_multiple_exclude_options() {
+        let mut grex = init_command();
+        grex.args([
+            "--words",
+            "--exclude",
+            "xyz",
+            "--exclude",
+            "abx",
+            "abc",
+            "abd",
+        ]);
+        grex.assert()
+            .success()
+            .stdout(predicate::eq("^a\\w[cd]$\n"));
+    }
+
+    #[test]
+    fn succeeds_with_exclude_file_option() {
+        let mut file = NamedTempFile::new().unwrap();
+        writeln!(file, "xyz\nabx").unwrap();
+
+        let mut grex = init_command();
+        grex.args([
+            "--words",
+            "--exclude-file",
+            file.path().to_str().unwrap(),
+            "abc",
+            "abd",
+        ]);
+        grex.assert()
+            .success()
+            .stdout(predicate::eq("^a\\w[cd]$\n"));
+    }
+
+    #[test]
+    fn fails_when_negative_test_case_equals_test_case() {
+        let mut grex = init_command();
+        grex.args(["--exclude", "abc", "abc", "def"]);
+        grex.assert()
+            .failure()
+            .stdout(predicate::str::is_empty())
+            .stderr(predicate::eq(
+                "error: Some negative test cases cannot be excluded because they are matched by the test cases themselves\n",
+            ));
+    }
+
+    #[test]
+    fn fails_when_exclude_file_does_not_exist() {
+        let mut grex = init_command();
+        grex.args(["--exclude-file", "/path/to/non-existing/file", "abc"]);
+        grex.assert()
+            .failure()
+            .stdout(predicate::str::is_empty())
+            .stderr(predicate::eq(
+                "error: the specified file could not be found\n",
+            ));
+    }
+}
+
+mod dialects {
+    use super::*;
+
+    #[test]
+    fn succeeds_with_python_dialect_option() {
+        let mut grex = init_command();
+        grex.args([
+            "--dialect",
+            "python",
+            "--escape",
+            "--ignore-case",
+            "♥",
+            "💩",
+        ]);
+        grex.assert()
+            .success()
+            .stdout(predicate::eq("(?i)^(?:\\u2665|\\U0001f4a9)\\Z\n"));
+    }
+
+    #[test]
+    fn succeeds_with_ecmascript_dialect_option() {
+        let mut grex = init_command();
+        grex.args([
+            "--dialect",
+            "ecmascript",
+            "--ignore-case",
+            "--digits",
+            "a/1",
+            "b-٣",
+        ]);
+        grex.assert()
+            .success()
+            .stdout(predicate::eq("/^(?:a\\/\\d|b-٣)$/iu\n"));
+    }
+
+    #[test]
+    fn succeeds_with_posix_extended_dialect_option() {
+        let mut grex = init_command();
+        grex.args(["--dialect", "posix-ere", "--digits", "a1", "b22", "(c)"]);
+        grex.assert().success().stdout(predicate::eq(
+            "^(\\(c\\)|b[[:digit:]][[:digit:]]|a[[:digit:]])$\n",
+        ));
+    }
+
+    #[test]
+    fn succeeds_with_posix_basic_dialect_option() {
+        let mut grex = init_command();
+        grex.args(["--dialect", "posix-bre", "--repetitions", "(aab)", "(aaab)"]);
+        grex.assert()
+            .success()
+            .stdout(predicate::eq("^(a\\{2,3\\}b)$\n"));
+    }
+
+    #[test]
+    fn fails_with_alternation_in_posix_basic_dialect() {
+        let mut grex = init_command();
+        grex.args(["--dialect", "posix-bre", "abc", "def"]);
+        grex.assert()
+            .failure()
+            .stdout(predicate::str::is_empty())
+            .stderr(predicate::eq(
+                "error: Alternation is not supported by the POSIX BRE regex dialect\n",
+            ));
+    }
+
+    #[test]
+    fn fails_with_ignore_case_in_posix_extended_dialect() {
+        let mut grex = init_command();
+        grex.args(["--dialect", "posix-ere", "--ignore-case", "abc"]);
+        grex.assert()
+            .failure()
+            .stdout(predicate::str::is_empty())
+            .stderr(predicate::eq(
+            "error: Case-insensitive matching is not supported by the POSIX ERE regex dialect\n",
+        ));
+    }
+
+    #[test]
+    fn fails_with_verbose_mode_in_go_dialect() {
+        let mut grex = init_command();
+        grex.args(["--dialect", "go", "--verbose", "abc"]);
+        grex.assert()
+            .failure()
+            .stdout(predicate::str::is_empty())
+            .stderr(predicate::eq(
+                "error: Verbose mode is not supported by the Go regex dialect\n",
+            ));
+    }
+
+    #[test]
+    fn fails_with_unknown_dialect() {
+        let mut grex = init_command();
+        grex.args(["--dialect", "perl", "abc"]);
+        grex.assert().failure().stderr(predicate::str::contains(
+            "invalid value 'perl' for '--dialect <DIALECT>'",
+        ));
+    }
+}
+
+mod numeric_range_conversion {
+    use super::*;
+
+    #[test]
+    fn succeeds_with_numeric_ranges_option() {
+        let mut grex = init_command();
+        grex.args(["--numeric-ranges", "1980", "2029", "1999"]);
+        grex.assert()
+            .success()
+            .stdout(predicate::eq("^(?:19[89][0-9]|20[0-2][0-9])$\n"));
+    }
+
+    #[test]
+    fn succeeds_with_numeric_ranges_option_and_surrounding_text() {
+        let mut grex = init_command();
+        grex.args(["--numeric-ranges", "id 8", "id 12", "none"]);
+        grex.assert()
+            .success()
+            .stdout(predicate::eq("^(?:id (?:1[0-2]|[89])|none)$\n"));
+    }
+
+    #[test]
+    fn succeeds_with_numeric_ranges_option_and_exclude_option() {
+        let mut grex = init_command();
+        grex.args(["--numeric-ranges", "--exclude", "5", "1", "9"]);
+        grex.assert().success().stdout(predicate::eq("^[19]$\n"));
+    }
+}
+
+mod unicode_property_conversion {
+    use super::*;
+
+    #[test]
+    fn succeeds_with_categories_option() {
+        let mut grex = init_command();
+        grex.args(["--categories", "Ab1", "Xy2"]);
+        grex.assert()
+            .success()
+            .stdout(predicate::eq("^\\p{Lu}\\p{Ll}\\p{Nd}$\n"));
+    }
+
+    #[test]
+    fn succeeds_with_scripts_option() {
+        let mut grex = init_command();
+        grex.args(["--scripts", "--repetitions", "αβ", "абв"]);
+        grex.assert()
+            .success()
+            .stdout(predicate::eq("^(?:\\p{Greek}{2}|\\p{Cyrillic}{3})$\n"));
+    }
+
+    #[test]
+    fn succeeds_with_categories_and_digits_option() {
+        let mut grex = init_command();
+        grex.args(["--categories", "--digits", "A1"]);
+        grex.assert()
+            .success()
+            .stdout(predicate::eq("^\\p{Lu}\\d$\n"));
+    }
+
+    #[test]
+    fn succeeds_with_scripts_option_and_dialect_option() {
+        let mut grex = init_command();
+        grex.args(["--scripts", "--dialect", "ecmascript", "αβ"]);
+        grex.assert()
+            .success()
+            .stdout(predicate::eq("/^\\p{Script=Greek}\\p{Script=Greek}$/u\n"));
+    }
+
+    #[test]
+    fn fails_with_scripts_option_and_unsupported_dialect() {
+        let mut grex = init_command();
+        grex.args(["--scripts", "--dialect", "dotnet", "αβ"]);
+        grex.assert().failure().stderr(predicate::eq(
+            "error: Conversion of scripts is not supported by the .NET regex dialect\n",
+        ));
+    }
+}
+
+mod positional_char_classes {
+    use super::*;
+
+    #[test]
+    fn succeeds_with_positional_classes_option() {
+        let mut grex = init_command();
+        grex.args(["--positional-classes", "a1x", "b2x", "c3x"]);
+        grex.assert()
+            .success()
+            .stdout(predicate::eq("^[a-c][1-3]x$\n"));
+    }
+
+    #[test]
+    fn succeeds_with_positional_tolerance_option() {
+        let mut grex = init_command();
+        grex.args([
+            "--positional-classes",
+            "--positional-tolerance",
+            "1",
+            "a1",
+            "b2",
+        ]);
+        grex.assert()
+            .success()
+            .stdout(predicate::eq("^(?:a1|b2)$\n"));
+    }
+
+    #[test]
+    fn fails_with_zero_positional_tolerance() {
+        let mut grex = init_command();
+        grex.args(["--positional-classes", "--positional-tolerance", "0", "a"]);
+        grex.assert().failure().stderr(predicate::str::contains(
+            "invalid value '0' for '--positional-tolerance <FACTOR>': \
+            Tolerance factor must be greater than zero",
+        ));
+    }
+
+    #[test]
+    fn fails_with_invalid_positional_tolerance() {
+        let mut grex = init_command();
+        grex.args(["--positional-classes", "--positional-tolerance", "x", "a"]);
+        grex.assert().failure().stderr(predicate::str::contains(
+            "invalid value 'x' for '--positional-tolerance <FACTOR>': \
+            Tolerance factor is not a valid unsigned integer",
+        ));
+    }
+
+    #[test]
+    fn succeeds_with_positional_classes_and_escape_option() {
+        let mut grex = init_command();
+        grex.args(["--positional-classes", "--escape", "ä1", "ö2"]);
+        grex.assert()
+            .success()
+            .stdout(predicate::eq("^(?:\\u{e4}1|\\u{f6}2)$\n"));
+    }
+
+    #[test]
+    fn succeeds_with_positional_classes_of_ascii_chars_and_escape_option() {
+        let mut grex = init_command();
+        grex.args(["--positional-classes", "--escape", "äa1", "äb2"]);
+        grex.assert()
+            .success()
+            .stdout(predicate::eq("^\\u{e4}[ab][12]$\n"));
+    }
+}
+
+mod case_insensitive_char_classes {
+    use super::*;
+
+    #[test]
+    fn succeeds_with_case_classes_option() {
+        let mut grex = init_command();
+        grex.args(["--case-classes", "Hello", "hello", "world"]);
+        grex.assert()
+            .success()
+            .stdout(predicate::eq("^(?:[hH]ello|world)$\n"));
+    }
+
+    #[test]
+    fn succeeds_with_case_classes_and_escape_and_repetitions_options() {
+        let mut grex = init_command();
+        grex.args(["--case-classes", "-e", "-r", "ääää", "ÄÄÄÄ"]);
+        grex.assert()
+            .success()
+            .stdout(predicate::eq("^[\\u{e4}\\u{c4}]{4}$\n"));
+    }
+
+    #[test]
+    fn fails_with_ignore_case_option() {
+        let mut grex = init_command();
+        grex.args(["--case-classes", "--ignore-case", "Hello", "hello"]);
+        grex.assert().failure().stderr(predicate::str::contains(
+            "the argument '--case-classes' cannot be used with '--ignore-case'",
+        ));
+    }
+}
+
+mod interactive_session {
+    use super::*;
+
+    #[test]
+    fn succeeds_with_added_test_cases() {
+        let mut grex = init_command();
+        grex.args(["--interactive"]);
+        grex.write_stdin("abc\n123\n:quit\n");
+        grex.assert().success().stdout(predicate::eq(indoc!(
+            "
+            ^abc$
+              [+] abc: matched
+            ^(?:123|abc)$
+              [+] abc: matched
+              [+] 123: matched
+            "
+        )));
+    }
+
+    #[test]
+    fn succeeds_with_initial_test_cases_and_options() {
+        let mut grex = init_command();
+        grex.args(["--interactive", "--digits", "123"]);
+        grex.write_stdin("4567\n");
+        grex.assert().success().stdout(predicate::eq(indoc!(
+            "
+            ^\\d\\d\\d$
+              [+] 123: matched
+            ^\\d\\d\\d(?:\\d)?$
+              [+] 123: matched
+              [+] 4567: matched
+            "
+        )));
+    }
+
+    #[test]
+    fn succeeds_with_negative_test_cases_and_removal() {
+        let mut grex = init_command();
+        grex.args(["--interactive", "a", "b"]);
+        grex.write_stdin(":exclude c\n:remove a\n");
+        grex.assert().success().stdout(predicate::eq(indoc!(
+            "
+            ^[ab]$
+              [+] a: matched
+              [+] b: matched
+            ^[ab]$
+              [+] a: matched
+              [+] b: matched
+              [-] c: not matched
+            ^b$
+              [+] b: matched
+              [-] c: not matched
+            "
+        )));
+    }
+
+    #[test]
+    fn succeeds_with_added_test_cases_after_removal_and_clearing() {
+        let mut grex = init_command();
+        grex.args(["--interactive", "a"]);
+        grex.write_stdin("b\n:remove a\nc\n:clear\nd\n");
+        grex.assert().success().stdout(predicate::eq(indoc!(
+            "
+            ^a$
+              [+] a: matched
+            ^[ab]$
+              [+] a: matched
+              [+] b: matched
+            ^b$
+              [+] b: matched
+            ^[bc]$
+              [+] b: matched
+              [+] c: matched
+            ^d$
+              [+] d: matched
+            "
+        )));
+    }
+
+    #[test]
+    fn succeeds_with_switched_options() {
+        let mut grex = init_command();
+        grex.args(["--interactive", "123"]);
+        grex.write_stdin(":digits on\n:anchors off\n:digits off\n");
+        grex.assert().success().stdout(predicate::eq(indoc!(
+            "
+            ^123$
+              [+] 123: matched
+            ^\\d\\d\\d$
+              [+] 123: matched
+            \\d\\d\\d
+              [+] 123: matched
+            123
+              [+] 123: matched
+            "
+        )));
+    }
+
+    #[test]
+    fn reports_invalid_commands_and_continues() {
+        let mut grex = init_command();
+        grex.args(["--interactive", "a"]);
+        grex.write_stdin(":foo on\n:bar\n:remove x\nb\n");
+        grex.assert()
+            .success()
+            .stdout(predicate::eq(indoc!(
+                "
+                ^a$
+                  [+] a: matched
+                ^[ab]$
+                  [+] a: matched
+                  [+] b: matched
+                "
+            )))
+            .stderr(predicate::eq(indoc!(
+                "
+                error: unknown option 'foo'
+                error: unknown command ':bar', type `:help` for a list of all commands
+                error: test case 'x' could not be found
+                "
+            )));
+    }
+
+    #[test]
+    fn fails_with_output_format_option() {
+        let mut grex = init_command();
+        grex.args(["--interactive", "--output-format", "json"]);
+        grex.assert().failure().stderr(predicate::str::contains(
+            "the argument '--interactive' cannot be used with '--output-format <FORMAT>'",
+        ));
+    }
+}
+
+mod input_formats {
+    use super::*;
+
+    #[test]
+    fn succeeds_with_nul_delimited_test_cases() {
+        let mut grex = init_command();
+        grex.write_stdin("a\nb\0c\0")
+            .args(["--input-format", "nul", "-"]);
+        grex.assert()
+            .success()
+            .stdout(predicate::eq("^(?:a\\nb|c)$\n"));
+    }
+
+    #[test]
+    fn succeeds_with_csv_column_by_name() {
+        let mut file = NamedTempFile::new().unwrap();
+        write!(
+            file,
+            "id,name\n1,\"Doe, Jane\"\n2,\"say \"\"hi\"\"\"\n\n3,\"multi\r\nline\"\n"
+        )
+        .unwrap();
+
+        let mut grex = init_command();
+        grex.args([
+            "--input-format",
+            "csv",
+            "--column",
+            "name",
+            "-f",
+            file.path().to_str().unwrap(),
+        ]);
+        grex.assert().success().stdout(predicate::eq(
+            "^(?:multi\\r\\nline|Doe, Jane|say \"hi\")$\n",
+        ));
+    }
+
+    #[test]
+    fn succeeds_with_csv_column_by_index() {
+        let mut grex = init_command();
+        grex.write_stdin("a,1\nb,22\n")
+            .args(["--input-format", "csv", "--column", "2", "-"]);
+        grex.assert()
+            .success()
+            .stdout(predicate::eq("^(?:22|1)$\n"));
+    }
+
+    #[test]
+    fn succeeds_with_csv_column_by_index_and_header() {
+        let mut grex = init_command();
+        grex.write_stdin("name,value\na,1\nb,22\n").args([
+            "--input-format",
+            "csv",
+            "--column",
+            "2",
+            "--header",
+            "-",
+        ]);
+        grex.assert()
+            .success()
+            .stdout(predicate::eq("^(?:22|1)$\n"));
+    }
+
+    #[test]
+    fn succeeds_with_tsv_first_column_and_header() {
+        let mut grex = init_command();
+        grex.write_stdin("name\tvalue\na\t1\nb\t2\n").args([
+            "--input-format",
+            "tsv",
+            "--header",
+            "-",
+        ]);
+        grex.assert().success().stdout(predicate::eq("^[ab]$\n"));
+    }
+
+    #[test]
+    fn succeeds_with_tsv_first_column() {
+        let mut grex = init_command();
+        grex.write_stdin("a b\t1\nc\t2\n")
+            .args(["--input-format", "tsv", "-"]);
+        grex.assert()
+            .success()
+            .stdout(predicate::eq("^(?:a b|c)$\n"));
+    }
+
+    #[test]
+    fn succeeds_with_json_array_of_strings() {
+        let mut grex = init_command();
+        grex.write_stdin(r#"["a\nb", "c", 42]"#)
+            .args(["--input-format", "json", "-"]);
+        grex.assert()
+            .success()
+            .stdout(predicate::eq("^(?:a\\nb|42|c)$\n"));
+    }
+
+    #[test]
+    fn succeeds_with_json_path() {
+        let mut grex = init_command();
+        grex.write_stdin(
+            r#"[{"user": {"emails": ["a@x.org"]}}, {"user": {"emails": ["b@x.org"]}}]"#,
+        )
+        .args([
+            "--input-format",
+            "json",
+            "--json-path",
+            "user.emails.0",
+            "-",
+        ]);
+        grex.assert()
+            .success()
+            .stdout(predicate::eq("^[ab]@x\\.org$\n"));
+    }
+
+    #[test]
+    fn succeeds_with_jsonl_and_json_path() {
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(
+            indoc!(
+                r#"
+            {"level": "info", "msg": "started"}
+
+            {"level": "warn", "msg": "stopped"}
+            "#
+            )
+            .as_bytes(),
+        )
+        .unwrap();
+
+        let mut grex = init_command();
+        grex.args([
+            "--input-format",
+            "jsonl",
+            "--json-path",
+            "msg",
+            "-f",
+            file.path().to_str().unwrap(),
+        ]);
+        grex.assert()
+            .success()
+            .stdout(predicate::eq("^st(?:art|opp)ed$\n"));
+    }
+
+    #[test]
+    fn succeeds_with_input_format_for_exclude_file() {
+        let mut file = NamedTempFile::new().unwrap();
+        write!(file, "c3\0d4").unwrap();
+
+        let mut grex = init_command();
+        grex.args([
+            "--words",
+            "--input-format",
+            "nul",
+            "--exclude-file",
+            file.path().to_str().unwrap(),
+            "a1",
+            "b2",
+        ]);
+        grex.assert().success().stdout(predicate::eq("^[ab]\\w$\n"));
+    }
+
+    #[test]
+    fn fails_with_unknown_csv_column() {
+        let mut grex = init_command();
+        grex.write_stdin("id,name\n1,a\n").args([
+            "--input-format",
+            "csv",
+            "--column",
+            "email",
+            "-",
+        ]);
+        grex.assert()
+            .failure()
+            .stdout(predicate::str::is_empty())
+            .stderr(predicate::eq(
+                "error: column 'email' could not be found in the header\n",
+            ));
+    }
+
+    #[test]
+    fn fails_with_missing_csv_column() {
+        let mut grex = init_command();
+        grex.write_stdin("a,1\nb\n")
+            .args(["--input-format", "csv", "--column", "2", "-"]);
+        grex.assert()
+            .failure()
+            .stdout(predicate::str::is_empty())
+            .stderr(predicate::eq("error: row in line 2 has no column 2\n"));
+    }
+
+    #[test]
+    fn fails_with_missing_json_value() {
+        let mut grex = init_command();
+        grex.write_stdin("{\"msg\": \"a\"}\n{\"message\": \"b\"}\n")
+            .args(["--input-format", "jsonl", "--json-path", "msg", "-"]);
+        grex.assert()
+            .failure()
+            .stdout(predicate::str::is_empty())
+            .stderr(predicate::eq(
+                "error: line 2 has no string or number at JSON path 'msg'\n",
+            ));
+    }
+
+    #[test]
+    fn fails_with_invalid_json() {
+        let mut grex = init_command();
+        grex.write_stdin("{\"a\": 1}")
+            .args(["--input-format", "json", "-"]);
+        grex.assert()
+            .failure()
+            .stdout(predicate::str::is_empty())
+            .stderr(predicate::eq("error: the JSON input is not an array\n"));
+    }
+
+    #[test]
+    fn fails_with_column_and_wrong_input_format() {
+        let mut grex = init_command();
+        grex.args(["--input-format", "json", "--column", "1", "a"]);
+        grex.assert()
+            .failure()
+            .stdout(predicate::str::is_empty())
+            .stderr(predicate::eq(
+                "error: --column can only be used with input format csv or tsv\n",
+            ));
+    }
+
+    #[test]
+    fn fails_with_header_and_wrong_input_format() {
+        let mut grex = init_command();
+        grex.args(["--header", "a"]);
+        grex.assert()
+            .failure()
+            .stdout(predicate::str::is_empty())
+            .stderr(predicate::eq(
+                "error: --header can only be used with input format csv or tsv\n",
+            ));
+    }
+}
+
+mod multiple_files {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn create_directory(files: &[(&str, &str)]) -> TempDir {
+        let directory = TempDir::new().unwrap();
+        for (file_name, content) in files {
+            let path = directory.path().join(file_name);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, content).unwrap();
+        }
+        directory
+    }
+
+    #[test]
+    fn succeeds_with_multiple_file_options() {
+        let directory = create_directory(&[("first.txt", "a\nb\n"), ("second.txt", "c\nd\n")]);
+        let path = directory.path();
+
+        let mut grex = init_command();
+        grex.args([
+            "-f",
+            path.join("first.txt").to_str().unwrap(),
+            "--file",
+            path.join("second.txt").to_str().unwrap(),
+            "x",
+        ]);
+        grex.assert().success().stdout(predicate::eq("^[a-dx]$\n"));
+    }
+
+    #[test]
+    fn succeeds_with_directory() {
+        let directory = create_directory(&[("a.txt", "a\n"), ("nested/b.txt", "b\nc")]);
+
+        let mut grex = init_command();
+        grex.args(["-f", directory.path().to_str().unwrap()]);
+        grex.assert().success().stdout(predicate::eq("^[a-c]$\n"));
+    }
+
+    #[test]
+    fn succeeds_with_glob_pattern() {
+        let directory = create_directory(&[("a.txt", "a\n"), ("b.txt", "b\n"), ("c.csv", "c\n")]);
+
+        let mut grex = init_command();
+        grex.args(["-f", directory.path().join("*.txt").to_str().unwrap()]);
+        grex.assert().success().stdout(predicate::eq("^[ab]$\n"));
+    }
+
+    #[test]
+    fn succeeds_with_whole_files() {
+        let directory = create_directory(&[("a.txt", "first\nline\n"), ("b.txt", "second\r\n")]);
+
+        let mut grex = init_command();
+        grex.args(["--whole-files", "-f", directory.path().to_str().unwrap()]);
+        grex.assert()
+            .success()
+            .stdout(predicate::eq("^(?:first\\nline|second)$\n"));
+    }
+
+    #[test]
+    fn succeeds_with_file_names_from_stdin() {
+        let directory = create_directory(&[("a.txt", "a\n"), ("b.txt", "b\n")]);
+        let path = directory.path();
+
+        let mut grex = init_command();
+        grex.write_stdin(format!(
+            "{}\n{}\n",
+            path.join("a.txt").display(),
+            path.join("b.txt").display()
+        ))
+        .args(["-f", "-"]);
+        grex.assert().success().stdout(predicate::eq("^[ab]$\n"));
+    }
+
+    #[test]
+    fn fails_when_glob_pattern_matches_no_files() {
+        let directory = create_directory(&[("a.txt", "a\n")]);
+        let pattern = directory.path().join("*.csv");
+
+        let mut grex = init_command();
+        grex.args(["-f", pattern.to_str().unwrap()]);
+        grex.assert()
+            .failure()
+            .stdout(predicate::str::is_empty())
+            .stderr(predicate::eq(format!(
+                "error: no files match the pattern '{}'\n",
+                pattern.display()
+            )));
+    }
+
+    #[test]
+    fn fails_with_empty_directory() {
+        let directory = create_directory(&[]);
+
+        let mut grex = init_command();
+        grex.args(["-f", directory.path().to_str().unwrap()]);
+        grex.assert()
+            .failure()
+            .stdout(predicate::str::is_empty())
+            .stderr(predicate::eq(
+                "error: No test cases have been provided for regular expression generation\n",
+            ));
+    }
+
+    #[test]
+    fn fails_when_one_of_multiple_files_does_not_exist() {
+        let directory = create_directory(&[("a.txt", "a\n")]);
+
+        let mut grex = init_command();
+        grex.args([
+            "-f",
+            directory.path().join("a.txt").to_str().unwrap(),
+            "-f",
+            "/path/to/non-existing/file",
+        ]);
+        grex.assert()
+            .failure()
+            .stdout(predicate::str::is_empty())
+            .stderr(predicate::eq(
+                "error: the specified file could not be found\n",
+            ));
+    }
+
+    #[test]
+    fn fails_with_whole_files_and_input_format() {
+        let mut grex = init_command();
+        grex.args(["--whole-files", "--input-format", "csv", "-f", "file.csv"]);
+        grex.assert().failure().stderr(predicate::str::contains(
+            "the argument '--whole-files' cannot be used with '--input-format <FORMAT>'",
+        ));
+    }
+}
+
+mod maximum_length {
+    use super::*;
+
+    #[test]
+    fn succeeds_without_generalization() {
+        let mut grex = init_command();
+        grex.args(["--max-length", "10", "abc", "abd"]);
+        grex.assert()
+            .success()
+            .stdout(predicate::eq("^ab[cd]$\n"))
+            .stderr(predicate::str::is_empty());
+    }
+
+    #[test]
+    fn succeeds_with_generalizations() {
+        let mut grex = init_command();
+        grex.args(["--max-length", "20", "a1", "b22", "c333", "d4444", "e55555"]);
+        grex.assert()
+            .success()
+            .stdout(predicate::eq("^\\w\\d{1,5}$\n"))
+            .stderr(predicate::eq(
+                "note: generalized to stay within 20 characters: repeated substrings converted to quantifiers, digits converted to \\d, word characters converted to \\w\n",
+            ));
+    }
+
+    #[test]
+    fn succeeds_with_generalizations_and_json_output_format() {
+        let mut grex = init_command();
+        grex.args([
+            "--output-format",
+            "json",
+            "--max-length",
+            "20",
+            "a1",
+            "b22",
+            "c333",
+            "d4444",
+            "e55555",
+        ]);
+        grex.assert()
+            .success()
+            .stdout(predicate::str::contains("\"maximum_length\":20"))
+            .stdout(predicate::str::contains(
+                "\"generalizations\":[\"repetitions\",\"digits\",\"words\"]",
+            ));
+    }
+
+    #[test]
+    fn fails_when_maximum_length_cannot_be_reached() {
+        let mut grex = init_command();
+        grex.args(["--max-length", "5", "abc", "def"]);
+        grex.assert()
+            .failure()
+            .stdout(predicate::str::is_empty())
+            .stderr(predicate::eq(
+                "error: The shortest regular expression found has 7 characters and exceeds the maximum length of 5\n",
+            ));
+    }
+
+    #[test]
+    fn fails_with_zero_maximum_length() {
+        let mut grex = init_command();
+        grex.args(["--max-length", "0", "abc"]);
+        grex.assert()
+            .failure()
+            .stderr(predicate::str::contains("Value must not be zero"));
+    }
+}
+
+mod explanation {
+    use super::*;
+
+    #[test]
+    fn succeeds_with_explain_option() {
+        let mut grex = init_command();
+        grex.args(["--explain", "--repetitions", "abcabc", "xyz", "a"]);
+        grex.assert().success().stdout(predicate::eq(indoc!(
+            r#"
+            ^(?:xyz|a|(?:abc){2})$
+
+            the start of the string
+              one of the following alternatives:
+                the text "xyz"
+                or
+                the character "a"
+                or
+                the text "abc", exactly 2 times
+            the end of the string
+            "#
+        )));
+    }
+
+    #[test]
+    fn fails_with_explain_option_and_output_format_option() {
+        let mut grex = init_command();
+        grex.args(["--explain", "--output-format", "json", "a"]);
+        grex.assert().failure().stderr(predicate::str::contains(
+            "the argument '--explain' cannot be used with '--output-format <FORMAT>'",
+        ));
+    }
+}
+
+mod verification {
+    use super::*;
+
+    #[test]
+    fn succeeds_with_matching_regex() {
+        let mut positives = NamedTempFile::new().unwrap();
+        writeln!(positives, "a\naa\naaa").unwrap();
+        let mut negatives = NamedTempFile::new().unwrap();
+        writeln!(negatives, "b\nab").unwrap();
+
+        let mut grex = init_command();
+        grex.args([
+            "verify",
+            "--regex",
+            "^a+$",
+            "--positives",
+            positives.path().to_str().unwrap(),
+            "--negatives",
+            negatives.path().to_str().unwrap(),
+        ]);
+        grex.assert()
+            .success()
+            .stdout(predicate::eq("all 5 test cases passed\n"));
+    }
+
+    #[test]
+    fn succeeds_with_positives_only() {
+        let mut positives = NamedTempFile::new().unwrap();
+        writeln!(positives, "1980\n2029").unwrap();
+
+        let mut grex = init_command();
+        grex.args([
+            "verify",
+            "--regex",
+            "^(?:19[89]\\d|20[0-2]\\d)$",
+            "--positives",
+            positives.path().to_str().unwrap(),
+        ]);
+        grex.assert()
+            .success()
+            .stdout(predicate::eq("all 2 test cases passed\n"));
+    }
+
+    #[test]
+    fn succeeds_with_unanchored_regex() {
+        let mut positives = NamedTempFile::new().unwrap();
+        writeln!(positives, "a\naa\nbab").unwrap();
+        let mut negatives = NamedTempFile::new().unwrap();
+        writeln!(negatives, "b").unwrap();
+
+        let mut grex = init_command();
+        grex.args([
+            "verify",
+            "--regex",
+            "a",
+            "--positives",
+            positives.path().to_str().unwrap(),
+            "--negatives",
+            negatives.path().to_str().unwrap(),
+        ]);
+        grex.assert()
+            .success()
+            .stdout(predicate::eq("all 4 test cases passed\n"));
+    }
+
+    #[test]
+    fn succeeds_with_verify_as_test_case_after_double_hyphen() {
+        let mut grex = init_command();
+        grex.args(["--", "verify", "verified"]);
+        grex.assert()
+            .success()
+            .stdout(predicate::eq("^verif(?:ied|y)$\n"));
+    }
+
+    #[test]
+    fn fails_with_mismatching_test_cases() {
+        let mut positives = NamedTempFile::new().unwrap();
+        writeln!(positives, "a\nb\naa").unwrap();
+        let mut negatives = NamedTempFile::new().unwrap();
+        writeln!(negatives, "c\naa").unwrap();
+        let positives_path = positives.path().to_str().unwrap();
+        let negatives_path = negatives.path().to_str().unwrap();
+
+        let mut grex = init_command();
+        grex.args([
+            "verify",
+            "--regex",
+            "^a{1,2}$",
+            "--positives",
+            positives_path,
+            "--negatives",
+            negatives_path,
+        ]);
+        grex.assert()
+            .failure()
+            .code(1)
+            .stdout(predicate::eq(format!(
+                "{}:2: test case is not matched: b\n{}:2: negative test case is matched: aa\n",
+                positives_path, negatives_path
+            )))
+            .stderr(predicate::eq("error: 2 of 5 test cases failed\n"));
+    }
+
+    #[test]
+    fn fails_with_invalid_regex() {
+        let mut positives = NamedTempFile::new().unwrap();
+        writeln!(positives, "a").unwrap();
+
+        let mut grex = init_command();
+        grex.args([
+            "verify",
+            "--regex",
+            "(a",
+            "--positives",
+            positives.path().to_str().unwrap(),
+        ]);
+        grex.assert()
+            .failure()
+            .stderr(predicate::str::contains("unclosed group"));
+    }
+
+    #[test]
+    fn fails_with_missing_file() {
+        let mut grex = init_command();
+        grex.args([
+            "verify",
+            "--regex",
+            "a",
+            "--positives",
+            "/tmp/missing-grex-test-file",
+        ]);
+        grex.assert().failure().stderr(predicate::eq(
+            "error: the specified file could not be found: /tmp/missing-grex-test-file\n",
+        ));
+    }
+
+    #[test]
+    fn fails_without_test_case_files() {
+        let mut grex = init_command();
+        grex.args(["verify", "--regex", "a"]);
+        grex.assert().failure().stderr(predicate::str::contains(
+            "the following required arguments were not provided",
+        ));
+    }
+}
+
+mod output_format {
+    use super::*;
+
+    #[test]
+    fn succeeds_with_json_output_format_option() {
+        let mut grex = init_command();
+        grex.args([
+            "--output-format",
+            "json",
+            "--dialect",
+            "pcre",
+            "a",
+            "aa",
+            "a",
+        ]);
+        grex.assert().success().stdout(predicate::eq(
+            "{\"regexp\":\"^aa?\\\\z\",\"config\":{\"dialect\":\"PCRE\",\"minimum_repetitions\":1,\
+            \"minimum_substring_length\":1,\"maximum_length\":null,\"is_digit_converted\":false,\"is_non_digit_converted\":false,\
+            \"is_space_converted\":false,\"is_non_space_converted\":false,\"is_word_converted\":false,\
+            \"is_non_word_converted\":false,\"is_general_category_converted\":false,\"is_script_converted\":false,\"is_repetition_converted\":false,\"is_numeric_range_converted\":false,\"is_positional_char_class_inferred\":false,\"positional_char_class_tolerance\":4,\
+            \"is_case_insensitive_matching\":false,\"is_case_insensitive_char_class_enabled\":false,\"is_capturing_group_enabled\":false,\
+            \"is_non_ascii_char_escaped\":false,\"is_astral_code_point_converted_to_surrogate\":false,\
+            \"is_verbose_mode_enabled\":false,\"is_start_anchor_disabled\":false,\
+            \"is_end_anchor_disabled\":false},\"test_case_count\":2,\"dfa_state_count\":3,\
+            \"minimized_dfa_state_count\":3,\"is_fallback_taken\":false,\"generalizations\":[]}\n",
+        ));
+    }
+
+    #[test]
+    fn succeeds_with_json_output_format_option_and_fallback() {
+        let mut grex = init_command();
+        grex.args([
+            "--output-format",
+            "json",
+            "--no-anchors",
+            "--words",
+            "--exclude",
+            "aa",
+            "1",
+            "1a",
+        ]);
+        grex.assert()
+            .success()
+            .stdout(predicate::str::contains("\"regexp\":\"(?:1a|1)\""))
+            .stdout(predicate::str::contains("\"is_fallback_taken\":true"));
+    }
+
+    #[test]
+    fn succeeds_with_json_output_format_option_and_verbose_mode() {
+        let mut grex = init_command();
+        grex.args(["--output-format", "json", "--verbose", "a", "b"]);
+        grex.assert().success().stdout(predicate::str::contains(
+            "\"regexp\":\"(?x)\\n^\\n  [ab]\\n$\"",
+        ));
+    }
+
+    #[test]
+    fn fails_with_json_output_format_option_and_colorize_option() {
+        let mut grex = init_command();
+        grex.args(["--output-format", "json", "--colorize", "a"]);
+        grex.assert().failure().stderr(predicate::str::contains(
+            "the argument '--output-format <FORMAT>' cannot be used with '--colorize'",
+        ));
+    }
+
+    #[test]
+    fn fails_with_unknown_output_format() {
+        let mut grex = init_command();
+        grex.args(["--output-format", "yaml", "a"]);
+        grex.assert()
+            .failure()
+            .stderr(predicate::str::contains("invalid value 'yaml'"));
+    }
+}
+
 mod anchor_conversion {
     use super::*;
 
@@ -15,8 +15,9 @@
  */
 
 #![cfg(not(target_family = "wasm"))]
+#![allow(clippy::invisible_characters)]
 
-use grex::RegExpBuilder;
+use grex::{Ast, CharClass, Dialect, Generalization, GrexError, RegExpBuilder, Report};
 use indoc::indoc;
 use regex::Regex;
 use rstest::rstest;
@@ -31,6 +32,8 @@ mod no_conversion {
 
         #[rstest(test_cases, expected_output,
             case(vec![""], "^$"),
+            case(vec!["", "a", "b"], "^[ab]?$"),
+            case(vec!["", "ab"], "^(?:ab)?$"),
             case(vec![" "], "^ $"),
             case(vec!["   "], "^   $"),
             case(vec!["["], "^\\[$"),
@@ -112,6 +115,8 @@ mod no_conversion {
 
         #[rstest(test_cases, expected_output,
             case(vec!["İ"], "(?i)^İ$"),
+            case(vec!["𖺥"], "(?i)^𖺥$"),
+            case(vec!["Ä𖺥", "ä𖺥"], "(?i)^[Ää]𖺥$"),
             case(vec!["ABC", "abc", "AbC", "aBc"], "(?i)^abc$"),
             case(vec!["ABC", "zBC", "abc", "AbC", "aBc"], "(?i)^[az]bc$"),
             case(vec!["Ä@Ö€Ü", "ä@ö€ü", "Ä@ö€Ü", "ä@Ö€ü"], "(?i)^ä@ö€ü$"),
@@ -1728,7 +1733,8 @@ mod non_word_conversion {
             case(
                 vec!["I   ♥♥♥ 36 and ٣ and y̆y̆ and 💩💩."],
                 "^I\\W\\W\\W\\W\\W\\W\\W36\\Wand\\W٣\\Wand\\Wy̆y̆\\Wand\\W\\W\\W\\W$"
-            )
+            ),
+            case(vec!["𐵐"], "^\\W$")
         )]
         fn succeeds(test_cases: Vec<&str>, expected_output: &str) {
             let regexp = RegExpBuilder::from(&test_cases)
@@ -2081,6 +2087,727 @@ mod word_non_word_conversion {
     }
 }
 
+mod numeric_range_conversion {
+    use super::*;
+
+    #[rstest(test_cases, expected_output,
+        case(vec!["1980", "2029", "1999"], "^(?:19[89][0-9]|20[0-2][0-9])$"),
+        case(vec!["5", "55"], "^(?:[1-4][0-9]|5[0-5]|[5-9])$"),
+        case(vec!["0", "255"], "^(?:1[0-9][0-9]|2[0-4][0-9]|25[0-5]|[1-9][0-9]|[0-9])$"),
+        case(vec!["10", "19"], "^1[0-9]$"),
+        case(vec!["v1.2", "v3.10"], "^v[1-3]\\.(?:10|[2-9])$"),
+        case(vec!["port 80", "port 8080", "host"], "^(?:port (?:[1-7][0-9][0-9][0-9]|80[0-7][0-9]|8080|[1-9][0-9][0-9]|[89][0-9])|host)$"),
+        case(vec!["a1", "b2"], "^(?:a1|b2)$"),
+        case(vec!["007", "010"], "^0(?:07|10)$")
+    )]
+    fn succeeds(test_cases: Vec<&str>, expected_output: &str) {
+        let regexp = RegExpBuilder::from(&test_cases)
+            .with_conversion_of_numeric_ranges()
+            .build();
+        assert_that_regexp_is_correct(regexp, expected_output, &test_cases);
+        assert_that_regexp_matches_test_cases(expected_output, test_cases);
+    }
+
+    #[rstest(test_cases, expected_output,
+        case(vec!["5", "55"], "(?:[1-4][0-9]|5[0-5]|[5-9])"),
+        case(vec!["id 8", "id 12"], "id (?:1[0-2]|[89])")
+    )]
+    fn succeeds_without_anchors(test_cases: Vec<&str>, expected_output: &str) {
+        let regexp = RegExpBuilder::from(&test_cases)
+            .with_conversion_of_numeric_ranges()
+            .without_anchors()
+            .build();
+        assert_that_regexp_is_correct(regexp, expected_output, &test_cases);
+    }
+
+    #[test]
+    fn succeeds_with_digit_class_in_dialect_with_ascii_digits_only() {
+        let regexp = RegExpBuilder::from(&["1980", "2029", "1999"])
+            .with_conversion_of_numeric_ranges()
+            .with_dialect(Dialect::Go)
+            .build();
+        assert_eq!(regexp, "^(?:19[89]\\d|20[0-2]\\d)$");
+    }
+
+    #[test]
+    fn fails_to_match_non_ascii_digits() {
+        let regexp = RegExpBuilder::from(&["10", "19"])
+            .with_conversion_of_numeric_ranges()
+            .build();
+        let regex = Regex::new(&regexp).unwrap();
+        assert!(!regex.is_match("1\u{664}"));
+    }
+
+    #[test]
+    fn succeeds_with_matching_of_numbers_within_range() {
+        let regexp = RegExpBuilder::from(&["1980", "2029"])
+            .with_conversion_of_numeric_ranges()
+            .build();
+        let regex = Regex::new(&regexp).unwrap();
+        assert!((1980..=2029).all(|number| regex.is_match(&number.to_string())));
+        assert!(!regex.is_match("1979"));
+        assert!(!regex.is_match("2030"));
+    }
+
+    #[test]
+    fn succeeds_with_negative_test_cases() {
+        let test_cases = vec!["1", "9"];
+        let negative_test_cases = vec!["5"];
+        let regexp = RegExpBuilder::from(&test_cases)
+            .with_conversion_of_numeric_ranges()
+            .with_negative_examples(&negative_test_cases)
+            .build();
+        assert_that_regexp_is_correct(regexp.clone(), "^[19]$", &test_cases);
+        assert_that_regexp_does_not_match_negative_test_cases(&regexp, negative_test_cases);
+    }
+}
+
+mod unicode_property_conversion {
+    use super::*;
+
+    #[rstest(test_cases, expected_output,
+        case(vec!["Ab1"], "^\\p{Lu}\\p{Ll}\\p{Nd}$"),
+        case(vec!["Ab", "Xy"], "^\\p{Lu}\\p{Ll}$"),
+        case(vec!["a-b", "c_d"], "^\\p{Ll}(?:\\p{Pd}|\\p{Pc})\\p{Ll}$"),
+        case(vec!["(a)"], "^\\p{Ps}\\p{Ll}\\p{Pe}$"),
+        case(vec!["1 €"], "^\\p{Nd}\\p{Zs}\\p{Sc}$"),
+        case(vec!["Αθήνα", "Μόσχα"], "^\\p{Lu}\\p{Ll}\\p{Ll}\\p{Ll}\\p{Ll}$"),
+        case(vec!["a\tb"], "^\\p{Ll}\\t\\p{Ll}$")
+    )]
+    fn succeeds_with_general_categories(test_cases: Vec<&str>, expected_output: &str) {
+        let regexp = RegExpBuilder::from(&test_cases)
+            .with_conversion_of_general_categories()
+            .build();
+        assert_that_regexp_is_correct(regexp, expected_output, &test_cases);
+        assert_that_regexp_matches_test_cases(expected_output, test_cases);
+    }
+
+    #[rstest(test_cases, expected_output,
+        case(vec!["αβ"], "^\\p{Greek}\\p{Greek}$"),
+        case(vec!["Москва"], "^\\p{Cyrillic}\\p{Cyrillic}\\p{Cyrillic}\\p{Cyrillic}\\p{Cyrillic}\\p{Cyrillic}$"),
+        case(vec!["漢字"], "^\\p{Han}\\p{Han}$"),
+        case(vec!["a1", "α2"], "^(?:\\p{Latin}1|\\p{Greek}2)$"),
+        case(vec!["a.b"], "^\\p{Latin}\\.\\p{Latin}$")
+    )]
+    fn succeeds_with_scripts(test_cases: Vec<&str>, expected_output: &str) {
+        let regexp = RegExpBuilder::from(&test_cases)
+            .with_conversion_of_scripts()
+            .build();
+        assert_that_regexp_is_correct(regexp, expected_output, &test_cases);
+        assert_that_regexp_matches_test_cases(expected_output, test_cases);
+    }
+
+    #[rstest(test_cases, expected_output,
+        case(vec!["αβγ", "абв"], "^(?:\\p{Greek}{3}|\\p{Cyrillic}{3})$"),
+        case(vec!["漢字漢字"], "^\\p{Han}{4}$")
+    )]
+    fn succeeds_with_repetitions(test_cases: Vec<&str>, expected_output: &str) {
+        let regexp = RegExpBuilder::from(&test_cases)
+            .with_conversion_of_scripts()
+            .with_conversion_of_repetitions()
+            .build();
+        assert_that_regexp_is_correct(regexp, expected_output, &test_cases);
+        assert_that_regexp_matches_test_cases(expected_output, test_cases);
+    }
+
+    #[test]
+    fn succeeds_with_precedence_of_digits_and_whitespace() {
+        let test_cases = vec!["A 1"];
+        let regexp = RegExpBuilder::from(&test_cases)
+            .with_conversion_of_digits()
+            .with_conversion_of_whitespace()
+            .with_conversion_of_words()
+            .with_conversion_of_general_categories()
+            .build();
+        assert_that_regexp_is_correct(regexp, "^\\p{Lu}\\s\\d$", &test_cases);
+    }
+
+    #[test]
+    fn succeeds_with_precedence_of_general_categories_over_scripts() {
+        let test_cases = vec!["Aα"];
+        let regexp = RegExpBuilder::from(&test_cases)
+            .with_conversion_of_general_categories()
+            .with_conversion_of_scripts()
+            .build();
+        assert_that_regexp_is_correct(regexp, "^\\p{Lu}\\p{Ll}$", &test_cases);
+    }
+
+    #[test]
+    fn succeeds_with_precedence_of_scripts_over_words() {
+        let test_cases = vec!["a1"];
+        let regexp = RegExpBuilder::from(&test_cases)
+            .with_conversion_of_scripts()
+            .with_conversion_of_words()
+            .build();
+        assert_that_regexp_is_correct(regexp, "^\\p{Latin}\\w$", &test_cases);
+    }
+
+    #[test]
+    fn succeeds_without_cased_letters_if_case_insensitive_matching() {
+        let test_cases = vec!["A!"];
+        let regexp = RegExpBuilder::from(&test_cases)
+            .with_conversion_of_general_categories()
+            .with_case_insensitive_matching()
+            .build();
+        assert_that_regexp_is_correct(regexp, "(?i)^a\\p{Po}$", &test_cases);
+    }
+
+    #[test]
+    fn succeeds_with_escape_option() {
+        let test_cases = vec!["ä{"];
+        let regexp = RegExpBuilder::from(&test_cases)
+            .with_conversion_of_scripts()
+            .with_escaping_of_non_ascii_chars(false)
+            .build();
+        assert_that_regexp_is_correct(regexp, "^\\p{Latin}\\{$", &test_cases);
+    }
+
+    #[test]
+    fn succeeds_with_negative_test_cases() {
+        let test_cases = vec!["Ab"];
+        let negative_test_cases = vec!["Xy"];
+        let regexp = RegExpBuilder::from(&test_cases)
+            .with_conversion_of_general_categories()
+            .with_negative_examples(&negative_test_cases)
+            .build();
+        assert_that_regexp_is_correct(regexp.clone(), "^A\\p{Ll}$", &test_cases);
+        assert_that_regexp_does_not_match_negative_test_cases(&regexp, negative_test_cases);
+    }
+
+    #[rstest(
+        dialect,
+        expected_output,
+        case(Dialect::Rust, "^\\p{Greek}$"),
+        case(Dialect::Pcre, "^\\p{Greek}\\z"),
+        case(Dialect::EcmaScript, "/^\\p{Script=Greek}$/u"),
+        case(Dialect::Go, "^\\p{Greek}$"),
+        case(Dialect::Java, "^\\p{IsGreek}\\z")
+    )]
+    fn succeeds_with_scripts_in_dialect(dialect: Dialect, expected_output: &str) {
+        let test_cases = vec!["α"];
+        let regexp = RegExpBuilder::from(&test_cases)
+            .with_conversion_of_scripts()
+            .with_dialect(dialect)
+            .build();
+        assert_eq!(regexp, expected_output);
+    }
+
+    #[rstest(
+        dialect,
+        is_script_converted,
+        expected_feature,
+        case(Dialect::Python, false, "Conversion of general categories"),
+        case(Dialect::PosixExtended, false, "Conversion of general categories"),
+        case(Dialect::Python, true, "Conversion of scripts"),
+        case(Dialect::DotNet, true, "Conversion of scripts"),
+        case(Dialect::PosixBasic, true, "Conversion of scripts")
+    )]
+    fn fails_with_unsupported_dialect(
+        dialect: Dialect,
+        is_script_converted: bool,
+        expected_feature: &str,
+    ) {
+        let mut builder = RegExpBuilder::from(&["a"]);
+        if is_script_converted {
+            builder.with_conversion_of_scripts();
+        } else {
+            builder.with_conversion_of_general_categories();
+        }
+        let error = builder.with_dialect(dialect).try_build().unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            format!(
+                "{} is not supported by the {} regex dialect",
+                expected_feature, dialect
+            )
+        );
+    }
+}
+
+mod positional_char_classes {
+    use super::*;
+
+    #[rstest(test_cases, expected_output,
+        case(vec!["a1x", "b2x", "c3x"], "^[a-c][1-3]x$"),
+        case(vec!["a1", "a2", "b1", "b2"], "^[ab][12]$"),
+        case(vec!["2023-01", "2024-02", "2025-03"], "^202[3-5]\\-0[1-3]$"),
+        case(vec!["a.b", "c-d"], "^[ac][\\-.][bd]$"),
+        case(vec!["ab", "xyz"], "^(?:xyz|ab)$"),
+        case(vec!["a", "aa", "aaa"], "^a(?:aa?)?$")
+    )]
+    fn succeeds(test_cases: Vec<&str>, expected_output: &str) {
+        let regexp = RegExpBuilder::from(&test_cases)
+            .with_positional_char_classes()
+            .build();
+        assert_that_regexp_is_correct(regexp, expected_output, &test_cases);
+        assert_that_regexp_matches_test_cases(expected_output, test_cases);
+    }
+
+    #[rstest(test_cases, tolerance, expected_output,
+        case(vec!["a1", "b2"], 1, "^(?:a1|b2)$"),
+        case(vec!["a1", "b2"], 2, "^[ab][12]$"),
+        case(vec!["ab", "cd", "ef", "gh"], 3, "^(?:ab|cd|ef|gh)$"),
+        case(vec!["ab", "cd", "ef", "gh"], 4, "^[aceg][bdfh]$")
+    )]
+    fn succeeds_with_tolerance(test_cases: Vec<&str>, tolerance: u32, expected_output: &str) {
+        let regexp = RegExpBuilder::from(&test_cases)
+            .with_positional_char_classes()
+            .with_positional_char_class_tolerance(tolerance)
+            .build();
+        assert_that_regexp_is_correct(regexp, expected_output, &test_cases);
+        assert_that_regexp_matches_test_cases(expected_output, test_cases);
+    }
+
+    #[test]
+    fn succeeds_with_conversion_of_digits() {
+        let test_cases = vec!["a1", "b2"];
+        let regexp = RegExpBuilder::from(&test_cases)
+            .with_positional_char_classes()
+            .with_conversion_of_digits()
+            .build();
+        assert_that_regexp_is_correct(regexp, "^[ab]\\d$", &test_cases);
+    }
+
+    #[rstest(test_cases, expected_output,
+        case(vec!["ä1", "ö2"], "^(?:\\u{e4}1|\\u{f6}2)$"),
+        case(vec!["äa1", "äb2"], "^\\u{e4}[ab][12]$")
+    )]
+    fn succeeds_with_escape_option(test_cases: Vec<&str>, expected_output: &str) {
+        let regexp = RegExpBuilder::from(&test_cases)
+            .with_positional_char_classes()
+            .with_escaping_of_non_ascii_chars(false)
+            .build();
+        assert_that_regexp_is_correct(regexp, expected_output, &test_cases);
+    }
+
+    #[test]
+    fn succeeds_with_negative_test_cases() {
+        let test_cases = vec!["a1", "b2"];
+        let negative_test_cases = vec!["a2"];
+        let regexp = RegExpBuilder::from(&test_cases)
+            .with_positional_char_classes()
+            .with_negative_examples(&negative_test_cases)
+            .build();
+        assert_that_regexp_is_correct(regexp.clone(), "^(?:a1|b2)$", &test_cases);
+        assert_that_regexp_does_not_match_negative_test_cases(&regexp, negative_test_cases);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Tolerance of positional character classes must be greater than zero"
+    )]
+    fn fails_with_zero_tolerance() {
+        RegExpBuilder::from(&["a"]).with_positional_char_class_tolerance(0);
+    }
+}
+
+mod case_insensitive_char_classes {
+    use super::*;
+
+    #[rstest(test_cases, expected_output,
+        case(vec!["Hello", "hello"], "^[hH]ello$"),
+        case(vec!["Hello", "hello", "world"], "^(?:[hH]ello|world)$"),
+        case(vec!["Hello", "hello", "hElLo"], "^[hH][eE]l[lL]o$"),
+        case(vec!["ab", "aB", "Ab", "AB"], "^[aA][bB]$"),
+        case(vec!["Hello", "World"], "^(?:Hello|World)$"),
+        case(vec!["Hello", "hallo"], "^(?:He|ha)llo$")
+    )]
+    fn succeeds(test_cases: Vec<&str>, expected_output: &str) {
+        let regexp = RegExpBuilder::from(&test_cases)
+            .with_case_insensitive_char_classes()
+            .build();
+        assert_that_regexp_is_correct(regexp, expected_output, &test_cases);
+        assert_that_regexp_matches_test_cases(expected_output, test_cases);
+    }
+
+    #[rstest(test_cases, expected_output,
+        case(vec!["aaa", "AAA"], "^[aA]{3}$"),
+        case(vec!["hoho", "HoHo"], "^(?:[hH]o){2}$"),
+        case(vec!["hoho", "HoHo", "hohoho", "HoHoHo"], "^(?:[hH]o){2,3}$")
+    )]
+    fn succeeds_with_conversion_of_repetitions(test_cases: Vec<&str>, expected_output: &str) {
+        let regexp = RegExpBuilder::from(&test_cases)
+            .with_case_insensitive_char_classes()
+            .with_conversion_of_repetitions()
+            .build();
+        assert_that_regexp_is_correct(regexp, expected_output, &test_cases);
+        assert_that_regexp_matches_test_cases(expected_output, test_cases);
+    }
+
+    #[rstest(test_cases, expected_output,
+        case(vec!["äb", "Äb"], "^[\\u{e4}\\u{c4}]b$"),
+        case(vec!["ääää", "ÄÄÄÄ"], "^[\\u{e4}\\u{c4}]{4}$"),
+        case(vec!["äbäb", "ÄbÄb"], "^(?:[\\u{e4}\\u{c4}]b){2}$"),
+        case(vec!["äbäb", "ÄbÄb", "äbäbäb", "ÄbÄbÄb"], "^(?:[\\u{e4}\\u{c4}]b){2,3}$")
+    )]
+    fn succeeds_with_escaping_of_non_ascii_chars(test_cases: Vec<&str>, expected_output: &str) {
+        let regexp = RegExpBuilder::from(&test_cases)
+            .with_case_insensitive_char_classes()
+            .with_conversion_of_repetitions()
+            .with_escaping_of_non_ascii_chars(false)
+            .build();
+        assert_that_regexp_is_correct(regexp, expected_output, &test_cases);
+    }
+
+    #[test]
+    fn prefers_case_insensitive_matching() {
+        let test_cases = vec!["Hello", "hello"];
+        let regexp = RegExpBuilder::from(&test_cases)
+            .with_case_insensitive_char_classes()
+            .with_case_insensitive_matching()
+            .build();
+        assert_that_regexp_is_correct(regexp, "(?i)^hello$", &test_cases);
+    }
+}
+
+mod incremental_builder {
+    use super::*;
+
+    #[rstest(test_cases, inserted_test_cases, removed_test_cases, expected_output,
+        case(vec!["a"], vec!["b", "c"], vec![], "^[a-c]$"),
+        case(vec!["abc"], vec!["abd", "x"], vec![], "^(?:ab[cd]|x)$"),
+        case(vec!["a", "b", "c"], vec![], vec!["b"], "^[ac]$"),
+        case(vec!["hello", "help"], vec!["world", "word"], vec!["hello"], "^(?:worl?d|help)$"),
+        case(vec!["2023-01"], vec!["2024-02", "2025-03"], vec![], "^202(?:3\\-01|4\\-02|5\\-03)$"),
+        case(vec!["a"], vec!["b", "a"], vec!["b", "x"], "^a$")
+    )]
+    fn succeeds(
+        test_cases: Vec<&str>,
+        inserted_test_cases: Vec<&str>,
+        removed_test_cases: Vec<&str>,
+        expected_output: &str,
+    ) {
+        let mut builder = RegExpBuilder::from(&test_cases).to_incremental();
+        for test_case in inserted_test_cases.iter() {
+            builder.insert(*test_case);
+        }
+        for test_case in removed_test_cases.iter() {
+            builder.remove(test_case);
+        }
+        let regexp = builder.build();
+        let remaining_test_cases = test_cases
+            .iter()
+            .chain(inserted_test_cases.iter())
+            .filter(|it| !removed_test_cases.contains(it))
+            .copied()
+            .collect::<Vec<_>>();
+        assert_that_regexp_is_correct(regexp, expected_output, &remaining_test_cases);
+        assert_that_regexp_matches_test_cases(expected_output, remaining_test_cases);
+    }
+
+    #[rstest(test_cases,
+        case(vec!["", "a", "b"]),
+        case(vec!["", "ab"]),
+        case(vec!["a", "aa", "aaa", ""]),
+        case(vec!["abc", "abd", "x"]),
+        case(vec!["hello", "help", "world", "word"])
+    )]
+    fn succeeds_with_same_output_as_batch_builder(test_cases: Vec<&str>) {
+        let mut builder = RegExpBuilder::from(&test_cases[..1]).to_incremental();
+        for test_case in test_cases[1..].iter() {
+            builder.insert(*test_case);
+        }
+        assert_eq!(builder.build(), RegExpBuilder::from(&test_cases).build());
+    }
+
+    #[test]
+    fn succeeds_with_conversion_of_digits() {
+        let mut builder = RegExpBuilder::from(&["a1"])
+            .with_conversion_of_digits()
+            .to_incremental();
+        builder.insert("a2");
+        builder.insert("b3");
+        assert_eq!(builder.build(), "^[ab]\\d$");
+    }
+
+    #[test]
+    fn succeeds_with_case_insensitive_matching() {
+        let mut builder = RegExpBuilder::from(&["abc"])
+            .with_case_insensitive_matching()
+            .to_incremental();
+        builder.insert("ABC");
+        builder.insert("abd");
+        assert_eq!(builder.build(), "(?i)^ab[cd]$");
+
+        builder.remove("abc");
+        assert_eq!(builder.build(), "(?i)^ab[cd]$");
+
+        builder.remove("ABC");
+        assert_eq!(builder.build(), "(?i)^abd$");
+    }
+
+    #[test]
+    fn succeeds_with_negative_test_cases() {
+        let mut builder = RegExpBuilder::from(&["a1"])
+            .with_conversion_of_digits()
+            .with_negative_examples(&["a3"])
+            .to_incremental();
+        builder.insert("a2");
+        let regexp = builder.build();
+        assert_that_regexp_is_correct(regexp.clone(), "^a[12]$", &["a1", "a2"]);
+        assert_that_regexp_does_not_match_negative_test_cases(&regexp, vec!["a3"]);
+    }
+
+    #[test]
+    fn succeeds_with_maximum_length() {
+        let mut builder = RegExpBuilder::from(&["a1", "b22"])
+            .with_max_length(20)
+            .to_incremental();
+        builder.insert("c333");
+        builder.insert("d4444");
+        builder.insert("e55555");
+        assert_eq!(builder.build(), "^\\w\\d{1,5}$");
+    }
+
+    #[test]
+    fn fails_with_unreachable_maximum_length() {
+        let mut batch_builder = RegExpBuilder::from(&["a1", "b22", "c333"]);
+        batch_builder.with_max_length(3);
+        let result = batch_builder.to_incremental().try_build();
+        assert!(result.is_err());
+        assert_eq!(result, batch_builder.try_build());
+    }
+
+    #[test]
+    fn returns_whether_test_case_has_been_changed() {
+        let mut builder = RegExpBuilder::from(&["a"]).to_incremental();
+        assert!(builder.insert("b"));
+        assert!(!builder.insert("b"));
+        assert_eq!(builder.len(), 2);
+        assert!(builder.remove("a"));
+        assert!(!builder.remove("a"));
+        assert_eq!(builder.len(), 1);
+    }
+
+    #[test]
+    fn fails_after_removing_all_test_cases() {
+        let mut builder = RegExpBuilder::from(&["a"]).to_incremental();
+        builder.remove("a");
+        assert!(builder.is_empty());
+        assert_eq!(builder.try_build(), Err(GrexError::MissingTestCases));
+    }
+}
+
+mod streaming_input {
+    use super::*;
+    use std::io::Cursor;
+
+    #[test]
+    fn succeeds_with_from_iter() {
+        let test_cases = ["a", "aa", "aaa"].iter().map(|it| it.to_string());
+        let regexp = RegExpBuilder::from_iter(test_cases).build();
+        assert_eq!(regexp, "^a(?:aa?)?$");
+    }
+
+    #[test]
+    fn succeeds_with_from_iter_and_duplicate_test_cases() {
+        let test_cases = (0..10_000).map(|it| if it % 2 == 0 { "ab" } else { "cd" });
+        let mut builder = RegExpBuilder::from_iter(test_cases);
+        assert_eq!(builder.build(), "^(?:ab|cd)$");
+        assert_eq!(builder.build_report().test_case_count(), 2);
+    }
+
+    #[test]
+    fn succeeds_with_from_iter_and_settings() {
+        let regexp = RegExpBuilder::from_iter(vec!["a1", "b22", "c333"])
+            .with_conversion_of_digits()
+            .with_conversion_of_words()
+            .build();
+        assert_eq!(regexp, "^\\w\\d(?:\\d(?:\\d)?)?$");
+    }
+
+    #[test]
+    fn succeeds_with_from_reader() {
+        let reader = Cursor::new("a\naa\r\naaa\n");
+        let regexp = RegExpBuilder::from_reader(reader).build();
+        assert_eq!(regexp, "^a(?:aa?)?$");
+    }
+
+    #[test]
+    fn succeeds_with_from_reader_and_bare_carriage_return() {
+        let reader = Cursor::new("a\nb\r");
+        let regexp = RegExpBuilder::from_reader(reader).build();
+        assert_eq!(regexp, "^(?:b\\r|a)$");
+    }
+
+    #[test]
+    fn succeeds_with_from_reader_and_empty_input() {
+        let regexp = RegExpBuilder::from_reader(Cursor::new("")).build();
+        assert_eq!(regexp, "^$");
+    }
+
+    #[test]
+    fn fails_with_try_from_iter_and_empty_iterator() {
+        let result = RegExpBuilder::try_from_iter(Vec::<String>::new());
+        assert_eq!(result.err(), Some(GrexError::MissingTestCases));
+    }
+
+    #[test]
+    fn fails_with_try_from_reader_and_empty_input() {
+        let result = RegExpBuilder::try_from_reader(Cursor::new(""));
+        assert_eq!(result.err(), Some(GrexError::MissingTestCases));
+    }
+
+    #[test]
+    fn fails_with_try_from_reader_and_invalid_utf8() {
+        let reader = Cursor::new(b"a\r\nb\n\xffc\nd".to_vec());
+        let result = RegExpBuilder::try_from_reader(reader);
+        assert_eq!(
+            result.err(),
+            Some(GrexError::InvalidUtf8 { line_number: 3 })
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "No test cases have been provided for regular expression generation")]
+    fn panics_with_from_iter_and_empty_iterator() {
+        RegExpBuilder::from_iter(Vec::<&str>::new());
+    }
+}
+
+mod maximum_length {
+    use super::*;
+
+    fn create_test_cases() -> Vec<String> {
+        (0..100)
+            .map(|n| format!("id-{}-{}", n * 37 % 1000, ["a", "bc", "def"][n % 3]))
+            .collect()
+    }
+
+    #[test]
+    fn succeeds_without_generalization_if_within_maximum_length() {
+        let mut builder = RegExpBuilder::from(&["abc", "abd"]);
+        builder.with_max_length(10);
+        let report = builder.build_report();
+        assert_eq!(report.regexp(), "^ab[cd]$");
+        assert!(report.generalizations().is_empty());
+    }
+
+    #[test]
+    fn succeeds_with_generalizations() {
+        let test_cases = create_test_cases();
+        let exact_regexp = RegExpBuilder::from(&test_cases).build();
+        assert!(exact_regexp.chars().count() > 100);
+
+        let report = RegExpBuilder::from(&test_cases)
+            .with_max_length(100)
+            .build_report();
+        assert!(report.regexp().chars().count() <= 100);
+        assert!(!report.generalizations().is_empty());
+        assert_that_regexp_matches_test_cases(
+            report.regexp(),
+            test_cases.iter().map(|it| it.as_str()).collect(),
+        );
+    }
+
+    #[test]
+    fn succeeds_with_generalizations_in_report() {
+        let report = RegExpBuilder::from(&["a1", "b22", "c333", "d4444", "e55555"])
+            .with_max_length(20)
+            .build_report();
+        assert_eq!(report.regexp(), "^\\w\\d{1,5}$");
+        assert_eq!(
+            report.generalizations(),
+            &[
+                Generalization::Repetitions,
+                Generalization::Digits,
+                Generalization::Words
+            ]
+        );
+        assert!(report
+            .to_json()
+            .ends_with("\"generalizations\":[\"repetitions\",\"digits\",\"words\"]}"));
+    }
+
+    #[test]
+    fn succeeds_without_generalizations_which_lengthen_the_expression() {
+        let report = RegExpBuilder::from(&["xx", "yy"])
+            .with_max_length(10)
+            .build_report();
+        assert_eq!(report.regexp(), "^[xy][xy]$");
+        assert_eq!(
+            report.generalizations(),
+            &[Generalization::PositionalCharClasses]
+        );
+    }
+
+    #[test]
+    fn succeeds_with_generalizations_which_are_already_set() {
+        let report = RegExpBuilder::from(&["a1", "b22", "c333", "d4444", "e55555"])
+            .with_conversion_of_digits()
+            .with_conversion_of_repetitions()
+            .with_max_length(20)
+            .build_report();
+        assert_eq!(report.regexp(), "^\\w\\d{1,5}$");
+        assert_eq!(report.generalizations(), &[Generalization::Words]);
+    }
+
+    #[test]
+    fn succeeds_with_syntax_highlighting_not_counted() {
+        let regexp = RegExpBuilder::from(&["a1", "b22", "c333", "d4444", "e55555"])
+            .with_syntax_highlighting()
+            .with_max_length(12)
+            .build();
+        assert!(regexp.chars().count() > 12);
+        assert!(regexp.contains("\\d"));
+    }
+
+    #[test]
+    fn fails_if_maximum_length_cannot_be_reached() {
+        let result = RegExpBuilder::from(&["abc", "def"])
+            .with_max_length(5)
+            .try_build();
+        assert_eq!(
+            result,
+            Err(GrexError::MaximumLengthExceeded {
+                length: 7,
+                maximum_length: 5
+            })
+        );
+    }
+
+    #[test]
+    fn fails_with_zero_maximum_length() {
+        let mut builder = RegExpBuilder::from(&["a"]);
+        let result = builder.try_with_max_length(0);
+        assert_eq!(result.err(), Some(GrexError::InvalidMaximumLength));
+    }
+}
+
+mod thread_safety {
+    use super::*;
+    use std::thread;
+
+    fn assert_send_and_sync<T: Send + Sync>() {}
+
+    #[test]
+    fn builder_and_results_are_send_and_sync() {
+        assert_send_and_sync::<RegExpBuilder>();
+        assert_send_and_sync::<Report>();
+        assert_send_and_sync::<GrexError>();
+    }
+
+    #[test]
+    fn succeeds_with_builders_moved_to_other_threads() {
+        let builders = (0..8)
+            .map(|n| RegExpBuilder::from(&[format!("{}a", n), format!("{}b", n)]))
+            .collect::<Vec<_>>();
+
+        let regexps = thread::scope(|scope| {
+            builders
+                .into_iter()
+                .map(|mut builder| scope.spawn(move || builder.build()))
+                .collect::<Vec<_>>()
+                .into_iter()
+                .map(|handle| handle.join().unwrap())
+                .collect::<Vec<_>>()
+        });
+
+        let expected_regexps = (0..8).map(|n| format!("^{}[ab]$", n)).collect::<Vec<_>>();
+        assert_eq!(regexps, expected_regexps);
+    }
+}
+
 mod anchor_conversion {
     use super::*;
 
@@ -2203,6 +2930,792 @@ mod anchor_conversion {
     }
 }
 
+mod negative_test_cases {
+    use super::*;
+
+    #[rstest(test_cases, negative_test_cases, expected_output,
+        case(vec!["a1", "b2"], vec!["c3"], "^[ab]\\w$"),
+        case(vec!["abc", "abd"], vec!["abx"], "^\\w\\w[cd]$"),
+        case(vec!["abc", "abd"], vec!["xyz", "abx"], "^a\\w[cd]$"),
+        case(vec!["abc", "abd"], vec!["abcd"], "^\\w\\w\\w$"),
+    )]
+    fn succeeds_with_word_conversion(
+        test_cases: Vec<&str>,
+        negative_test_cases: Vec<&str>,
+        expected_output: &str,
+    ) {
+        let regexp = RegExpBuilder::from(&test_cases)
+            .with_negative_examples(&negative_test_cases)
+            .with_conversion_of_words()
+            .build();
+        assert_that_regexp_is_correct(regexp, expected_output, &test_cases);
+        assert_that_regexp_matches_test_cases(expected_output, test_cases);
+        assert_that_regexp_does_not_match_negative_test_cases(expected_output, negative_test_cases);
+    }
+
+    #[rstest(test_cases, negative_test_cases, expected_output,
+        case(vec!["a1", "b2"], vec!["ab"], "^\\w\\d$"),
+        case(vec!["a1", "b2"], vec!["a3"], "^(?:\\w1|b\\d)$"),
+    )]
+    fn succeeds_with_digit_and_word_conversion(
+        test_cases: Vec<&str>,
+        negative_test_cases: Vec<&str>,
+        expected_output: &str,
+    ) {
+        let regexp = RegExpBuilder::from(&test_cases)
+            .with_negative_examples(&negative_test_cases)
+            .with_conversion_of_digits()
+            .with_conversion_of_words()
+            .build();
+        assert_that_regexp_is_correct(regexp, expected_output, &test_cases);
+        assert_that_regexp_matches_test_cases(expected_output, test_cases);
+        assert_that_regexp_does_not_match_negative_test_cases(expected_output, negative_test_cases);
+    }
+
+    #[rstest(test_cases, negative_test_cases, expected_output,
+        case(vec!["ABC", "abd"], vec!["xbc"], "(?i)^a\\w\\w$"),
+        case(vec!["ABC", "abd"], vec!["XBC"], "(?i)^a\\w\\w$"),
+    )]
+    fn succeeds_with_word_conversion_and_ignore_case_option(
+        test_cases: Vec<&str>,
+        negative_test_cases: Vec<&str>,
+        expected_output: &str,
+    ) {
+        let regexp = RegExpBuilder::from(&test_cases)
+            .with_negative_examples(&negative_test_cases)
+            .with_conversion_of_words()
+            .with_case_insensitive_matching()
+            .build();
+        assert_that_regexp_is_correct(regexp, expected_output, &test_cases);
+        assert_that_regexp_matches_test_cases(expected_output, test_cases);
+        assert_that_regexp_does_not_match_negative_test_cases(expected_output, negative_test_cases);
+    }
+
+    #[rstest(test_cases, negative_test_cases, expected_output,
+        case(vec!["aab", "aaac"], vec![], "^a{2,3}[bc]$"),
+        case(vec!["aab", "aaac"], vec!["aac"], "^(?:a{2}b|a{3}c)$"),
+    )]
+    fn succeeds_with_repetition_conversion(
+        test_cases: Vec<&str>,
+        negative_test_cases: Vec<&str>,
+        expected_output: &str,
+    ) {
+        let regexp = RegExpBuilder::from(&test_cases)
+            .with_negative_examples(&negative_test_cases)
+            .with_conversion_of_repetitions()
+            .build();
+        assert_that_regexp_is_correct(regexp, expected_output, &test_cases);
+        assert_that_regexp_matches_test_cases(expected_output, test_cases);
+        assert_that_regexp_does_not_match_negative_test_cases(expected_output, negative_test_cases);
+    }
+
+    #[rstest(test_cases, negative_test_cases, expected_output,
+        case(vec!["a1", "b2"], vec!["zz1"], "(?:a1|b2)"),
+    )]
+    fn succeeds_with_word_conversion_and_without_anchors(
+        test_cases: Vec<&str>,
+        negative_test_cases: Vec<&str>,
+        expected_output: &str,
+    ) {
+        let regexp = RegExpBuilder::from(&test_cases)
+            .with_negative_examples(&negative_test_cases)
+            .with_conversion_of_words()
+            .without_anchors()
+            .build();
+        assert_that_regexp_is_correct(regexp, expected_output, &test_cases);
+        assert_that_regexp_matches_test_cases(expected_output, test_cases);
+        assert_that_regexp_does_not_match_negative_test_cases(expected_output, negative_test_cases);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Some negative test cases cannot be excluded because they are matched by the test cases themselves"
+    )]
+    fn fails_when_negative_test_case_equals_test_case() {
+        RegExpBuilder::from(&["abc", "def"])
+            .with_negative_examples(&["abc"])
+            .build();
+    }
+}
+
+mod fallible_api {
+    use super::*;
+
+    #[test]
+    fn succeeds_with_try_from() {
+        let regexp = RegExpBuilder::try_from(&["a", "aa", "aaa"])
+            .and_then(|mut builder| builder.try_build());
+        assert_eq!(regexp, Ok("^a(?:aa?)?$".to_string()));
+    }
+
+    #[test]
+    fn succeeds_with_try_from_file() {
+        let mut file = NamedTempFile::new().unwrap();
+        writeln!(file, "a\naa\r\naaa").unwrap();
+
+        let regexp =
+            RegExpBuilder::try_from_file(file.path()).and_then(|mut builder| builder.try_build());
+        assert_eq!(regexp, Ok("^a(?:aa?)?$".to_string()));
+    }
+
+    #[test]
+    fn fails_with_try_from_and_empty_test_cases() {
+        let test_cases: Vec<&str> = vec![];
+        let result = RegExpBuilder::try_from(&test_cases);
+        assert_eq!(result.err(), Some(GrexError::MissingTestCases));
+    }
+
+    #[test]
+    fn fails_with_try_from_file_and_non_existing_file() {
+        let result = RegExpBuilder::try_from_file("/path/to/non-existing/file");
+        assert_eq!(result.err(), Some(GrexError::FileNotFound));
+    }
+
+    #[test]
+    fn succeeds_with_from_file_and_empty_file() {
+        let file = NamedTempFile::new().unwrap();
+        let regexp = RegExpBuilder::from_file(file.path()).build();
+        assert_eq!(regexp, "^$");
+    }
+
+    #[test]
+    fn fails_with_try_from_file_and_empty_file() {
+        let file = NamedTempFile::new().unwrap();
+        let result = RegExpBuilder::try_from_file(file.path());
+        assert_eq!(result.err(), Some(GrexError::MissingTestCases));
+    }
+
+    #[test]
+    fn fails_with_try_from_file_and_invalid_utf8() {
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(b"a\nb\nc\xff\nd").unwrap();
+
+        let result = RegExpBuilder::try_from_file(file.path());
+        let error = result.err().unwrap();
+        assert_eq!(error, GrexError::InvalidUtf8 { line_number: 3 });
+        assert_eq!(
+            error.to_string(),
+            "The specified file's encoding is not valid UTF-8 in line 3"
+        );
+    }
+
+    #[test]
+    fn fails_with_zero_minimum_repetitions() {
+        let mut builder = RegExpBuilder::from(&["a"]);
+        let result = builder.try_with_minimum_repetitions(0);
+        assert_eq!(result.err(), Some(GrexError::InvalidMinimumRepetitions));
+    }
+
+    #[test]
+    fn fails_with_zero_minimum_substring_length() {
+        let mut builder = RegExpBuilder::from(&["a"]);
+        let result = builder.try_with_minimum_substring_length(0);
+        assert_eq!(result.err(), Some(GrexError::InvalidMinimumSubstringLength));
+    }
+
+    #[test]
+    fn fails_with_zero_positional_char_class_tolerance() {
+        let mut builder = RegExpBuilder::from(&["a"]);
+        let result = builder.try_with_positional_char_class_tolerance(0);
+        assert_eq!(
+            result.err(),
+            Some(GrexError::InvalidPositionalCharClassTolerance)
+        );
+    }
+
+    #[test]
+    fn fails_with_conflicting_negative_test_cases() {
+        let result = RegExpBuilder::from(&["abc", "def"])
+            .with_negative_examples(&["abc"])
+            .try_build();
+        assert_eq!(result, Err(GrexError::ConflictingTestCases));
+    }
+}
+
+mod dialects {
+    use super::*;
+
+    #[rstest(
+        dialect,
+        expected_output,
+        case(Dialect::Rust, "^(?:a\\-b/|\\u{e4}|\\u{1f4a9})$"),
+        case(Dialect::Pcre, "^(?:a\\-b/|\\x{e4}|\\x{1f4a9})\\z"),
+        case(Dialect::EcmaScript, "/^(?:a-b\\/|\\u00e4|\\u{1f4a9})$/u"),
+        case(Dialect::Python, "^(?:a\\-b/|\\u00e4|\\U0001f4a9)\\Z"),
+        case(Dialect::Go, "^(?:a\\-b/|\\x{e4}|\\x{1f4a9})$"),
+        case(Dialect::DotNet, "^(?:a\\-b/|\\u00e4|\\ud83d\\udca9)\\z"),
+        case(Dialect::Java, "^(?:a\\-b/|\\u00e4|\\x{1f4a9})\\z")
+    )]
+    fn succeeds_with_escape_sequences(dialect: Dialect, expected_output: &str) {
+        let test_cases = vec!["a-b/", "ä", "💩"];
+        let regexp = RegExpBuilder::from(&test_cases)
+            .with_escaping_of_non_ascii_chars(false)
+            .with_dialect(dialect)
+            .build();
+        assert_that_regexp_is_correct(regexp, expected_output, &test_cases);
+    }
+
+    #[rstest(
+        dialect,
+        expected_output,
+        case(Dialect::Rust, "(?i)^[ab]$"),
+        case(Dialect::Pcre, "(?i)^[ab]\\z"),
+        case(Dialect::EcmaScript, "/^[ab]$/iu"),
+        case(Dialect::Python, "(?i)^[ab]\\Z"),
+        case(Dialect::Go, "(?i)^[ab]$"),
+        case(Dialect::DotNet, "(?i)^[ab]\\z"),
+        case(Dialect::Java, "(?iu)^[ab]\\z")
+    )]
+    fn succeeds_with_case_insensitive_matching(dialect: Dialect, expected_output: &str) {
+        let test_cases = vec!["a", "B"];
+        let regexp = RegExpBuilder::from(&test_cases)
+            .with_case_insensitive_matching()
+            .with_dialect(dialect)
+            .build();
+        assert_that_regexp_is_correct(regexp, expected_output, &test_cases);
+    }
+
+    #[rstest(dialect, expected_output,
+        case(Dialect::Pcre, indoc!(
+            r#"
+            (?x)
+            ^
+              (?:
+                a\ b
+                |
+                c\x0b
+              )
+            \z"#
+        )),
+        case(Dialect::Java, indoc!(
+            r#"
+            (?x)
+            ^
+              (?:
+                a\ b
+                |
+                c\x0b
+              )
+            \z"#
+        )),
+    )]
+    fn succeeds_with_verbose_mode(dialect: Dialect, expected_output: &str) {
+        let test_cases = vec!["a b", "c\u{b}"];
+        let regexp = RegExpBuilder::from(&test_cases)
+            .with_verbose_mode()
+            .with_dialect(dialect)
+            .build();
+        assert_that_regexp_is_correct(regexp, expected_output, &test_cases);
+    }
+
+    #[rstest(
+        dialect,
+        expected_output,
+        case(Dialect::Rust, "^\\d$"),
+        case(Dialect::Pcre, "^(?:\\d|٣)\\z"),
+        case(Dialect::EcmaScript, "/^(?:\\d|٣)$/u"),
+        case(Dialect::Python, "^\\d\\Z"),
+        case(Dialect::Go, "^(?:\\d|٣)$"),
+        case(Dialect::DotNet, "^\\d\\z"),
+        case(Dialect::Java, "^(?:\\d|٣)\\z")
+    )]
+    fn succeeds_with_digit_conversion(dialect: Dialect, expected_output: &str) {
+        let test_cases = vec!["1", "٣"];
+        let regexp = RegExpBuilder::from(&test_cases)
+            .with_conversion_of_digits()
+            .with_dialect(dialect)
+            .build();
+        assert_that_regexp_is_correct(regexp, expected_output, &test_cases);
+    }
+
+    #[rstest(
+        dialect,
+        expected_output,
+        case(Dialect::Rust, "^\\s$"),
+        case(Dialect::Pcre, "^\\s\\z"),
+        case(Dialect::Go, "^(?:\\v|\\s)$")
+    )]
+    fn succeeds_with_whitespace_conversion(dialect: Dialect, expected_output: &str) {
+        let test_cases = vec![" ", "\u{b}"];
+        let regexp = RegExpBuilder::from(&test_cases)
+            .with_conversion_of_whitespace()
+            .with_dialect(dialect)
+            .build();
+        assert_that_regexp_is_correct(regexp, expected_output, &test_cases);
+    }
+
+    #[rstest(
+        dialect,
+        expected_output,
+        case(Dialect::Rust, "^\\wb$"),
+        case(Dialect::Java, "^\\w\\w\\z")
+    )]
+    fn succeeds_with_word_conversion_and_negative_test_cases(
+        dialect: Dialect,
+        expected_output: &str,
+    ) {
+        let test_cases = vec!["ab"];
+        let regexp = RegExpBuilder::from(&test_cases)
+            .with_negative_examples(&["aé"])
+            .with_conversion_of_words()
+            .with_dialect(dialect)
+            .build();
+        assert_that_regexp_is_correct(regexp, expected_output, &test_cases);
+    }
+
+    #[rstest(test_cases, expected_output,
+        case(vec!["a", "b", "c", "]", "^", "-", "\\"], "^[]\\a-c^-]$"),
+        case(vec!["^", "-"], "^[-^]$"),
+        case(vec!["a\tb", "a\nb"], "^a[\t\n]b$"),
+        case(vec!["a+?", "a.*{}|"], "^a(\\.\\*\\{}\\||\\+\\?)$"),
+        case(vec!["(a)", "(b)"], "^\\([ab]\\)$"),
+    )]
+    fn succeeds_with_posix_extended_dialect(test_cases: Vec<&str>, expected_output: &str) {
+        let regexp = RegExpBuilder::from(&test_cases)
+            .with_dialect(Dialect::PosixExtended)
+            .build();
+        assert_that_regexp_is_correct(regexp, expected_output, &test_cases);
+    }
+
+    #[rstest(test_cases, expected_output,
+        case(vec!["a1", "b22", "(c)"], "^(\\(c\\)|a[[:digit:]]|b[[:digit:]]{2})$"),
+        case(vec!["1", "٣"], "^([[:digit:]]|٣)$"),
+    )]
+    fn succeeds_with_posix_extended_dialect_and_digit_conversion(
+        test_cases: Vec<&str>,
+        expected_output: &str,
+    ) {
+        let regexp = RegExpBuilder::from(&test_cases)
+            .with_conversion_of_digits()
+            .with_conversion_of_repetitions()
+            .with_dialect(Dialect::PosixExtended)
+            .build();
+        assert_that_regexp_is_correct(regexp, expected_output, &test_cases);
+    }
+
+    #[rstest(test_cases, expected_output,
+        case(vec!["a b"], "^[[:alnum:]_][^[:alnum:]_][[:alnum:]_]$"),
+        case(vec!["a\u{b}"], "^[[:alnum:]_][^[:alnum:]_]$"),
+    )]
+    fn succeeds_with_posix_extended_dialect_and_word_conversion(
+        test_cases: Vec<&str>,
+        expected_output: &str,
+    ) {
+        let regexp = RegExpBuilder::from(&test_cases)
+            .with_conversion_of_words()
+            .with_conversion_of_non_words()
+            .with_dialect(Dialect::PosixExtended)
+            .build();
+        assert_that_regexp_is_correct(regexp, expected_output, &test_cases);
+    }
+
+    #[rstest(test_cases, expected_output,
+        case(vec!["ababx("], "^\\(ab\\)\\{2\\}x($"),
+        case(vec!["a", "ab"], "^ab\\{0,1\\}$"),
+        case(vec!["a+?.*{}|"], "^a+?\\.\\*{}|$"),
+    )]
+    fn succeeds_with_posix_basic_dialect(test_cases: Vec<&str>, expected_output: &str) {
+        let regexp = RegExpBuilder::from(&test_cases)
+            .with_conversion_of_repetitions()
+            .with_dialect(Dialect::PosixBasic)
+            .build();
+        assert_that_regexp_is_correct(regexp, expected_output, &test_cases);
+    }
+
+    #[test]
+    fn fails_with_alternation_in_posix_basic_dialect() {
+        let result = RegExpBuilder::from(&["abc", "def"])
+            .with_dialect(Dialect::PosixBasic)
+            .try_build();
+        assert_eq!(
+            result,
+            Err(GrexError::UnsupportedFeature {
+                feature: "Alternation",
+                dialect: Dialect::PosixBasic
+            })
+        );
+    }
+
+    #[rstest(dialect, case(Dialect::PosixExtended), case(Dialect::PosixBasic))]
+    fn fails_with_case_insensitive_matching_in_posix_dialects(dialect: Dialect) {
+        let result = RegExpBuilder::from(&["a"])
+            .with_case_insensitive_matching()
+            .with_dialect(dialect)
+            .try_build();
+        assert_eq!(
+            result,
+            Err(GrexError::UnsupportedFeature {
+                feature: "Case-insensitive matching",
+                dialect
+            })
+        );
+    }
+
+    #[rstest(dialect, case(Dialect::PosixExtended), case(Dialect::PosixBasic))]
+    fn fails_with_escaping_of_non_ascii_chars_in_posix_dialects(dialect: Dialect) {
+        let result = RegExpBuilder::from(&["ä"])
+            .with_escaping_of_non_ascii_chars(false)
+            .with_dialect(dialect)
+            .try_build();
+        assert_eq!(
+            result,
+            Err(GrexError::UnsupportedFeature {
+                feature: "Escaping of non-ASCII characters",
+                dialect
+            })
+        );
+    }
+
+    #[rstest(
+        dialect,
+        case(Dialect::EcmaScript),
+        case(Dialect::Go),
+        case(Dialect::PosixExtended),
+        case(Dialect::PosixBasic)
+    )]
+    fn fails_with_verbose_mode(dialect: Dialect) {
+        let result = RegExpBuilder::from(&["a"])
+            .with_verbose_mode()
+            .with_dialect(dialect)
+            .try_build();
+        assert_eq!(
+            result,
+            Err(GrexError::UnsupportedFeature {
+                feature: "Verbose mode",
+                dialect
+            })
+        );
+    }
+}
+
+mod syntax_tree {
+    use super::*;
+    use std::collections::BTreeSet;
+
+    fn literal(s: &str) -> Ast {
+        Ast::Literal(s.to_string())
+    }
+
+    fn repeat(node: Ast, min: u32, max: Option<u32>) -> Ast {
+        Ast::Repeat {
+            node: Box::new(node),
+            min,
+            max,
+        }
+    }
+
+    #[test]
+    fn succeeds_with_single_literal() {
+        let ast = RegExpBuilder::from(&["abc"]).build_ast();
+        assert_eq!(ast, literal("abc"));
+    }
+
+    #[test]
+    fn succeeds_with_empty_literal() {
+        let ast = RegExpBuilder::from(&[""]).build_ast();
+        assert_eq!(ast, literal(""));
+    }
+
+    #[test]
+    fn succeeds_with_alternation_and_character_class() {
+        let ast = RegExpBuilder::from(&["abc", "abd", "x"]).build_ast();
+        assert_eq!(
+            ast,
+            Ast::Alternation(vec![
+                Ast::Concat(vec![
+                    literal("ab"),
+                    Ast::Class(CharClass::Set(BTreeSet::from(['c', 'd'])))
+                ]),
+                literal("x")
+            ])
+        );
+    }
+
+    #[test]
+    fn succeeds_with_optional_nodes() {
+        let ast = RegExpBuilder::from(&["a", "aa", "aaa"]).build_ast();
+        assert_eq!(
+            ast,
+            Ast::Concat(vec![
+                literal("a"),
+                repeat(
+                    Ast::Concat(vec![literal("a"), repeat(literal("a"), 0, Some(1))]),
+                    0,
+                    Some(1)
+                )
+            ])
+        );
+    }
+
+    #[test]
+    fn succeeds_with_repetitions() {
+        let ast = RegExpBuilder::from(&["abab", "ababab"])
+            .with_conversion_of_repetitions()
+            .build_ast();
+        assert_eq!(ast, repeat(literal("ab"), 2, Some(3)));
+
+        let ast = RegExpBuilder::from(&["aaab"])
+            .with_conversion_of_repetitions()
+            .build_ast();
+        assert_eq!(
+            ast,
+            Ast::Concat(vec![repeat(literal("a"), 3, Some(3)), literal("b")])
+        );
+    }
+
+    #[test]
+    fn succeeds_with_converted_character_classes() {
+        let ast = RegExpBuilder::from(&["a1", "b 2"])
+            .with_conversion_of_digits()
+            .with_conversion_of_whitespace()
+            .build_ast();
+        assert_eq!(
+            ast,
+            Ast::Concat(vec![
+                Ast::Alternation(vec![
+                    Ast::Concat(vec![literal("b"), Ast::Class(CharClass::Whitespace)]),
+                    literal("a")
+                ]),
+                Ast::Class(CharClass::Digit)
+            ])
+        );
+
+        let ast = RegExpBuilder::from(&["ab"])
+            .with_conversion_of_non_words()
+            .with_conversion_of_words()
+            .build_ast();
+        assert_eq!(
+            ast,
+            Ast::Concat(vec![
+                Ast::Class(CharClass::Word),
+                Ast::Class(CharClass::Word)
+            ])
+        );
+    }
+
+    #[test]
+    fn succeeds_with_unicode_property_classes() {
+        let ast = RegExpBuilder::from(&["Aα"])
+            .with_conversion_of_general_categories()
+            .build_ast();
+        assert_eq!(
+            ast,
+            Ast::Concat(vec![
+                Ast::Class(CharClass::GeneralCategory("Lu".to_string())),
+                Ast::Class(CharClass::GeneralCategory("Ll".to_string()))
+            ])
+        );
+
+        let ast = RegExpBuilder::from(&["α1"])
+            .with_conversion_of_scripts()
+            .build_ast();
+        assert_eq!(
+            ast,
+            Ast::Concat(vec![
+                Ast::Class(CharClass::Script("Greek".to_string())),
+                literal("1")
+            ])
+        );
+    }
+
+    #[test]
+    fn succeeds_with_case_insensitive_char_classes() {
+        let ast = RegExpBuilder::from(&["Hello", "hello"])
+            .with_case_insensitive_char_classes()
+            .build_ast();
+        assert_eq!(
+            ast,
+            Ast::Concat(vec![
+                Ast::Class(CharClass::Set(BTreeSet::from(['h', 'H']))),
+                literal("ello")
+            ])
+        );
+    }
+
+    #[test]
+    fn keeps_special_characters_unescaped() {
+        let ast = RegExpBuilder::from(&["a.b", "\\d"]).build_ast();
+        assert_eq!(ast, Ast::Alternation(vec![literal("a.b"), literal("\\d")]));
+    }
+
+    #[test]
+    fn does_not_depend_on_dialect() {
+        let test_cases = vec!["a1", "b22"];
+        let ast = RegExpBuilder::from(&test_cases)
+            .with_conversion_of_digits()
+            .build_ast();
+        let dialect_ast = RegExpBuilder::from(&test_cases)
+            .with_conversion_of_digits()
+            .with_dialect(Dialect::Pcre)
+            .build_ast();
+        assert_eq!(ast, dialect_ast);
+    }
+
+    #[test]
+    fn fails_with_conflicting_test_cases() {
+        let result = RegExpBuilder::from(&["a"])
+            .with_negative_examples(&["a"])
+            .try_build_ast();
+        assert_eq!(result, Err(GrexError::ConflictingTestCases));
+    }
+}
+
+mod explanation {
+    use super::*;
+
+    #[test]
+    fn succeeds_with_alternation_and_repetition() {
+        let explanation = RegExpBuilder::from(&["abcabc", "xyz", "a1", "a22"])
+            .with_conversion_of_repetitions()
+            .explain();
+        assert_eq!(
+            explanation,
+            indoc!(
+                r#"
+                the start of the string
+                  one of the following alternatives:
+                    the text "xyz"
+                    or
+                    the character "a"
+                    one of the following alternatives:
+                      the character "1"
+                      or
+                      the character "2", exactly 2 times
+                    or
+                    the text "abc", exactly 2 times
+                the end of the string"#
+            )
+        );
+    }
+
+    #[test]
+    fn succeeds_with_character_classes() {
+        let explanation = RegExpBuilder::from(&["a", "b", "c", "x", "\t", "1 "])
+            .with_conversion_of_digits()
+            .without_anchors()
+            .explain();
+        assert_eq!(
+            explanation,
+            [
+                "  one of the following alternatives:",
+                "    a digit",
+                "    the character \" \"",
+                "    or",
+                "    one of the characters \"\\t\", \"a\" to \"c\", \"x\"",
+            ]
+            .join("\n")
+        );
+    }
+
+    #[test]
+    fn succeeds_with_unicode_property_classes() {
+        let explanation = RegExpBuilder::from(&["A!"])
+            .with_conversion_of_general_categories()
+            .with_conversion_of_scripts()
+            .with_case_insensitive_matching()
+            .without_anchors()
+            .explain();
+        assert_eq!(
+            explanation,
+            [
+                "matching letters case-insensitively",
+                "  a character of the Latin script",
+                "  a character of the general category Po",
+            ]
+            .join("\n")
+        );
+    }
+
+    #[test]
+    fn succeeds_with_nested_repetition() {
+        let explanation = RegExpBuilder::from(&["a", "aa", "aaa"])
+            .with_case_insensitive_matching()
+            .explain();
+        assert_eq!(
+            explanation,
+            indoc!(
+                r#"
+                matching letters case-insensitively
+                the start of the string
+                  the character "a"
+                  the following, optionally:
+                    the character "a"
+                    the character "a", optionally
+                the end of the string"#
+            )
+        );
+    }
+
+    #[test]
+    fn fails_with_conflicting_test_cases() {
+        let result = RegExpBuilder::from(&["a"])
+            .with_negative_examples(&["a"])
+            .try_explain();
+        assert_eq!(result, Err(GrexError::ConflictingTestCases));
+    }
+}
+
+mod report {
+    use super::*;
+
+    #[test]
+    fn succeeds_with_generalized_regexp() {
+        let report = RegExpBuilder::from(&["a", "aa", "aaa", "aa"]).build_report();
+        assert_eq!(report.regexp(), "^a(?:aa?)?$");
+        assert_eq!(report.test_case_count(), 3);
+        assert_eq!(report.dfa_state_count(), 4);
+        assert_eq!(report.minimized_dfa_state_count(), 4);
+        assert!(!report.is_fallback_taken());
+    }
+
+    #[test]
+    fn succeeds_with_minimized_dfa() {
+        let report = RegExpBuilder::from(&["abc", "xbc"]).build_report();
+        assert_eq!(report.regexp(), "^[ax]bc$");
+        assert_eq!(report.dfa_state_count(), 7);
+        assert_eq!(report.minimized_dfa_state_count(), 4);
+    }
+
+    #[test]
+    fn succeeds_with_fallback_to_alternation() {
+        let report = RegExpBuilder::from(&["1", "1a"])
+            .with_negative_examples(&["aa"])
+            .with_conversion_of_words()
+            .without_anchors()
+            .build_report();
+        assert_eq!(report.regexp(), "(?:1a|1)");
+        assert!(report.is_fallback_taken());
+    }
+
+    #[test]
+    fn succeeds_with_explanation() {
+        let mut builder = RegExpBuilder::from(&["abcabc", "xyz", "a1", "a22"])
+            .with_conversion_of_repetitions()
+            .clone();
+        assert_eq!(builder.build_report().explanation(), builder.explain());
+    }
+
+    #[test]
+    fn succeeds_with_json() {
+        let report = RegExpBuilder::from(&["a\"b"])
+            .with_dialect(Dialect::Go)
+            .build_report();
+        let json = report.to_json();
+        assert!(json.starts_with("{\"regexp\":\"^a\\\"b$\",\"config\":{\"dialect\":\"Go\","));
+        assert!(json.ends_with(
+            "\"test_case_count\":1,\"dfa_state_count\":4,\"minimized_dfa_state_count\":4,\"is_fallback_taken\":false,\"generalizations\":[]}"
+        ));
+    }
+
+    #[test]
+    fn fails_with_conflicting_test_cases() {
+        let result = RegExpBuilder::from(&["a"])
+            .with_negative_examples(&["a"])
+            .try_build_report();
+        assert_eq!(result, Err(GrexError::ConflictingTestCases));
+    }
+}
+
 fn assert_that_regexp_is_correct(regexp: String, expected_output: &str, test_cases: &[&str]) {
     assert_eq!(
         regexp, expected_output,
@@ -2230,3 +3743,18 @@ fn assert_that_regexp_matches_test_cases(expected_output: &str, test_cases: Vec<
         );
     }
 }
+
+fn assert_that_regexp_does_not_match_negative_test_cases(
+    expected_output: &str,
+    negative_test_cases: Vec<&str>,
+) {
+    let regexp = Regex::new(expected_output).unwrap();
+    for negative_test_case in negative_test_cases {
+        assert!(
+            !regexp.is_match(negative_test_case),
+            "expression '{}' matches negative test case '{}'",
+            expected_output,
+            negative_test_case
+        );
+    }
+}
@@ -14,10 +14,13 @@
 # limitations under the License.
 
 import inspect
+import io
 import pytest
 import re
 
-from grex import RegExpBuilder
+from concurrent.futures import ThreadPoolExecutor
+
+from grex import RegExpBuilder, RegExpMetadata
 
 
 @pytest.mark.parametrize(
@@ -279,6 +282,33 @@ def test_conversion_of_non_words(test_cases, expected_pattern):
         assert re.match(pattern, test_case)
 
 
+@pytest.mark.parametrize(
+    "test_cases,expected_pattern",
+    [
+        pytest.param(["Ab", "c!"], "^(?:\\p{Lu}\\p{Ll}|\\p{Ll}\\p{Po})$"),
+    ]
+)
+def test_conversion_of_general_categories(test_cases, expected_pattern):
+    pattern = (RegExpBuilder.from_test_cases(test_cases)
+               .with_conversion_of_general_categories()
+               .build())
+    assert pattern == expected_pattern
+
+
+@pytest.mark.parametrize(
+    "test_cases,expected_pattern",
+    [
+        pytest.param(["αβ", "漢字"], "^(?:\\p{Greek}\\p{Greek}|\\p{Han}\\p{Han})$"),
+        pytest.param(["α1"], "^\\p{Greek}1$"),
+    ]
+)
+def test_conversion_of_scripts(test_cases, expected_pattern):
+    pattern = (RegExpBuilder.from_test_cases(test_cases)
+               .with_conversion_of_scripts()
+               .build())
+    assert pattern == expected_pattern
+
+
 @pytest.mark.parametrize(
     "test_cases,expected_pattern",
     [
@@ -338,3 +368,383 @@ def test_error_for_invalid_minimum_substring_length():
         exception_info.value.args[0] ==
         "Minimum substring length must be greater than zero"
     )
+
+
+@pytest.mark.parametrize(
+    "test_cases,expected_pattern",
+    [
+        pytest.param(["abc", "abd", "abe"], "^ab[c-e]\\Z"),
+        pytest.param(["My ♥ and 💩 is yours."], "^My ♥ and 💩 is yours\\.\\Z"),
+    ]
+)
+def test_compile(test_cases, expected_pattern):
+    pattern = RegExpBuilder.from_test_cases(test_cases).compile()
+    assert isinstance(pattern, re.Pattern)
+    assert pattern.pattern == expected_pattern
+    assert pattern.flags == re.UNICODE
+    for test_case in test_cases:
+        assert pattern.match(test_case)
+        assert not pattern.match(test_case + "\n")
+
+
+@pytest.mark.parametrize(
+    "test_cases,expected_pattern",
+    [
+        pytest.param(["ABC", "zBC", "abc", "AbC", "aBc"], "^[az]bc\\Z"),
+    ]
+)
+def test_compile_with_case_insensitive_matching(test_cases, expected_pattern):
+    pattern = (RegExpBuilder.from_test_cases(test_cases)
+               .with_case_insensitive_matching()
+               .compile())
+    assert pattern.pattern == expected_pattern
+    assert pattern.flags == re.IGNORECASE | re.UNICODE
+    for test_case in test_cases:
+        assert pattern.match(test_case)
+
+
+@pytest.mark.parametrize(
+    "test_cases,expected_pattern",
+    [
+        pytest.param(
+            ["[a-z]", "(d,e,f)"],
+            inspect.cleandoc("""
+                ^
+                  (?:
+                    \\(d,e,f\\)
+                    |
+                    \\[a\\-z\\]
+                  )
+                \\Z
+                """)
+        ),
+    ]
+)
+def test_compile_with_verbose_mode(test_cases, expected_pattern):
+    pattern = (RegExpBuilder.from_test_cases(test_cases)
+               .with_verbose_mode()
+               .compile())
+    assert pattern.pattern == expected_pattern
+    assert pattern.flags == re.VERBOSE | re.UNICODE
+    for test_case in test_cases:
+        assert pattern.match(test_case)
+
+
+@pytest.mark.parametrize(
+    "test_cases,expected_pattern",
+    [
+        pytest.param(["My ♥ and 💩 is yours."], "^My \\u2665 and \\U0001f4a9 is yours\\.\\Z"),
+    ]
+)
+def test_compile_with_escaping(test_cases, expected_pattern):
+    pattern = (RegExpBuilder.from_test_cases(test_cases)
+               .with_escaping_of_non_ascii_chars(use_surrogate_pairs=False)
+               .compile())
+    assert pattern.pattern == expected_pattern
+    for test_case in test_cases:
+        assert pattern.match(test_case)
+
+
+def test_build_with_metadata():
+    metadata = (RegExpBuilder.from_test_cases(["ABC", "abc", "aBc"])
+                .with_case_insensitive_matching()
+                .build_with_metadata())
+    assert isinstance(metadata, RegExpMetadata)
+    assert metadata.pattern == "^abc\\Z"
+    assert metadata.flags == re.IGNORECASE
+    assert metadata.test_case_count == 1
+    assert metadata.dfa_state_count == 4
+    assert metadata.minimized_dfa_state_count == 4
+    assert not metadata.is_fallback_taken
+    assert metadata.generalizations == []
+    assert re.compile(metadata.pattern, metadata.flags).match("AbC")
+
+
+def test_metadata_is_immutable():
+    builder = RegExpBuilder.from_test_cases(["abc"])
+    metadata = builder.build_with_metadata()
+    assert metadata == builder.build_with_metadata()
+    with pytest.raises(AttributeError):
+        metadata.pattern = "^xyz\\Z"
+
+
+@pytest.mark.parametrize(
+    "test_cases,expected_pattern,expected_generalizations",
+    [
+        pytest.param(["abc", "abd"], "^ab[cd]\\Z", []),
+        pytest.param(
+            ["a1", "b22", "c333", "d4444", "e55555"],
+            "^\\w\\d{1,5}\\Z",
+            ["repetitions", "digits", "words"]
+        ),
+    ]
+)
+def test_maximum_length(test_cases, expected_pattern, expected_generalizations):
+    metadata = (RegExpBuilder.from_test_cases(test_cases)
+                .with_max_length(20)
+                .build_with_metadata())
+    assert metadata.pattern == expected_pattern
+    assert metadata.generalizations == expected_generalizations
+    for test_case in test_cases:
+        assert re.match(metadata.pattern, test_case)
+
+
+@pytest.mark.parametrize(
+    "test_cases,negative_test_cases,expected_pattern",
+    [
+        pytest.param(["a1", "b2"], ["c3"], "^[ab]\\d$"),
+    ]
+)
+def test_negative_examples(test_cases, negative_test_cases, expected_pattern):
+    pattern = (RegExpBuilder.from_test_cases(test_cases)
+               .with_negative_examples(negative_test_cases)
+               .with_conversion_of_digits()
+               .with_conversion_of_words()
+               .build())
+    assert pattern == expected_pattern
+    for test_case in test_cases:
+        assert re.match(pattern, test_case)
+    for negative_test_case in negative_test_cases:
+        assert not re.match(pattern, negative_test_case)
+
+
+@pytest.mark.parametrize(
+    "test_cases,expected_pattern",
+    [
+        pytest.param(["1980", "2029"], "^(?:19[89][0-9]|20[0-2][0-9])$"),
+    ]
+)
+def test_conversion_of_numeric_ranges(test_cases, expected_pattern):
+    pattern = (RegExpBuilder.from_test_cases(test_cases)
+               .with_conversion_of_numeric_ranges()
+               .build())
+    assert pattern == expected_pattern
+    for test_case in test_cases:
+        assert re.match(pattern, test_case)
+
+
+@pytest.mark.parametrize(
+    "test_cases,tolerance,expected_pattern",
+    [
+        pytest.param(["a1x", "b2x", "c3x"], 2, "^(?:[ab][12]|c3)x$"),
+        pytest.param(["a1x", "b2x", "c3x"], 3, "^[a-c][1-3]x$"),
+    ]
+)
+def test_positional_char_classes(test_cases, tolerance, expected_pattern):
+    pattern = (RegExpBuilder.from_test_cases(test_cases)
+               .with_positional_char_classes()
+               .with_positional_char_class_tolerance(tolerance)
+               .build())
+    assert pattern == expected_pattern
+    for test_case in test_cases:
+        assert re.match(pattern, test_case)
+
+
+@pytest.mark.parametrize(
+    "test_cases,expected_pattern",
+    [
+        pytest.param(["Hello", "hello"], "^[hH]ello$"),
+    ]
+)
+def test_case_insensitive_char_classes(test_cases, expected_pattern):
+    pattern = (RegExpBuilder.from_test_cases(test_cases)
+               .with_case_insensitive_char_classes()
+               .build())
+    assert pattern == expected_pattern
+    for test_case in test_cases:
+        assert re.match(pattern, test_case)
+
+
+def test_error_for_invalid_positional_char_class_tolerance():
+    with pytest.raises(ValueError) as exception_info:
+        RegExpBuilder.from_test_cases(["abcd"]).with_positional_char_class_tolerance(0)
+    assert (
+        exception_info.value.args[0] ==
+        "Tolerance of positional character classes must be greater than zero"
+    )
+
+
+def test_error_for_invalid_maximum_length():
+    with pytest.raises(ValueError) as exception_info:
+        RegExpBuilder.from_test_cases(["abcd"]).with_max_length(0)
+    assert (
+        exception_info.value.args[0] ==
+        "Maximum length of the regular expression must be greater than zero"
+    )
+
+
+def test_error_for_exceeded_maximum_length():
+    with pytest.raises(ValueError) as exception_info:
+        RegExpBuilder.from_test_cases(["abc", "def"]).with_max_length(5).compile()
+    assert (
+        exception_info.value.args[0] ==
+        "The shortest regular expression found has 8 characters and exceeds the maximum length of 5"
+    )
+
+
+def test_error_for_conversion_of_general_categories_with_compile():
+    with pytest.raises(ValueError) as exception_info:
+        RegExpBuilder.from_test_cases(["abc"]).with_conversion_of_general_categories().compile()
+    assert (
+        exception_info.value.args[0] ==
+        "Conversion of general categories is not supported by the Python regex dialect"
+    )
+
+
+def test_error_for_conversion_of_scripts_with_compile():
+    with pytest.raises(ValueError) as exception_info:
+        RegExpBuilder.from_test_cases(["abc"]).with_conversion_of_scripts().compile()
+    assert (
+        exception_info.value.args[0] ==
+        "Conversion of scripts is not supported by the Python regex dialect"
+    )
+
+
+def test_error_for_conflicting_test_cases():
+    with pytest.raises(ValueError) as exception_info:
+        RegExpBuilder.from_test_cases(["abc"]).with_negative_examples(["abc"]).build()
+    assert (
+        exception_info.value.args[0] ==
+        "Some negative test cases cannot be excluded because they are matched by the test cases themselves"
+    )
+
+
+@pytest.mark.parametrize(
+    "test_cases,expected_pattern",
+    [
+        pytest.param(("abc", "abd", "abe"), "^ab[c-e]$"),
+        pytest.param({"abc", "abd", "abe"}, "^ab[c-e]$"),
+        pytest.param((test_case for test_case in ["abc", "abd", "abe"]), "^ab[c-e]$"),
+        pytest.param(map(str, range(1, 4)), "^[1-3]$"),
+        pytest.param(iter(["abc", "abc", "abd"]), "^ab[cd]$"),
+    ]
+)
+def test_iterables(test_cases, expected_pattern):
+    pattern = RegExpBuilder.from_test_cases(test_cases).build()
+    assert pattern == expected_pattern
+
+
+@pytest.mark.parametrize(
+    "test_cases,encoding,expected_pattern",
+    [
+        pytest.param([b"caf\xc3\xa9", bytearray(b"abc")], "utf-8", "^(?:café|abc)$"),
+        pytest.param([b"caf\xe9", "abc"], "latin-1", "^(?:café|abc)$"),
+        pytest.param([b"caf\xe9"], "ISO-8859-1", "^café$"),
+    ]
+)
+def test_bytes(test_cases, encoding, expected_pattern):
+    pattern = RegExpBuilder.from_test_cases(test_cases, encoding=encoding).build()
+    assert pattern == expected_pattern
+
+
+@pytest.mark.parametrize(
+    "file,expected_pattern",
+    [
+        pytest.param(io.StringIO("a\nbb\nccc"), "^(?:ccc|bb|a)$"),
+        pytest.param(io.StringIO("a\nbb\nccc\n"), "^(?:ccc|bb|a)$"),
+        pytest.param(io.BytesIO(b"a\r\nbb\r\nccc\r\n"), "^(?:ccc|bb|a)$"),
+    ]
+)
+def test_file_objects(file, expected_pattern):
+    pattern = RegExpBuilder.from_test_cases(file).build()
+    assert pattern == expected_pattern
+
+
+def test_negative_examples_from_iterable():
+    pattern = (RegExpBuilder.from_test_cases(["a1", "b2"])
+               .with_negative_examples(test_case for test_case in [b"c3"])
+               .with_conversion_of_digits()
+               .with_conversion_of_words()
+               .build())
+    assert pattern == "^[ab]\\d$"
+
+
+@pytest.mark.parametrize(
+    "test_cases,expected_message",
+    [
+        pytest.param("abc", "Test cases must be given as an iterable, not as a single string"),
+        pytest.param(b"abc", "Test cases must be given as an iterable, not as a single string"),
+        pytest.param(["abc", 1], "Test case 2 must be of type str, bytes or bytearray, not int"),
+    ]
+)
+def test_error_for_invalid_test_case_types(test_cases, expected_message):
+    with pytest.raises(TypeError) as exception_info:
+        RegExpBuilder.from_test_cases(test_cases)
+    assert exception_info.value.args[0] == expected_message
+
+
+def test_error_for_invalid_utf8():
+    with pytest.raises(ValueError) as exception_info:
+        RegExpBuilder.from_test_cases(["abc", b"caf\xe9"])
+    assert (
+        exception_info.value.args[0] ==
+        "Test case 2 is not valid UTF-8, use encoding 'latin-1' to decode it"
+    )
+
+
+def test_error_for_unsupported_encoding():
+    with pytest.raises(ValueError) as exception_info:
+        RegExpBuilder.from_test_cases([b"abc"], encoding="utf-16")
+    assert (
+        exception_info.value.args[0] ==
+        "Encoding 'utf-16' is not supported, use either 'utf-8' or 'latin-1'"
+    )
+
+
+def test_error_for_empty_iterable():
+    with pytest.raises(ValueError) as exception_info:
+        RegExpBuilder.from_test_cases(test_case for test_case in [])
+    assert (
+        exception_info.value.args[0] ==
+        "No test cases have been provided for regular expression generation"
+    )
+
+
+def test_build_many():
+    patterns = (RegExpBuilder.from_test_cases(["a"])
+                .with_conversion_of_digits()
+                .build_many([["a1", "a2"], ("b3",), (test_case for test_case in ["c"])]))
+    assert patterns == ["^a\\d$", "^b\\d$", "^c$"]
+
+
+def test_build_many_without_test_case_collections():
+    assert RegExpBuilder.from_test_cases(["a"]).build_many([]) == []
+
+
+def test_build_many_keeps_order():
+    test_case_collections = [[str(n), str(n + 1)] for n in range(0, 100, 2)]
+    patterns = RegExpBuilder.from_test_cases(["a"]).build_many(test_case_collections)
+    assert patterns == [
+        RegExpBuilder.from_test_cases(test_cases).build()
+        for test_cases in test_case_collections
+    ]
+
+
+def test_concurrent_builds():
+    test_case_collections = [[f"{n}a", f"{n}b", f"{n}c"] for n in range(50)]
+    with ThreadPoolExecutor(max_workers=8) as executor:
+        patterns = list(executor.map(
+            lambda test_cases: RegExpBuilder.from_test_cases(test_cases).build(),
+            test_case_collections
+        ))
+    assert patterns == [f"^{n}[a-c]$" for n in range(50)]
+
+
+def test_error_for_empty_test_case_collection():
+    with pytest.raises(ValueError) as exception_info:
+        RegExpBuilder.from_test_cases(["a"]).build_many([["abc"], []])
+    assert (
+        exception_info.value.args[0] ==
+        "No test cases have been provided for regular expression generation"
+    )
+
+
+def test_error_for_conflicting_test_cases_in_build_many():
+    with pytest.raises(ValueError) as exception_info:
+        (RegExpBuilder.from_test_cases(["a"])
+         .with_negative_examples(["abc"])
+         .build_many([["abd"], ["abc"]]))
+    assert (
+        exception_info.value.args[0] ==
+        "Some negative test cases cannot be excluded because they are matched by the test cases themselves"
+    )
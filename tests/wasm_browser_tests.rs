@@ -16,20 +16,70 @@
 
 #![cfg(target_family = "wasm")]
 
-use grex::WasmRegExpBuilder;
-use indoc::indoc;
-use wasm_bindgen::JsValue;
+use grex::{BuildResult, RegExpBuilderOptions, WasmRegExpBuilder};
+use js_sys::{Array, Object, Reflect, RegExp};
+use wasm_bindgen::{JsCast, JsValue};
 use wasm_bindgen_test::*;
 
 wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
 
+const TEST_CASES: [&str; 2] = ["abc  ", "123"];
+
+fn test_cases(test_cases: &[&str]) -> Box<[JsValue]> {
+    test_cases.iter().map(|&it| JsValue::from(it)).collect()
+}
+
+fn options(entries: &[(&str, JsValue)]) -> Option<RegExpBuilderOptions> {
+    let options = Object::new();
+    for (name, value) in entries {
+        Reflect::set(&options, &JsValue::from(*name), value).unwrap();
+    }
+    Some(options.unchecked_into())
+}
+
+fn result_property(result: &BuildResult, name: &str) -> JsValue {
+    Reflect::get(result, &JsValue::from(name)).unwrap()
+}
+
+/// Compiles the regular expression with the `RegExp` engine of JavaScript
+/// and verifies that it matches all test cases and none of the negative ones.
+fn assert_round_trip(
+    builder: &mut WasmRegExpBuilder,
+    test_cases: &[&str],
+    negative_test_cases: &[&str],
+) {
+    let result = builder.buildResult().unwrap();
+    let pattern = result_property(&result, "pattern").as_string().unwrap();
+    let flags = result_property(&result, "flags").as_string().unwrap();
+
+    let regexp = RegExp::new(&pattern, &flags);
+    for test_case in test_cases {
+        assert!(
+            regexp.test(test_case),
+            "/{}/{} does not match '{}'",
+            pattern,
+            flags,
+            test_case
+        );
+    }
+    for negative_test_case in negative_test_cases {
+        assert!(
+            !regexp.test(negative_test_case),
+            "/{}/{} matches '{}'",
+            pattern,
+            flags,
+            negative_test_case
+        );
+    }
+}
+
 #[wasm_bindgen_test]
 fn assert_regexpbuilder_succeeds() {
-    let test_cases = Box::new([JsValue::from("hello"), JsValue::from("world")]);
-    let builder = WasmRegExpBuilder::from(test_cases);
+    let builder = WasmRegExpBuilder::from(test_cases(&["hello", "world"]));
     assert!(builder.is_ok());
-    let regexp = builder.unwrap().build();
-    assert_eq!(regexp, "^(?:hello|world)$");
+    let mut builder = builder.unwrap();
+    assert_eq!(builder.build().unwrap(), "^(?:hello|world)$");
+    assert_round_trip(&mut builder, &["hello", "world"], &["hello world"]);
 }
 
 #[wasm_bindgen_test]
@@ -45,175 +95,192 @@ fn assert_regexpbuilder_fails() {
 
 #[wasm_bindgen_test]
 fn test_conversion_of_digits() {
-    let test_cases = Box::new([JsValue::from("abc  "), JsValue::from("123")]);
-    let regexp = WasmRegExpBuilder::from(test_cases)
+    let mut builder = WasmRegExpBuilder::from(test_cases(&TEST_CASES))
         .unwrap()
-        .withConversionOfDigits()
-        .build();
-    assert_eq!(regexp, "^(?:abc  |\\d\\d\\d)$");
+        .withConversionOfDigits();
+    assert_eq!(builder.build().unwrap(), "^(?:abc  |\\d\\d\\d)$");
+    assert_round_trip(&mut builder, &TEST_CASES, &["١٢٣"]);
 }
 
 #[wasm_bindgen_test]
 fn test_conversion_of_non_digits() {
-    let test_cases = Box::new([JsValue::from("abc  "), JsValue::from("123")]);
-    let regexp = WasmRegExpBuilder::from(test_cases)
+    let mut builder = WasmRegExpBuilder::from(test_cases(&TEST_CASES))
         .unwrap()
-        .withConversionOfNonDigits()
-        .build();
-    assert_eq!(regexp, "^(?:\\D\\D\\D\\D\\D|123)$");
+        .withConversionOfNonDigits();
+    assert_eq!(builder.build().unwrap(), "^(?:\\D\\D\\D\\D\\D|123)$");
+    assert_round_trip(&mut builder, &TEST_CASES, &["abc"]);
 }
 
 #[wasm_bindgen_test]
 fn test_conversion_of_whitespace() {
-    let test_cases = Box::new([JsValue::from("abc  "), JsValue::from("123")]);
-    let regexp = WasmRegExpBuilder::from(test_cases)
+    let mut builder = WasmRegExpBuilder::from(test_cases(&TEST_CASES))
         .unwrap()
-        .withConversionOfWhitespace()
-        .build();
-    assert_eq!(regexp, "^(?:abc\\s\\s|123)$");
+        .withConversionOfWhitespace();
+    assert_eq!(builder.build().unwrap(), "^(?:abc\\s\\s|123)$");
+    assert_round_trip(&mut builder, &TEST_CASES, &["abc"]);
 }
 
 #[wasm_bindgen_test]
 fn test_conversion_of_non_whitespace() {
-    let test_cases = Box::new([JsValue::from("abc  "), JsValue::from("123")]);
-    let regexp = WasmRegExpBuilder::from(test_cases)
+    let mut builder = WasmRegExpBuilder::from(test_cases(&TEST_CASES))
         .unwrap()
-        .withConversionOfNonWhitespace()
-        .build();
-    assert_eq!(regexp, "^\\S\\S\\S(?:  )?$");
+        .withConversionOfNonWhitespace();
+    assert_eq!(builder.build().unwrap(), "^\\S\\S\\S(?:  )?$");
+    assert_round_trip(&mut builder, &TEST_CASES, &["a c"]);
 }
 
 #[wasm_bindgen_test]
 fn test_conversion_of_words() {
-    let test_cases = Box::new([JsValue::from("abc  "), JsValue::from("123")]);
-    let regexp = WasmRegExpBuilder::from(test_cases)
+    let mut builder = WasmRegExpBuilder::from(test_cases(&TEST_CASES))
         .unwrap()
-        .withConversionOfWords()
-        .build();
-    assert_eq!(regexp, "^\\w\\w\\w(?:  )?$");
+        .withConversionOfWords();
+    assert_eq!(builder.build().unwrap(), "^\\w\\w\\w(?:  )?$");
+    assert_round_trip(&mut builder, &TEST_CASES, &["äöü"]);
 }
 
 #[wasm_bindgen_test]
 fn test_conversion_of_non_words() {
-    let test_cases = Box::new([JsValue::from("abc  "), JsValue::from("123")]);
-    let regexp = WasmRegExpBuilder::from(test_cases)
+    let mut builder = WasmRegExpBuilder::from(test_cases(&TEST_CASES))
         .unwrap()
-        .withConversionOfNonWords()
-        .build();
-    assert_eq!(regexp, "^(?:abc\\W\\W|123)$");
+        .withConversionOfNonWords();
+    assert_eq!(builder.build().unwrap(), "^(?:abc\\W\\W|123)$");
+    assert_round_trip(&mut builder, &TEST_CASES, &["abc__"]);
 }
 
 #[wasm_bindgen_test]
 fn test_conversion_of_repetitions() {
-    let test_cases = Box::new([JsValue::from("abc  "), JsValue::from("123")]);
-    let regexp = WasmRegExpBuilder::from(test_cases)
+    let mut builder = WasmRegExpBuilder::from(test_cases(&TEST_CASES))
         .unwrap()
-        .withConversionOfRepetitions()
-        .build();
-    assert_eq!(regexp, "^(?:abc {2}|123)$");
+        .withConversionOfRepetitions();
+    assert_eq!(builder.build().unwrap(), "^(?:abc {2}|123)$");
+    assert_round_trip(&mut builder, &TEST_CASES, &["abc   "]);
 }
 
 #[wasm_bindgen_test]
 fn test_case_insensitive_matching() {
-    let test_cases = Box::new([
-        JsValue::from("ABC"),
-        JsValue::from("abc  "),
-        JsValue::from("123"),
-    ]);
-    let regexp = WasmRegExpBuilder::from(test_cases)
+    let test_cases_with_upper_case = ["ABC", "abc  ", "123"];
+    let mut builder = WasmRegExpBuilder::from(test_cases(&test_cases_with_upper_case))
         .unwrap()
-        .withCaseInsensitiveMatching()
-        .build();
-    assert_eq!(regexp, "(?i)^(?:abc(?:  )?|123)$");
+        .withCaseInsensitiveMatching();
+    assert_eq!(builder.build().unwrap(), "(?i)^(?:abc(?:  )?|123)$");
+
+    let result = builder.buildResult().unwrap();
+    assert_eq!(result_property(&result, "pattern"), "^(?:abc(?:  )?|123)$");
+    assert_eq!(result_property(&result, "flags"), "i");
+    assert_round_trip(&mut builder, &test_cases_with_upper_case, &["ABD"]);
 }
 
 #[wasm_bindgen_test]
 fn test_capturing_groups() {
-    let test_cases = Box::new([JsValue::from("abc  "), JsValue::from("123")]);
-    let regexp = WasmRegExpBuilder::from(test_cases)
+    let mut builder = WasmRegExpBuilder::from(test_cases(&TEST_CASES))
         .unwrap()
-        .withCapturingGroups()
-        .build();
-    assert_eq!(regexp, "^(abc  |123)$");
+        .withCapturingGroups();
+    assert_eq!(builder.build().unwrap(), "^(abc  |123)$");
+    assert_round_trip(&mut builder, &TEST_CASES, &["abc"]);
 }
 
 #[wasm_bindgen_test]
 fn test_escaping_of_non_ascii_chars() {
-    let test_cases = Box::new([
-        JsValue::from("abc  "),
-        JsValue::from("123"),
-        JsValue::from("♥"),
-    ]);
-    let regexp = WasmRegExpBuilder::from(test_cases)
+    let test_cases_with_heart = ["abc  ", "123", "♥"];
+    let mut builder = WasmRegExpBuilder::from(test_cases(&test_cases_with_heart))
         .unwrap()
-        .withEscapingOfNonAsciiChars(false)
-        .build();
-    assert_eq!(regexp, "^(?:abc  |123|\\u{2665})$");
+        .withEscapingOfNonAsciiChars(false);
+    assert_eq!(builder.build().unwrap(), "^(?:abc  |123|\\u{2665})$");
+
+    let result = builder.buildResult().unwrap();
+    assert_eq!(
+        result_property(&result, "pattern"),
+        "^(?:abc  |123|\\u2665)$"
+    );
+    assert_eq!(result_property(&result, "flags"), "");
+    assert_round_trip(&mut builder, &test_cases_with_heart, &["♡"]);
+}
+
+#[wasm_bindgen_test]
+fn test_escaping_of_astral_code_points() {
+    let test_cases_with_astral_code_points = ["💩", "💩💩", "♥"];
+    let mut builder = WasmRegExpBuilder::from(test_cases(&test_cases_with_astral_code_points))
+        .unwrap()
+        .withConversionOfRepetitions()
+        .withEscapingOfNonAsciiChars(false);
+    assert_eq!(builder.build().unwrap(), "^(?:\\u{2665}|\\u{1f4a9}{1,2})$");
+
+    let result = builder.buildResult().unwrap();
+    assert_eq!(
+        result_property(&result, "pattern"),
+        "^(?:\\u2665|\\u{1f4a9}{1,2})$"
+    );
+    assert_eq!(result_property(&result, "flags"), "u");
+    assert_eq!(result_property(&result, "unicode"), true);
+    assert_round_trip(
+        &mut builder,
+        &test_cases_with_astral_code_points,
+        &["💩💩💩"],
+    );
+}
+
+#[wasm_bindgen_test]
+fn test_escaping_of_astral_code_points_with_surrogate_pairs() {
+    let test_cases_with_astral_code_points = ["💩", "♥"];
+    let mut builder = WasmRegExpBuilder::from(test_cases(&test_cases_with_astral_code_points))
+        .unwrap()
+        .withEscapingOfNonAsciiChars(true);
+    assert_eq!(
+        builder.build().unwrap(),
+        "^(?:\\u{2665}|\\u{d83d}\\u{dca9})$"
+    );
+
+    let result = builder.buildResult().unwrap();
+    assert_eq!(
+        result_property(&result, "pattern"),
+        "^(?:\\u2665|\\ud83d\\udca9)$"
+    );
+    assert_eq!(result_property(&result, "flags"), "");
+    assert_eq!(result_property(&result, "unicode"), false);
+    assert_round_trip(&mut builder, &test_cases_with_astral_code_points, &["💪"]);
 }
 
 #[wasm_bindgen_test]
 fn test_verbose_mode() {
-    let test_cases = Box::new([
-        JsValue::from("abc  "),
-        JsValue::from("123"),
-        JsValue::from("♥"),
-    ]);
-    let regexp = WasmRegExpBuilder::from(test_cases)
+    let mut builder = WasmRegExpBuilder::from(test_cases(&TEST_CASES))
         .unwrap()
-        .withVerboseMode()
-        .build();
+        .withVerboseMode();
     assert_eq!(
-        regexp,
-        indoc!(
-            r#"
-            (?x)
-            ^
-              (?:
-                abc\ \ 
-                |
-                123
-                |
-                ♥
-              )
-            $"#
-        )
+        builder.build().unwrap(),
+        "(?x)\n^\n  (?:\n    abc\\ \\ \n    |\n    123\n  )\n$"
     );
 }
 
 #[wasm_bindgen_test]
 fn test_without_start_anchor() {
-    let test_cases = Box::new([JsValue::from("abc  "), JsValue::from("123")]);
-    let regexp = WasmRegExpBuilder::from(test_cases)
+    let mut builder = WasmRegExpBuilder::from(test_cases(&TEST_CASES))
         .unwrap()
-        .withoutStartAnchor()
-        .build();
-    assert_eq!(regexp, "(?:abc  |123)$");
+        .withoutStartAnchor();
+    assert_eq!(builder.build().unwrap(), "(?:abc  |123)$");
+    assert_round_trip(&mut builder, &["xabc  ", "x123"], &["123x"]);
 }
 
 #[wasm_bindgen_test]
 fn test_without_end_anchor() {
-    let test_cases = Box::new([JsValue::from("abc  "), JsValue::from("123")]);
-    let regexp = WasmRegExpBuilder::from(test_cases)
+    let mut builder = WasmRegExpBuilder::from(test_cases(&TEST_CASES))
         .unwrap()
-        .withoutEndAnchor()
-        .build();
-    assert_eq!(regexp, "^(?:abc  |123)");
+        .withoutEndAnchor();
+    assert_eq!(builder.build().unwrap(), "^(?:abc  |123)");
+    assert_round_trip(&mut builder, &["abc  x", "123x"], &["x123"]);
 }
 
 #[wasm_bindgen_test]
 fn test_without_anchors() {
-    let test_cases = Box::new([JsValue::from("abc  "), JsValue::from("123")]);
-    let regexp = WasmRegExpBuilder::from(test_cases)
+    let mut builder = WasmRegExpBuilder::from(test_cases(&TEST_CASES))
         .unwrap()
-        .withoutAnchors()
-        .build();
-    assert_eq!(regexp, "(?:abc  |123)");
+        .withoutAnchors();
+    assert_eq!(builder.build().unwrap(), "(?:abc  |123)");
+    assert_round_trip(&mut builder, &["xabc  x", "x123x"], &["x12x"]);
 }
 
 #[wasm_bindgen_test]
 fn test_minimum_repetitions() {
-    let test_cases = Box::new([JsValue::from("abc  "), JsValue::from("123")]);
-    let builder = WasmRegExpBuilder::from(test_cases)
+    let builder = WasmRegExpBuilder::from(test_cases(&TEST_CASES))
         .unwrap()
         .withMinimumRepetitions(0);
     assert_eq!(
@@ -226,8 +293,7 @@ fn test_minimum_repetitions() {
 
 #[wasm_bindgen_test]
 fn test_minimum_substring_length() {
-    let test_cases = Box::new([JsValue::from("abc  "), JsValue::from("123")]);
-    let builder = WasmRegExpBuilder::from(test_cases)
+    let builder = WasmRegExpBuilder::from(test_cases(&TEST_CASES))
         .unwrap()
         .withMinimumSubstringLength(0);
     assert_eq!(
@@ -237,3 +303,184 @@ fn test_minimum_substring_length() {
         ))
     );
 }
+
+#[wasm_bindgen_test]
+fn test_from_options() {
+    let mut builder = WasmRegExpBuilder::fromOptions(
+        test_cases(&TEST_CASES),
+        options(&[
+            ("digits", JsValue::from(true)),
+            ("repetitions", JsValue::from(true)),
+            ("anchors", JsValue::from(false)),
+        ]),
+    )
+    .unwrap();
+    assert_eq!(builder.build().unwrap(), "(?:abc {2}|\\d{3})");
+    assert_round_trip(&mut builder, &TEST_CASES, &["abc 12"]);
+}
+
+#[wasm_bindgen_test]
+fn test_from_options_without_options() {
+    let mut builder = WasmRegExpBuilder::fromOptions(test_cases(&TEST_CASES), None).unwrap();
+    assert_eq!(builder.build().unwrap(), "^(?:abc  |123)$");
+    assert_round_trip(&mut builder, &TEST_CASES, &["abc"]);
+}
+
+#[wasm_bindgen_test]
+fn test_from_options_with_dialect() {
+    let mut builder = WasmRegExpBuilder::fromOptions(
+        test_cases(&["abc", "ABC"]),
+        options(&[
+            ("caseInsensitive", JsValue::from(true)),
+            ("dialect", JsValue::from("python")),
+        ]),
+    )
+    .unwrap();
+    assert_eq!(builder.build().unwrap(), "(?i)^abc\\Z");
+    assert_round_trip(&mut builder, &["abc", "ABC"], &["abd"]);
+}
+
+#[wasm_bindgen_test]
+fn test_from_options_with_ecmascript_dialect() {
+    let mut builder = WasmRegExpBuilder::fromOptions(
+        test_cases(&["abc", "ABC"]),
+        options(&[
+            ("caseInsensitive", JsValue::from(true)),
+            ("dialect", JsValue::from("ecmascript")),
+        ]),
+    )
+    .unwrap();
+    assert_eq!(builder.build().unwrap(), "/^abc$/iu");
+    assert_round_trip(&mut builder, &["abc", "ABC"], &["abd"]);
+}
+
+#[wasm_bindgen_test]
+fn test_from_options_with_negative_test_cases() {
+    let negative_test_cases = Array::of1(&JsValue::from("c3"));
+    let mut builder = WasmRegExpBuilder::fromOptions(
+        test_cases(&["a1", "b2"]),
+        options(&[
+            ("digits", JsValue::from(true)),
+            ("words", JsValue::from(true)),
+            ("negativeTestCases", negative_test_cases.into()),
+        ]),
+    )
+    .unwrap();
+    assert_eq!(builder.build().unwrap(), "^[ab]\\d$");
+    assert_round_trip(&mut builder, &["a1", "b2"], &["c3"]);
+}
+
+#[wasm_bindgen_test]
+fn test_from_options_with_numeric_ranges() {
+    let mut builder = WasmRegExpBuilder::fromOptions(
+        test_cases(&["1980", "2029"]),
+        options(&[("numericRanges", JsValue::from(true))]),
+    )
+    .unwrap();
+    assert_eq!(builder.build().unwrap(), "^(?:19[89][0-9]|20[0-2][0-9])$");
+    assert_round_trip(&mut builder, &["1980", "2000", "2029"], &["1979", "2030"]);
+}
+
+#[wasm_bindgen_test]
+fn test_from_options_with_general_categories() {
+    let mut builder = WasmRegExpBuilder::fromOptions(
+        test_cases(&["Aé", "Öb"]),
+        options(&[("generalCategories", JsValue::from(true))]),
+    )
+    .unwrap();
+    let result = builder.buildResult().unwrap();
+    assert_eq!(result_property(&result, "pattern"), "^\\p{Lu}\\p{Ll}$");
+    assert_eq!(result_property(&result, "flags"), "u");
+    assert_round_trip(&mut builder, &["Aé", "Öb", "Źż"], &["aB"]);
+}
+
+#[wasm_bindgen_test]
+fn test_from_options_fails() {
+    for (name, value, expected_error) in [
+        ("digit", JsValue::from(true), "Unknown option 'digit'"),
+        (
+            "digits",
+            JsValue::from(1),
+            "Option 'digits' must be a boolean",
+        ),
+        (
+            "minimumRepetitions",
+            JsValue::from(0),
+            "Quantity of minimum repetitions must be greater than zero",
+        ),
+        (
+            "maxLength",
+            JsValue::from(2.5),
+            "Option 'maxLength' must be a non-negative integer",
+        ),
+        (
+            "dialect",
+            JsValue::from("perl"),
+            "unknown regex dialect 'perl'",
+        ),
+        (
+            "negativeTestCases",
+            JsValue::from("c3"),
+            "Option 'negativeTestCases' must be an array of strings",
+        ),
+        (
+            "negativeTestCases",
+            Array::of2(&JsValue::from("c3"), &JsValue::from(3)).into(),
+            "Option 'negativeTestCases' must be an array of strings",
+        ),
+    ] {
+        let builder =
+            WasmRegExpBuilder::fromOptions(test_cases(&["abc"]), options(&[(name, value)]));
+        assert_eq!(builder.err(), Some(JsValue::from(expected_error)));
+    }
+}
+
+#[wasm_bindgen_test]
+fn test_build_result() {
+    let mut builder = WasmRegExpBuilder::from(test_cases(&["abc", "ABD"]))
+        .unwrap()
+        .withCaseInsensitiveMatching();
+    let result = builder.buildResult().unwrap();
+    assert_eq!(result_property(&result, "pattern"), "^ab[cd]$");
+    assert_eq!(result_property(&result, "flags"), "i");
+    assert_eq!(result_property(&result, "unicode"), false);
+    assert_round_trip(&mut builder, &["abc", "ABD", "aBc"], &["abe"]);
+}
+
+#[wasm_bindgen_test]
+fn test_build_result_with_astral_code_points() {
+    let mut builder = WasmRegExpBuilder::from(test_cases(&["💩", "💩💩"]))
+        .unwrap()
+        .withConversionOfRepetitions();
+    let result = builder.buildResult().unwrap();
+    assert_eq!(result_property(&result, "pattern"), "^💩{1,2}$");
+    assert_eq!(result_property(&result, "flags"), "u");
+    assert_eq!(result_property(&result, "unicode"), true);
+    assert_round_trip(&mut builder, &["💩", "💩💩"], &["💩💩💩"]);
+}
+
+#[wasm_bindgen_test]
+fn test_build_result_with_max_length() {
+    let test_cases_with_digits = ["a1", "b22", "c333", "d4444", "e55555"];
+    let mut builder = WasmRegExpBuilder::fromOptions(
+        test_cases(&test_cases_with_digits),
+        options(&[("maxLength", JsValue::from(20))]),
+    )
+    .unwrap();
+    assert_eq!(builder.build().unwrap(), "^\\w\\d{1,5}$");
+    assert_round_trip(&mut builder, &test_cases_with_digits, &["a123456"]);
+}
+
+#[wasm_bindgen_test]
+fn test_build_result_fails_with_verbose_mode() {
+    let result = WasmRegExpBuilder::from(test_cases(&["abc"]))
+        .unwrap()
+        .withVerboseMode()
+        .buildResult();
+    assert_eq!(
+        result.err(),
+        Some(JsValue::from(
+            "Verbose mode is not supported by the ECMAScript regex dialect"
+        ))
+    );
+}